@@ -7,7 +7,12 @@ use tic_tac_toe::models::{
     InstantiateMsg, 
     ExecuteMsg, 
     QueryMsg,
-    responses::GameResponse,
+    responses::{
+        ChannelResponse, ClaimableResponse, ClockResponse, EvaluationResponse,
+        GameResponse, HouseResponse, PauseResponse, SessionKeyResponse, SolvencyResponse,
+        SponsorshipResponse,
+    },
+    errors::ErrorCode,
     state::Game,
 };
 
@@ -22,4 +27,14 @@ fn main() {
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(Game), &out_dir);
     export_schema(&schema_for!(GameResponse), &out_dir);
+    export_schema(&schema_for!(EvaluationResponse), &out_dir);
+    export_schema(&schema_for!(HouseResponse), &out_dir);
+    export_schema(&schema_for!(ChannelResponse), &out_dir);
+    export_schema(&schema_for!(ClaimableResponse), &out_dir);
+    export_schema(&schema_for!(PauseResponse), &out_dir);
+    export_schema(&schema_for!(SessionKeyResponse), &out_dir);
+    export_schema(&schema_for!(SolvencyResponse), &out_dir);
+    export_schema(&schema_for!(SponsorshipResponse), &out_dir);
+    export_schema(&schema_for!(ClockResponse), &out_dir);
+    export_schema(&schema_for!(ErrorCode), &out_dir);
 }
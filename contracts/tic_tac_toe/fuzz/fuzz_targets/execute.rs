@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tic_tac_toe::models::state::PayoutMode;
+use tic_tac_toe_fuzz::{Harness, Step};
+
+fuzz_target!(|input: (bool, Vec<Step>)| {
+    let (pull, steps) = input;
+    let mut harness = Harness::with_payout_mode(if pull {
+        PayoutMode::PULL
+    } else {
+        PayoutMode::PUSH
+    });
+
+    for step in steps {
+        if !harness.execute(step) {
+            return;
+        }
+        harness.check_escrow();
+    }
+});
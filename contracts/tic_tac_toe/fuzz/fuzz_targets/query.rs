@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tic_tac_toe_fuzz::{FuzzQueryMsg, Harness, Step};
+
+fuzz_target!(|input: (Vec<Step>, Vec<FuzzQueryMsg>)| {
+    let (steps, queries) = input;
+    let mut harness = Harness::new();
+
+    for step in steps {
+        if !harness.execute(step) {
+            return;
+        }
+    }
+    for msg in queries.into_iter().filter_map(FuzzQueryMsg::into_msg) {
+        harness.query(msg);
+    }
+});
@@ -0,0 +1,727 @@
+use std::collections::BTreeMap;
+
+use arbitrary::Arbitrary;
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{from_binary, BankMsg, Binary, Coin, CosmosMsg, Env, OwnedDeps, Uint128};
+use cw_utils::Expiration;
+use tic_tac_toe::contract::{execute::execute, instantiate::instantiate, query::query};
+use tic_tac_toe::models::{
+    responses::{
+        ClaimableResponse, GameResponse, HouseResponse, SolvencyResponse, SponsorshipResponse,
+    },
+    state::{
+        Coord, Difficulty, Opening, Pause, PayoutMode, PlayerSymbol, SessionScope, Status,
+        TimeControl, Variant,
+    },
+    DelegatedMsg, ExecuteMsg, InstantiateMsg, QueryKey, QueryMsg, SignedMove,
+};
+
+/**
+    Senders and opponents are picked from these addresses so the
+    messages hit the same games, the last one is the contract
+    itself to play against the house.
+*/
+pub const ADDRESSES: [&str; 5] = ["host", "opponent", "admin", "session", MOCK_CONTRACT_ADDR];
+
+const DENOMS: [&str; 2] = ["token", "uluna"];
+
+fn address(index: u8) -> String {
+    String::from(ADDRESSES[index as usize % ADDRESSES.len()])
+}
+
+fn coins(amounts: &[(u8, u128)]) -> Vec<Coin> {
+    amounts
+        .iter()
+        .map(|(denom, amount)| Coin {
+            denom: String::from(DENOMS[*denom as usize % DENOMS.len()]),
+            amount: Uint128::new(*amount),
+        })
+        .collect()
+}
+
+fn symbol(x: bool) -> PlayerSymbol {
+    if x {
+        PlayerSymbol::X
+    } else {
+        PlayerSymbol::O
+    }
+}
+
+fn pause(value: u8) -> Pause {
+    match value % 4 {
+        0 => Pause::NONE,
+        1 => Pause::INVITES,
+        2 => Pause::MOVES,
+        _ => Pause::ALL,
+    }
+}
+
+fn opening((value, x, y): (u8, u8, u8)) -> Opening {
+    match value % 4 {
+        0 => Opening::Standard {},
+        1 => Opening::Pie {},
+        2 => Opening::NoCenter {},
+        _ => Opening::Cell {
+            coord: Coord { x, y },
+        },
+    }
+}
+
+fn variant((value, columns, rows): (u8, u8, u8)) -> Variant {
+    match value % 3 {
+        0 => Variant::Classic {},
+        1 => Variant::Gravity { columns, rows },
+        _ => Variant::Morris {},
+    }
+}
+
+fn difficulty(value: u8) -> Difficulty {
+    match value % 3 {
+        0 => Difficulty::RANDOM,
+        1 => Difficulty::MEDIUM,
+        _ => Difficulty::PERFECT,
+    }
+}
+
+/**
+    Signed move as (from, x, y, signature), from is
+    only set to move a piece of a MORRIS game.
+*/
+type SignedStep = (Option<(u8, u8)>, u8, u8, Vec<u8>);
+
+/**
+    Message executed by one of the addresses with random funds.
+    Besides the structured messages, `Raw` sends any JSON that
+    deserializes into an ExecuteMsg.
+*/
+#[derive(Arbitrary, Debug)]
+pub struct Step {
+    pub sender: u8,
+    pub funds: Vec<(u8, u128)>,
+    pub msg: FuzzExecuteMsg,
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum FuzzExecuteMsg {
+    Raw(String),
+    Invite {
+        x: u8,
+        y: u8,
+        host_x: bool,
+        opponent: u8,
+        opponent_stake: Option<Vec<(u8, u128)>>,
+        opening: Option<(u8, u8, u8)>,
+        time_control: Option<(u8, u8)>,
+        variant: Option<(u8, u8, u8)>,
+        practice: Option<bool>,
+    },
+    Reject {
+        as_host: bool,
+        opponent: u8,
+    },
+    Accept {
+        x: u8,
+        y: u8,
+        host: u8,
+    },
+    Swap {
+        host: u8,
+    },
+    Rematch {
+        host: u8,
+        opponent: u8,
+        coord: Option<(u8, u8)>,
+        double_or_nothing: bool,
+    },
+    Play {
+        as_host: bool,
+        x: u8,
+        y: u8,
+        opponent: u8,
+    },
+    DropPiece {
+        as_host: bool,
+        column: u8,
+        opponent: u8,
+    },
+    MovePiece {
+        as_host: bool,
+        from: (u8, u8),
+        to: (u8, u8),
+        opponent: u8,
+    },
+    FundHouse,
+    WithdrawHouse {
+        amount: Vec<(u8, u128)>,
+    },
+    UpdateHouse {
+        difficulty: Option<u8>,
+        max_exposure: Option<Vec<(u8, u128)>>,
+    },
+    Claim,
+    ClaimFor {
+        address: u8,
+    },
+    UpdatePause {
+        pause: u8,
+    },
+    SweepSurplus {
+        recipient: Option<u8>,
+    },
+    EmergencyWithdraw {
+        as_host: bool,
+        opponent: u8,
+    },
+    Sponsor {
+        host: u8,
+        opponent: u8,
+        expires: u64,
+    },
+    ReclaimSponsorship {
+        host: u8,
+        opponent: u8,
+    },
+    RegisterPubkey {
+        pubkey: Vec<u8>,
+    },
+    SettleSignedMoves {
+        host: u8,
+        opponent: u8,
+        moves: Vec<SignedStep>,
+    },
+    Resign {
+        as_host: bool,
+        opponent: u8,
+    },
+    OfferDraw {
+        as_host: bool,
+        opponent: u8,
+    },
+    CallFlag {
+        as_host: bool,
+        opponent: u8,
+    },
+    AuthorizeSessionKey {
+        session_key: u8,
+        expires: u64,
+        games: Option<Vec<u64>>,
+    },
+    RevokeSessionKey {
+        session_key: u8,
+    },
+    Delegate {
+        player: u8,
+        action: u8,
+        as_host: bool,
+        x: u8,
+        y: u8,
+        opponent: u8,
+    },
+}
+
+impl FuzzExecuteMsg {
+    pub fn into_msg(self) -> Option<ExecuteMsg> {
+        let msg = match self {
+            FuzzExecuteMsg::Raw(json) => return serde_json::from_str(&json).ok(),
+            FuzzExecuteMsg::Invite {
+                x,
+                y,
+                host_x,
+                opponent,
+                opponent_stake,
+                opening,
+                time_control,
+                variant,
+                practice,
+            } => ExecuteMsg::Invite {
+                coord: Coord { x, y },
+                host_symbol: symbol(host_x),
+                opponent: address(opponent),
+                opponent_stake: opponent_stake.map(|amount| coins(&amount)),
+                opening: opening.map(self::opening),
+                time_control: time_control.map(|(base, increment)| TimeControl {
+                    base: base.into(),
+                    increment: increment.into(),
+                }),
+                variant: variant.map(self::variant),
+                practice,
+            },
+            FuzzExecuteMsg::Reject { as_host, opponent } => ExecuteMsg::Reject {
+                as_host,
+                opponent: address(opponent),
+            },
+            FuzzExecuteMsg::Accept { x, y, host } => ExecuteMsg::Accept {
+                coord: Coord { x, y },
+                host: address(host),
+            },
+            FuzzExecuteMsg::Swap { host } => ExecuteMsg::Swap {
+                host: address(host),
+            },
+            FuzzExecuteMsg::Rematch {
+                host,
+                opponent,
+                coord,
+                double_or_nothing,
+            } => ExecuteMsg::Rematch {
+                game: key(host, opponent),
+                coord: coord.map(|(x, y)| Coord { x, y }),
+                double_or_nothing,
+            },
+            FuzzExecuteMsg::Play {
+                as_host,
+                x,
+                y,
+                opponent,
+            } => ExecuteMsg::Play {
+                as_host,
+                coord: Coord { x, y },
+                opponent: address(opponent),
+            },
+            FuzzExecuteMsg::DropPiece {
+                as_host,
+                column,
+                opponent,
+            } => ExecuteMsg::DropPiece {
+                as_host,
+                column,
+                opponent: address(opponent),
+            },
+            FuzzExecuteMsg::MovePiece {
+                as_host,
+                from,
+                to,
+                opponent,
+            } => ExecuteMsg::MovePiece {
+                as_host,
+                from: Coord {
+                    x: from.0,
+                    y: from.1,
+                },
+                to: Coord { x: to.0, y: to.1 },
+                opponent: address(opponent),
+            },
+            FuzzExecuteMsg::FundHouse => ExecuteMsg::FundHouse {},
+            FuzzExecuteMsg::WithdrawHouse { amount } => ExecuteMsg::WithdrawHouse {
+                amount: coins(&amount),
+            },
+            FuzzExecuteMsg::UpdateHouse {
+                difficulty: value,
+                max_exposure,
+            } => ExecuteMsg::UpdateHouse {
+                difficulty: value.map(difficulty),
+                max_exposure: max_exposure.map(|amount| coins(&amount)),
+            },
+            FuzzExecuteMsg::Claim => ExecuteMsg::Claim {},
+            FuzzExecuteMsg::ClaimFor { address: index } => ExecuteMsg::ClaimFor {
+                address: address(index),
+            },
+            FuzzExecuteMsg::UpdatePause { pause: value } => ExecuteMsg::UpdatePause {
+                pause: pause(value),
+            },
+            FuzzExecuteMsg::Sponsor {
+                host,
+                opponent,
+                expires,
+            } => ExecuteMsg::Sponsor {
+                host: address(host),
+                opponent: address(opponent),
+                expires: Expiration::AtHeight(expires),
+            },
+            FuzzExecuteMsg::ReclaimSponsorship { host, opponent } => {
+                ExecuteMsg::ReclaimSponsorship {
+                    host: address(host),
+                    opponent: address(opponent),
+                }
+            }
+            FuzzExecuteMsg::SweepSurplus { recipient } => ExecuteMsg::SweepSurplus {
+                recipient: recipient.map(address),
+            },
+            FuzzExecuteMsg::EmergencyWithdraw { as_host, opponent } => {
+                ExecuteMsg::EmergencyWithdraw {
+                    as_host,
+                    opponent: address(opponent),
+                }
+            }
+            FuzzExecuteMsg::RegisterPubkey { pubkey } => ExecuteMsg::RegisterPubkey {
+                pubkey: Binary(pubkey),
+            },
+            FuzzExecuteMsg::SettleSignedMoves {
+                host,
+                opponent,
+                moves,
+            } => ExecuteMsg::SettleSignedMoves {
+                host: address(host),
+                opponent: address(opponent),
+                moves: moves
+                    .into_iter()
+                    .map(|(from, x, y, signature)| SignedMove {
+                        from: from.map(|(x, y)| Coord { x, y }),
+                        coord: Coord { x, y },
+                        signature: Binary(signature),
+                    })
+                    .collect(),
+            },
+            FuzzExecuteMsg::Resign { as_host, opponent } => ExecuteMsg::Resign {
+                as_host,
+                opponent: address(opponent),
+            },
+            FuzzExecuteMsg::OfferDraw { as_host, opponent } => ExecuteMsg::OfferDraw {
+                as_host,
+                opponent: address(opponent),
+            },
+            FuzzExecuteMsg::CallFlag { as_host, opponent } => ExecuteMsg::CallFlag {
+                as_host,
+                opponent: address(opponent),
+            },
+            FuzzExecuteMsg::AuthorizeSessionKey {
+                session_key,
+                expires,
+                games,
+            } => ExecuteMsg::AuthorizeSessionKey {
+                session_key: address(session_key),
+                expires: Expiration::AtHeight(expires),
+                scope: match games {
+                    Some(ids) => SessionScope::Games { ids },
+                    None => SessionScope::Moves {},
+                },
+            },
+            FuzzExecuteMsg::RevokeSessionKey { session_key } => ExecuteMsg::RevokeSessionKey {
+                session_key: address(session_key),
+            },
+            FuzzExecuteMsg::Delegate {
+                player,
+                action,
+                as_host,
+                x,
+                y,
+                opponent,
+            } => {
+                let opponent = address(opponent);
+                ExecuteMsg::Delegate {
+                    player: address(player),
+                    msg: match action % 5 {
+                        0 => DelegatedMsg::Play {
+                            as_host,
+                            coord: Coord { x, y },
+                            opponent,
+                        },
+                        1 => DelegatedMsg::DropPiece {
+                            as_host,
+                            column: x,
+                            opponent,
+                        },
+                        2 => DelegatedMsg::MovePiece {
+                            as_host,
+                            from: Coord { x, y },
+                            to: Coord {
+                                x,
+                                y: y.wrapping_add(1),
+                            },
+                            opponent,
+                        },
+                        3 => DelegatedMsg::Resign { as_host, opponent },
+                        _ => DelegatedMsg::OfferDraw { as_host, opponent },
+                    },
+                }
+            }
+        };
+
+        Some(msg)
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+pub enum FuzzQueryMsg {
+    Raw(String),
+    Games {
+        key: Option<(u8, u8)>,
+        status: Option<u8>,
+    },
+    Evaluate {
+        host: u8,
+        opponent: u8,
+    },
+    House,
+    Claimable {
+        address: u8,
+    },
+    Pause,
+    Solvency,
+    Sponsorship {
+        host: u8,
+        opponent: u8,
+    },
+    Channel {
+        host: u8,
+        opponent: u8,
+    },
+    Clock {
+        host: u8,
+        opponent: u8,
+    },
+    SessionKeys {
+        player: u8,
+    },
+}
+
+fn key(host: u8, opponent: u8) -> QueryKey {
+    QueryKey {
+        host: address(host),
+        opponent: address(opponent),
+    }
+}
+
+impl FuzzQueryMsg {
+    pub fn into_msg(self) -> Option<QueryMsg> {
+        let msg = match self {
+            FuzzQueryMsg::Raw(json) => return serde_json::from_str(&json).ok(),
+            FuzzQueryMsg::Games { key: game, status } => QueryMsg::Games {
+                key: game.map(|(host, opponent)| key(host, opponent)),
+                status: status.map(|value| match value % 5 {
+                    0 => Status::INVITED,
+                    1 => Status::PLAYING,
+                    2 => Status::COMPLETED,
+                    3 => Status::REJECTED,
+                    _ => Status::VOIDED,
+                }),
+            },
+            FuzzQueryMsg::Evaluate { host, opponent } => QueryMsg::Evaluate {
+                game: key(host, opponent),
+            },
+            FuzzQueryMsg::House => QueryMsg::House {},
+            FuzzQueryMsg::Claimable { address: index } => QueryMsg::Claimable {
+                address: address(index),
+            },
+            FuzzQueryMsg::Pause => QueryMsg::Pause {},
+            FuzzQueryMsg::Solvency => QueryMsg::Solvency {},
+            FuzzQueryMsg::Sponsorship { host, opponent } => QueryMsg::Sponsorship {
+                game: key(host, opponent),
+            },
+            FuzzQueryMsg::Channel { host, opponent } => QueryMsg::Channel {
+                game: key(host, opponent),
+            },
+            FuzzQueryMsg::Clock { host, opponent } => QueryMsg::Clock {
+                game: key(host, opponent),
+            },
+            FuzzQueryMsg::SessionKeys { player } => QueryMsg::SessionKeys {
+                player: address(player),
+            },
+        };
+
+        Some(msg)
+    }
+}
+
+/**
+    Contract on mock storage that keeps track of the funds it
+    holds: the funds of every successful message are added and
+    the bank messages it sends are subtracted.
+*/
+pub struct Harness {
+    pub deps: OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    pub env: Env,
+    pub balance: BTreeMap<String, u128>,
+}
+
+impl Harness {
+    pub fn new() -> Harness {
+        Harness::with_payout_mode(PayoutMode::PUSH)
+    }
+
+    pub fn with_payout_mode(payout_mode: PayoutMode) -> Harness {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let msg = InstantiateMsg {
+            house_max_exposure: Some(coins(&[(0, 1_000), (1, 1_000)])),
+            payout_mode: Some(payout_mode),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        Harness {
+            deps,
+            env,
+            balance: BTreeMap::new(),
+        }
+    }
+
+    /**
+        Executes the step in a new block. Returns false when
+        the funds received by the contract would exceed the
+        total supply of a denom, which cannot happen on-chain.
+    */
+    pub fn execute(&mut self, step: Step) -> bool {
+        let msg = match step.msg.into_msg() {
+            Some(msg) => msg,
+            None => return true,
+        };
+        let funds = coins(&step.funds);
+        self.env.block.height += 1;
+        self.env.block.time = self.env.block.time.plus_seconds(5);
+
+        let mut balance = self.balance.clone();
+        for coin in &funds {
+            let held = balance.entry(coin.denom.clone()).or_default();
+            match held.checked_add(coin.amount.u128()) {
+                Some(amount) => *held = amount,
+                None => return false,
+            }
+        }
+
+        // the funds are transferred to the contract before it executes
+        self.update_querier(&balance);
+        let info = mock_info(&address(step.sender), &funds);
+        let res = match execute(self.deps.as_mut(), self.env.clone(), info, msg) {
+            Ok(res) => res,
+            Err(_) => {
+                self.update_querier(&self.balance.clone());
+                return true;
+            }
+        };
+
+        for sub_msg in res.messages {
+            if let CosmosMsg::Bank(BankMsg::Send { amount, .. }) = sub_msg.msg {
+                for coin in amount {
+                    let held = balance.entry(coin.denom.clone()).or_default();
+                    *held = held
+                        .checked_sub(coin.amount.u128())
+                        .unwrap_or_else(|| panic!("contract sent {} it does not hold", coin));
+                }
+            }
+        }
+        self.update_querier(&balance);
+        self.balance = balance;
+
+        true
+    }
+
+    fn update_querier(&mut self, balance: &BTreeMap<String, u128>) {
+        let coins = balance
+            .iter()
+            .filter(|(_, amount)| **amount > 0)
+            .map(|(denom, amount)| Coin {
+                denom: denom.clone(),
+                amount: Uint128::new(*amount),
+            })
+            .collect();
+        self.deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins);
+    }
+
+    pub fn query(&self, msg: QueryMsg) -> Option<Binary> {
+        query(self.deps.as_ref(), self.env.clone(), msg).ok()
+    }
+
+    /**
+        The contract must hold at least the prizes of the games
+        in progress and the unused sponsorships plus the bankroll
+        of the house and the claims of the addresses,
+        and its escrow and claims ledgers must match the games,
+        sponsorships and claimable queries.
+    */
+    pub fn check_escrow(&self) {
+        let games: Vec<GameResponse> = from_binary(
+            &self
+                .query(QueryMsg::Games {
+                    key: None,
+                    status: None,
+                })
+                .unwrap(),
+        )
+        .unwrap();
+        let house: HouseResponse = from_binary(&self.query(QueryMsg::House {}).unwrap()).unwrap();
+        let claims: Vec<Coin> = ADDRESSES
+            .iter()
+            .flat_map(|address| {
+                let claimable: ClaimableResponse = from_binary(
+                    &self
+                        .query(QueryMsg::Claimable {
+                            address: String::from(*address),
+                        })
+                        .unwrap(),
+                )
+                .unwrap();
+                claimable.amount
+            })
+            .collect();
+
+        let solvency: SolvencyResponse =
+            from_binary(&self.query(QueryMsg::Solvency {}).unwrap()).unwrap();
+
+        let sponsorships = ADDRESSES.iter().flat_map(|host| {
+            ADDRESSES.iter().flat_map(move |opponent| {
+                let res: SponsorshipResponse = from_binary(
+                    &self
+                        .query(QueryMsg::Sponsorship {
+                            game: QueryKey {
+                                host: String::from(*host),
+                                opponent: String::from(*opponent),
+                            },
+                        })
+                        .unwrap(),
+                )
+                .unwrap();
+                res.sponsorship
+                    .map(|sponsorship| sponsorship.amount)
+                    .unwrap_or_default()
+            })
+        });
+        let open: Vec<Coin> = games
+            .iter()
+            .filter(|res| matches!(res.game.status, Status::INVITED | Status::PLAYING))
+            .flat_map(|res| res.game.prize.clone())
+            .chain(sponsorships)
+            .collect();
+        assert_eq!(solvency.escrow, sum(&open), "escrow ledger");
+        assert_eq!(solvency.claims, sum(&claims), "claims ledger");
+        assert!(solvency.solvent, "contract is insolvent: {:?}", solvency);
+
+        let mut required: BTreeMap<String, u128> = BTreeMap::new();
+        for coin in open
+            .iter()
+            .chain(house.bankroll.iter())
+            .chain(claims.iter())
+        {
+            *required.entry(coin.denom.clone()).or_default() += coin.amount.u128();
+        }
+
+        for (denom, amount) in required {
+            let balance = self.balance.get(&denom).copied().unwrap_or_default();
+            assert!(
+                balance >= amount,
+                "contract holds {}{} but escrows require {}{}",
+                balance,
+                denom,
+                amount,
+                denom
+            );
+        }
+    }
+}
+
+/**
+    Total per denom sorted by denom without zero amounts,
+    as the contract stores its ledgers.
+*/
+fn sum(coins: &[Coin]) -> Vec<Coin> {
+    let mut total: BTreeMap<&str, u128> = BTreeMap::new();
+    for coin in coins {
+        *total.entry(&coin.denom).or_default() += coin.amount.u128();
+    }
+
+    total
+        .into_iter()
+        .filter(|(_, amount)| *amount > 0)
+        .map(|(denom, amount)| Coin {
+            denom: String::from(denom),
+            amount: Uint128::new(amount),
+        })
+        .collect()
+}
+
+impl Default for Harness {
+    fn default() -> Harness {
+        Harness::new()
+    }
+}
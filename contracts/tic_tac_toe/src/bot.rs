@@ -0,0 +1,91 @@
+use crate::models::state::{Coord, Difficulty, Game, PlayerSymbol};
+use crate::solver::Solver;
+
+/**
+    Choose the move of the player in `player_round` following
+    the given difficulty. When more than one move is a candidate
+    the seed is used to pick one of them, so the same game, difficulty
+    and seed always produce the same move.
+*/
+pub fn choose_move(game: &Game, difficulty: Difficulty, seed: u64) -> Option<Coord> {
+    let candidates = match difficulty {
+        Difficulty::RANDOM => empty_cells(game),
+        Difficulty::MEDIUM => threats(game),
+        Difficulty::PERFECT => Solver::new().evaluate(game)?.1,
+    };
+
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let index = (mix(seed) % candidates.len() as u64) as usize;
+    Some(candidates[index])
+}
+
+fn empty_cells(game: &Game) -> Vec<Coord> {
+    let mut cells = vec![];
+    for (y, row) in game.board.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            if cell.is_none() {
+                cells.push(Coord {
+                    x: x as u8,
+                    y: y as u8,
+                });
+            }
+        }
+    }
+    cells
+}
+
+/**
+    Moves that win the game for the player in `player_round`,
+    otherwise moves that stop the other player from winning
+    on the next round, otherwise every empty cell.
+*/
+fn threats(game: &Game) -> Vec<Coord> {
+    let player = match game.player_round {
+        Some(player) => player,
+        None => return vec![],
+    };
+    let other = player.other();
+
+    let cells = empty_cells(game);
+    let wins: Vec<Coord> = cells
+        .iter()
+        .copied()
+        .filter(|coord| wins_on(game, player, *coord))
+        .collect();
+    if !wins.is_empty() {
+        return wins;
+    }
+
+    let blocks: Vec<Coord> = cells
+        .iter()
+        .copied()
+        .filter(|coord| wins_on(game, other, *coord))
+        .collect();
+    if !blocks.is_empty() {
+        return blocks;
+    }
+
+    cells
+}
+
+fn wins_on(game: &Game, symbol: PlayerSymbol, coord: Coord) -> bool {
+    let mut game = game.clone();
+    game.player_round = Some(symbol);
+    game.play(coord)
+        .map(|game| game.is_current_player_winner())
+        .unwrap_or(false)
+}
+
+/**
+    SplitMix64 finalizer used to spread
+    the seed bits before picking a move.
+*/
+fn mix(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,50 @@
+use sha2::{Digest, Sha256};
+
+use crate::models::state::{Coord, Game, PlayerSymbol};
+
+/**
+    Hash of the board serialised row by row,
+    one byte per cell where:
+    - 0: empty cell,
+    - 1: cell selected by 'X',
+    - 2: cell selected by 'O'
+*/
+pub fn board_hash(game: &Game) -> [u8; 32] {
+    let cells: Vec<u8> = game
+        .board
+        .iter()
+        .flatten()
+        .map(|cell| match cell {
+            None => 0,
+            Some(PlayerSymbol::X) => 1,
+            Some(PlayerSymbol::O) => 2,
+        })
+        .collect();
+
+    Sha256::digest(&cells).into()
+}
+
+/**
+    Hash signed by the player that plays a move off-chain:
+    sha256(game_id as u64 big-endian || move_index as u32 big-endian || [from.x, from.y] || board_hash)
+    where move_index is the number of moves played after the move
+    (see Game::moves_count), the from bytes are only there when a
+    piece of a MORRIS game is moved and board_hash is the board_hash
+    after the move.
+*/
+pub fn move_hash(
+    game_id: u64,
+    move_index: u32,
+    from: Option<Coord>,
+    board_hash: &[u8; 32],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(game_id.to_be_bytes());
+    hasher.update(move_index.to_be_bytes());
+    if let Some(from) = from {
+        hasher.update([from.x, from.y]);
+    }
+    hasher.update(board_hash);
+
+    hasher.finalize().into()
+}
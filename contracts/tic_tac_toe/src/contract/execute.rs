@@ -1,63 +1,254 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Addr, BankMsg, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{
+    Addr, BankMsg, Binary, Coin, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Timestamp,
+};
+use cw_utils::{Expiration, NativeBalance};
 
 use crate::{
+    bot, channel,
+    contract::query::query_solvency,
     models::{
         errors::ContractError,
-        state::{Coord, Game, PlayerSymbol, Status},
-        ExecuteMsg,
+        events,
+        state::{
+            Clock, Coord, Difficulty, Game, Opening, Pause, PayoutMode, PlayerSymbol, SessionKey,
+            SessionScope, Sponsorship, Status, TimeControl, Variant,
+        },
+        DelegatedMsg, ExecuteMsg, QueryKey, SignedMove,
     },
-    GAMES,
+    BANKROLL, CHALLENGES, CLAIMS, CLAIMS_TOTAL, CLOCKS, CLOCK_PAUSE, CONFIG, DRAW_OFFERS, ESCROW,
+    GAMES, GAME_COUNT, HOUSE_EXPOSURE, PUBKEYS, SESSION_KEYS, SPONSORSHIPS,
 };
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    let pause = CONFIG.load(deps.storage)?.pause;
+    if is_paused(pause, &msg) {
+        return Err(ContractError::Paused { pause });
+    }
+
     match msg {
         ExecuteMsg::Invite {
             coord,
             host_symbol,
             opponent,
-        } => try_invite(deps, info, coord, host_symbol, opponent),
+            opponent_stake,
+            opening,
+            time_control,
+            variant,
+            practice,
+        } => try_invite(
+            deps,
+            env,
+            info,
+            coord,
+            host_symbol,
+            opponent,
+            opponent_stake,
+            opening.unwrap_or_default(),
+            time_control,
+            variant.unwrap_or_default(),
+            practice.unwrap_or_default(),
+        ),
         ExecuteMsg::Reject { as_host, opponent } => try_reject(deps, info, as_host, opponent),
-        ExecuteMsg::Accept { coord, host } => try_accept(deps, info, coord, host),
+        ExecuteMsg::Accept { coord, host } => try_accept(deps, env, info, Some(coord), host),
+        ExecuteMsg::Swap { host } => try_accept(deps, env, info, None, host),
+        ExecuteMsg::Rematch {
+            game,
+            coord,
+            double_or_nothing,
+        } => try_rematch(deps, env, info, game, coord, double_or_nothing),
         ExecuteMsg::Play {
             as_host,
             coord,
             opponent,
-        } => try_play(deps, info, as_host, coord, opponent),
+        } => try_play(deps, env, info.sender, None, as_host, None, coord, opponent),
+        ExecuteMsg::MovePiece {
+            as_host,
+            from,
+            to,
+            opponent,
+        } => try_play(
+            deps,
+            env,
+            info.sender,
+            None,
+            as_host,
+            Some(from),
+            to,
+            opponent,
+        ),
+        ExecuteMsg::DropPiece {
+            as_host,
+            column,
+            opponent,
+        } => try_drop_piece(deps, env, info.sender, None, as_host, column, opponent),
+        ExecuteMsg::FundHouse {} => try_fund_house(deps, info),
+        ExecuteMsg::WithdrawHouse { amount } => try_withdraw_house(deps, info, amount),
+        ExecuteMsg::UpdateHouse {
+            difficulty,
+            max_exposure,
+        } => try_update_house(deps, info, difficulty, max_exposure),
+        ExecuteMsg::Claim {} => try_claim(deps, info.sender),
+        ExecuteMsg::ClaimFor { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            try_claim(deps, address)
+        }
+        ExecuteMsg::UpdatePause { pause } => try_update_pause(deps, env, info, pause),
+        ExecuteMsg::SweepSurplus { recipient } => try_sweep_surplus(deps, env, info, recipient),
+        ExecuteMsg::EmergencyWithdraw { as_host, opponent } => {
+            try_emergency_withdraw(deps, env, info, as_host, opponent)
+        }
+        ExecuteMsg::Sponsor {
+            host,
+            opponent,
+            expires,
+        } => try_sponsor(deps, env, info, host, opponent, expires),
+        ExecuteMsg::ReclaimSponsorship { host, opponent } => {
+            try_reclaim_sponsorship(deps, env, host, opponent)
+        }
+        ExecuteMsg::RegisterPubkey { pubkey } => try_register_pubkey(deps, info, pubkey),
+        ExecuteMsg::SettleSignedMoves {
+            host,
+            opponent,
+            moves,
+        } => try_settle_signed_moves(deps, env, host, opponent, moves),
+        ExecuteMsg::Resign { as_host, opponent } => {
+            try_resign(deps, env, info.sender, None, as_host, opponent)
+        }
+        ExecuteMsg::OfferDraw { as_host, opponent } => {
+            try_offer_draw(deps, env, info.sender, None, as_host, opponent)
+        }
+        ExecuteMsg::CallFlag { as_host, opponent } => {
+            try_call_flag(deps, env, info, as_host, opponent)
+        }
+        ExecuteMsg::AuthorizeSessionKey {
+            session_key,
+            expires,
+            scope,
+        } => try_authorize_session_key(deps, info, session_key, expires, scope),
+        ExecuteMsg::RevokeSessionKey { session_key } => {
+            try_revoke_session_key(deps, info, session_key)
+        }
+        ExecuteMsg::Delegate { player, msg } => {
+            let player = deps.api.addr_validate(&player)?;
+            let session_key = Some(info.sender);
+            match msg {
+                DelegatedMsg::Play {
+                    as_host,
+                    coord,
+                    opponent,
+                } => try_play(
+                    deps,
+                    env,
+                    player,
+                    session_key,
+                    as_host,
+                    None,
+                    coord,
+                    opponent,
+                ),
+                DelegatedMsg::MovePiece {
+                    as_host,
+                    from,
+                    to,
+                    opponent,
+                } => try_play(
+                    deps,
+                    env,
+                    player,
+                    session_key,
+                    as_host,
+                    Some(from),
+                    to,
+                    opponent,
+                ),
+                DelegatedMsg::DropPiece {
+                    as_host,
+                    column,
+                    opponent,
+                } => try_drop_piece(deps, env, player, session_key, as_host, column, opponent),
+                DelegatedMsg::Resign { as_host, opponent } => {
+                    try_resign(deps, env, player, session_key, as_host, opponent)
+                }
+                DelegatedMsg::OfferDraw { as_host, opponent } => {
+                    try_offer_draw(deps, env, player, session_key, as_host, opponent)
+                }
+            }
+        }
     }
 }
 
+/**
+    Whether the message is blocked by the circuit breaker, admin
+    messages, claims, ReclaimSponsorship and EmergencyWithdraw
+    never are.
+*/
+fn is_paused(pause: Pause, msg: &ExecuteMsg) -> bool {
+    match msg {
+        ExecuteMsg::Invite { .. }
+        | ExecuteMsg::Accept { .. }
+        | ExecuteMsg::Swap { .. }
+        | ExecuteMsg::Rematch { .. }
+        | ExecuteMsg::Sponsor { .. } => pause.blocks_invites(),
+        ExecuteMsg::Play { .. }
+        | ExecuteMsg::DropPiece { .. }
+        | ExecuteMsg::MovePiece { .. }
+        | ExecuteMsg::Resign { .. }
+        | ExecuteMsg::OfferDraw { .. }
+        | ExecuteMsg::CallFlag { .. }
+        | ExecuteMsg::SettleSignedMoves { .. }
+        | ExecuteMsg::Delegate { .. } => pause.blocks_moves(),
+        ExecuteMsg::Reject { .. } => pause == Pause::ALL,
+        _ => false,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn try_invite(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     coord: Coord,
     host_symbol: PlayerSymbol,
     opponent: String,
+    opponent_stake: Option<Vec<Coin>>,
+    opening: Opening,
+    time_control: Option<TimeControl>,
+    variant: Variant,
+    practice: bool,
 ) -> Result<Response, ContractError> {
     let opponent_address = deps.api.addr_validate(&opponent)?;
-    if !coord.is_valid() {
-        return Err(ContractError::InvalidCoord { coord });
+    if practice && (!normalize(&info.funds)?.is_empty() || opponent_stake.is_some()) {
+        return Err(ContractError::PracticeGameStake {});
+    }
+    variant.check()?.check_coord(coord)?;
+    if matches!(variant, Variant::Gravity { .. }) && opening == (Opening::NoCenter {}) {
+        return Err(ContractError::InvalidVariant {
+            reason: String::from("a gravity board has no center to forbid"),
+        });
+    }
+    if time_control
+        .filter(|time_control| time_control.base == 0)
+        .is_some()
+    {
+        return Err(ContractError::InvalidTimeControl {});
     }
 
     if opponent_address == info.sender {
         return Err(ContractError::CannotStartGame {});
     }
 
-    let in_progress_hosted_game = GAMES
-        .may_load(deps.storage, (&info.sender, &opponent_address))
-        .unwrap()
+    let in_progress_hosted_game = load_game(deps.storage, &info.sender, &opponent_address)?
         .filter(|game| game.status == Status::PLAYING || game.status == Status::INVITED);
 
-    let in_progress_invited_game = GAMES
-        .may_load(deps.storage, (&opponent_address, &info.sender))
-        .unwrap()
+    let in_progress_invited_game = load_game(deps.storage, &opponent_address, &info.sender)?
         .filter(|game| game.status == Status::PLAYING || game.status == Status::INVITED);
 
     if in_progress_hosted_game.is_some() || in_progress_invited_game.is_some() {
@@ -65,17 +256,135 @@ fn try_invite(
             host: info.sender,
             opponent: opponent_address,
         });
-    } else {
-        let game = Game::new(coord, info.funds, host_symbol.clone());
-        GAMES.save(deps.storage, (&info.sender, &opponent_address), &game)?;
     }
 
+    let opponent_stake = normalize(&opponent_stake.unwrap_or_else(|| info.funds.clone()))?;
+    if opponent_address == env.contract.address {
+        return try_invite_house(
+            deps,
+            env,
+            info,
+            coord,
+            host_symbol,
+            opponent_stake,
+            opening,
+            time_control,
+            variant,
+            practice,
+        );
+    }
+
+    let mut game = Game::new(
+        next_game_id(deps.storage)?,
+        coord,
+        info.funds,
+        opponent_stake,
+        host_symbol,
+        opening,
+        time_control,
+        variant,
+    )?;
+    game.practice = practice;
+    GAMES.save(deps.storage, (&info.sender, &opponent_address), &game)?;
+    lock_escrow(deps.storage, &game.prize)?;
+
     Ok(Response::new()
         .add_attribute("method", "invite")
         .add_attribute("x", coord.x.to_string())
         .add_attribute("y", coord.y.to_string())
         .add_attribute("host_symbol", host_symbol.to_string())
-        .add_attribute("opponent", opponent))
+        .add_attribute("opponent", opponent)
+        .add_event(events::game_created(
+            &info.sender,
+            &opponent_address,
+            &game,
+            coord,
+        )))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn try_invite_house(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    coord: Coord,
+    host_symbol: PlayerSymbol,
+    house_stake: Vec<Coin>,
+    opening: Opening,
+    time_control: Option<TimeControl>,
+    variant: Variant,
+    practice: bool,
+) -> Result<Response, ContractError> {
+    if variant != (Variant::Classic {}) {
+        return Err(ContractError::InvalidVariant {
+            reason: String::from("the house only plays classic games"),
+        });
+    }
+    if house_stake != normalize(&info.funds)? {
+        return Err(ContractError::HouseUnevenStakes {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let max_exposure = NativeBalance(config.house_max_exposure);
+
+    let exposure = add_balance(HOUSE_EXPOSURE.load(deps.storage)?, &info.funds)?;
+    if !exposure.0.iter().all(|coin| max_exposure.has(coin)) {
+        return Err(ContractError::HouseExposureExceeded {});
+    }
+    let bankroll = (BANKROLL.load(deps.storage)? - info.funds.clone())
+        .map_err(|_| ContractError::InsufficientBankroll {})?;
+
+    let house = &env.contract.address;
+    let mut game = Game::new(
+        next_game_id(deps.storage)?,
+        coord,
+        info.funds,
+        house_stake.clone(),
+        host_symbol,
+        opening,
+        time_control,
+        variant,
+    )?;
+    game.practice = practice;
+    let created_event = events::game_created(&info.sender, house, &game, coord);
+
+    let house_coord = house_move(&env, &game, config.house_difficulty)?;
+    let game = game
+        .add_opponent_stake()?
+        .play(house_coord)?
+        .finish_round()?;
+    game.status = Status::PLAYING;
+
+    GAMES.save(deps.storage, (&info.sender, house), game)?;
+    if let Some(time_control) = game.time_control {
+        let clock = Clock::new(time_control, clock_time(deps.storage, env.block.time)?);
+        CLOCKS.save(deps.storage, game.id, &clock)?;
+    }
+    lock_escrow(deps.storage, &game.prize)?;
+    HOUSE_EXPOSURE.save(deps.storage, &exposure)?;
+    BANKROLL.save(deps.storage, &bankroll)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "invite")
+        .add_attribute("x", coord.x.to_string())
+        .add_attribute("y", coord.y.to_string())
+        .add_attribute("host_symbol", host_symbol.to_string())
+        .add_attribute("opponent", house)
+        .add_attribute("house_x", house_coord.x.to_string())
+        .add_attribute("house_y", house_coord.y.to_string())
+        .add_event(created_event)
+        .add_event(events::game_accepted(
+            &info.sender,
+            house,
+            game,
+            &house_stake,
+        ))
+        .add_event(events::move_played(
+            &info.sender,
+            house,
+            game,
+            house,
+            house_coord,
+        )))
 }
 
 fn try_reject(
@@ -96,155 +405,1502 @@ fn try_reject(
         refund_address = &opponent_address;
     };
 
-    let game = GAMES
-        .may_load(deps.storage, key)
-        .unwrap()
-        .filter(|game| game.status == Status::INVITED);
+    let game = load_game(deps.storage, key.0, key.1)?.filter(|game| game.status == Status::INVITED);
 
-    if game.is_none() {
-        return Err(ContractError::GameNotFound {
-            host: info.sender,
-            opponent: opponent_address,
-        });
-    } else {
-        let mut game = game.unwrap();
+    if let Some(mut game) = game {
         game.status = Status::REJECTED;
         GAMES.save(deps.storage, key, &game)?;
+        release_escrow(deps.storage, &game.prize)?;
+
+        let refund = BankMsg::Send {
+            to_address: refund_address.to_string(),
+            amount: game.prize.clone(),
+        };
 
-        Ok(Response::new()
+        let mut res = Response::new()
             .add_attribute("method", "reject")
             .add_attribute("opponent", opponent)
-            .add_message(BankMsg::Send {
-                to_address: refund_address.to_string(),
-                amount: game.prize.clone(),
-            }))
+            .add_event(events::game_rejected(key.0, key.1, &game))
+            .add_events(events::prizes_paid(
+                key.0,
+                key.1,
+                &game,
+                std::slice::from_ref(&refund),
+            ))
+            .add_messages(payouts(deps.storage, vec![refund])?);
+
+        if let Some(sponsorship) = SPONSORSHIPS.may_load(deps.storage, key)? {
+            SPONSORSHIPS.remove(deps.storage, key);
+            res = return_sponsorship(deps.storage, res, key.0, key.1, sponsorship)?;
+        }
+
+        Ok(res)
+    } else {
+        Err(ContractError::GameNotFound {
+            host: key.0.clone(),
+            opponent: key.1.clone(),
+        })
     }
 }
 
+/**
+    Accepts the game playing the move at coord, or without
+    coord swapping sides under the pie rule (see Game::swap_sides).
+*/
 fn try_accept(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    coord: Coord,
+    coord: Option<Coord>,
     host: String,
 ) -> Result<Response, ContractError> {
     let host_address = deps.api.addr_validate(&host)?;
-    if !coord.is_valid() {
-        return Err(ContractError::InvalidCoord { coord });
-    }
 
-    let game = GAMES
-        .may_load(deps.storage, (&host_address, &info.sender))
-        .unwrap()
+    let game = load_game(deps.storage, &host_address, &info.sender)?
         .filter(|game| game.status == Status::INVITED);
 
-    if game.is_none() {
-        return Err(ContractError::InvalidGame {
-            host: info.sender,
-            opponent: host_address,
-        });
-    } else {
-        let mut game = game.unwrap();
-        if game.already_played_on(coord) {
-            return Err(ContractError::CoordinateAlreadyPlayed { coord });
-        } else if game.prize.ne(&info.funds) {
+    if let Some(mut game) = game {
+        if let Some(coord) = coord {
+            game.check_move(coord)?;
+        }
+        if game.opponent_stake.ne(&normalize(&info.funds)?) {
             return Err(ContractError::InvalidReceivedFunds {});
         }
-        let game = game.double_prize().play(coord).finish_round();
+        game.add_opponent_stake()?;
+        match coord {
+            Some(coord) => game.play(coord)?.finish_round()?,
+            None => game.swap_sides()?,
+        };
         game.status = Status::PLAYING;
 
-        GAMES.save(deps.storage, (&host_address, &info.sender), game)?;
+        let key = (&host_address, &info.sender);
+        let mut expired = None;
+        if let Some(sponsorship) = SPONSORSHIPS.may_load(deps.storage, key)? {
+            SPONSORSHIPS.remove(deps.storage, key);
+            if sponsorship.expires.is_expired(&env.block) {
+                expired = Some(sponsorship);
+            } else {
+                game.add_sponsorship(sponsorship.sponsor, sponsorship.amount)?;
+            }
+        }
+
+        GAMES.save(deps.storage, key, &game)?;
+        lock_escrow(deps.storage, &info.funds)?;
+        if let Some(time_control) = game.time_control {
+            let clock = Clock::new(time_control, clock_time(deps.storage, env.block.time)?);
+            CLOCKS.save(deps.storage, game.id, &clock)?;
+        }
+
+        let accepted_event = events::game_accepted(&host_address, &info.sender, &game, &info.funds);
+        let res = match coord {
+            Some(coord) => Response::new()
+                .add_attribute("method", "accept")
+                .add_attribute("x", coord.x.to_string())
+                .add_attribute("y", coord.y.to_string())
+                .add_attribute("host", host_address.clone())
+                .add_event(accepted_event)
+                .add_event(events::move_played(
+                    &host_address,
+                    &info.sender,
+                    &game,
+                    &info.sender,
+                    coord,
+                )),
+            None => Response::new()
+                .add_attribute("method", "swap")
+                .add_attribute("host", host_address.clone())
+                .add_event(accepted_event)
+                .add_event(events::sides_swapped(&host_address, &info.sender, &game)),
+        };
+
+        match expired {
+            Some(sponsorship) => return_sponsorship(deps.storage, res, key.0, key.1, sponsorship),
+            None => Ok(res),
+        }
+    } else {
+        Err(ContractError::InvalidGame {
+            host: host_address,
+            opponent: info.sender,
+        })
     }
+}
 
-    Ok(Response::new()
-        .add_attribute("method", "accept")
-        .add_attribute("x", coord.x.to_string())
-        .add_attribute("y", coord.y.to_string())
-        .add_attribute("opponent", host_address))
+/**
+    Invites the other player of a COMPLETED game to a rematch hosted
+    by the sender, see Game::rematch. Each player stakes the same as
+    in the previous game unless the winner reinvests its winnings,
+    then both players stake the whole prize. The part of it still
+    credited to the winner in the claims ledger is held back as its
+    stake until the rematch is accepted or rejected, and the rest
+    has to be sent. Winnings are only reinvested in the PULL payout
+    mode, in the PUSH mode they are sent before a rematch.
+*/
+fn try_rematch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game: QueryKey,
+    coord: Option<Coord>,
+    double_or_nothing: bool,
+) -> Result<Response, ContractError> {
+    let host_address = deps.api.addr_validate(&game.host)?;
+    let opponent_address = deps.api.addr_validate(&game.opponent)?;
+    let as_host = info.sender == host_address;
+    if !as_host && info.sender != opponent_address {
+        return Err(ContractError::InvalidRematch {
+            reason: String::from("only the players of the game can request a rematch"),
+        });
+    }
+    let other_address = if as_host {
+        opponent_address.clone()
+    } else {
+        host_address.clone()
+    };
+    if other_address == env.contract.address {
+        return Err(ContractError::InvalidRematch {
+            reason: String::from("the house does not play rematches"),
+        });
+    }
+
+    let previous = match load_game(deps.storage, &host_address, &opponent_address)?
+        .filter(|game| game.status == Status::COMPLETED)
+    {
+        Some(game) => game,
+        None => {
+            return Err(ContractError::InvalidGame {
+                host: host_address,
+                opponent: opponent_address,
+            })
+        }
+    };
+    if let Some(coord) = coord {
+        previous.variant.check_coord(coord)?;
+    }
+    if let Some(reversed) = load_game(deps.storage, &opponent_address, &host_address)? {
+        if reversed.status == Status::INVITED || reversed.status == Status::PLAYING {
+            return Err(ContractError::GameAlreadyInProgress {
+                host: info.sender,
+                opponent: other_address,
+            });
+        } else if reversed.id > previous.id {
+            return Err(ContractError::InvalidRematch {
+                reason: String::from("only the last game between the players can be rematched"),
+            });
+        }
+    }
+
+    let mut reinvested = vec![];
+    let (stake, other_stake) = if double_or_nothing {
+        if previous.winner_address(&host_address, &opponent_address) != Some(&info.sender) {
+            return Err(ContractError::InvalidRematch {
+                reason: String::from("only the winner can reinvest its winnings"),
+            });
+        }
+        if CONFIG.load(deps.storage)?.payout_mode == PayoutMode::PUSH {
+            return Err(ContractError::InvalidRematch {
+                reason: String::from("winnings are only reinvested in the PULL payout mode"),
+            });
+        }
+        let prize = normalize(&previous.prize)?;
+        let claims = CLAIMS
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default();
+        reinvested = prize
+            .iter()
+            .filter_map(|coin| {
+                claims
+                    .0
+                    .iter()
+                    .find(|claimed| claimed.denom == coin.denom)
+                    .map(|claimed| Coin {
+                        denom: coin.denom.clone(),
+                        amount: claimed.amount.min(coin.amount),
+                    })
+            })
+            .collect();
+        if !reinvested.is_empty() {
+            let claims = sub_balance(claims, &reinvested)?;
+            if claims.is_empty() {
+                CLAIMS.remove(deps.storage, &info.sender);
+            } else {
+                CLAIMS.save(deps.storage, &info.sender, &claims)?;
+            }
+            let total = sub_balance(CLAIMS_TOTAL.load(deps.storage)?, &reinvested)?;
+            CLAIMS_TOTAL.save(deps.storage, &total)?;
+        }
+        (prize.clone(), prize)
+    } else {
+        let (host_stake, opponent_stake) = previous.refunds();
+        if as_host {
+            (normalize(&host_stake)?, normalize(&opponent_stake)?)
+        } else {
+            (normalize(&opponent_stake)?, normalize(&host_stake)?)
+        }
+    };
+
+    let sent = sub_balance(NativeBalance(stake.clone()), &reinvested)?.into_vec();
+    if normalize(&info.funds)? != sent {
+        return Err(ContractError::InvalidReceivedFunds {});
+    }
+
+    let rematch = previous.rematch(
+        next_game_id(deps.storage)?,
+        as_host,
+        coord,
+        stake,
+        other_stake,
+    )?;
+    GAMES.save(deps.storage, (&info.sender, &other_address), &rematch)?;
+    lock_escrow(deps.storage, &rematch.prize)?;
+
+    let mut res = Response::new()
+        .add_attribute("method", "rematch")
+        .add_attribute("opponent", other_address.clone())
+        .add_attribute("rematch_of", previous.id.to_string());
+    if let Some(coord) = coord {
+        res = res
+            .add_attribute("x", coord.x.to_string())
+            .add_attribute("y", coord.y.to_string());
+    }
+
+    Ok(res.add_event(events::rematch_created(
+        &info.sender,
+        &other_address,
+        &rematch,
+        coord,
+        previous.id,
+        &reinvested,
+    )))
 }
 
+/**
+    Plays the move at coord, or moves the piece on `from` to
+    coord in a MORRIS game (see Game::move_piece).
+*/
+#[allow(clippy::too_many_arguments)]
 fn try_play(
     deps: DepsMut,
-    info: MessageInfo,
+    env: Env,
+    player: Addr,
+    session_key: Option<Addr>,
     as_host: bool,
+    from: Option<Coord>,
     coord: Coord,
     opponent: String,
 ) -> Result<Response, ContractError> {
     let opponent_address = deps.api.addr_validate(&opponent)?;
-    if !coord.is_valid() {
-        return Err(ContractError::InvalidCoord { coord });
-    }
     let key = if as_host {
-        (&info.sender, &opponent_address)
+        (&player, &opponent_address)
     } else {
-        (&opponent_address, &info.sender)
+        (&opponent_address, &player)
     };
 
-    let game = GAMES
-        .may_load(deps.storage, key)
-        .unwrap()
-        .filter(|game| game.status == Status::PLAYING);
+    let game = load_game(deps.storage, key.0, key.1)?.filter(|game| game.status == Status::PLAYING);
 
-    if game.is_none() {
-        return Err(ContractError::InvalidGame {
-            host: info.sender,
-            opponent: opponent_address,
-        });
-    } else {
-        let mut game = game.unwrap();
-        if game.already_played_on(coord) {
-            return Err(ContractError::CoordinateAlreadyPlayed { coord });
-        } else if game.already_played(as_host) {
+    if let Some(mut game) = game {
+        check_session_key(deps.storage, &env, &player, session_key, game.id, true)?;
+        match from {
+            Some(from) => game.check_move_piece(from, coord)?,
+            None => game.check_move(coord)?,
+        }
+        if game.already_played(as_host) {
             return Err(ContractError::TurnAlreadyPlayed {
                 second_player: opponent,
             });
         }
+        if let Some(deadline) = CHALLENGES.may_load(deps.storage, game.id)? {
+            if env.block.time < deadline {
+                return Err(ContractError::ChallengeWindowOpen { deadline });
+            }
+        }
 
-        let game = game.play(coord);
+        let against_house = opponent_address == env.contract.address;
+        let now = clock_time(deps.storage, env.block.time)?;
+        let mut clock = match game.time_control {
+            Some(time_control) => {
+                let mut clock = load_clock(deps.storage, &game)?;
+                if !clock.punch(as_host, now, time_control.increment) {
+                    return flag_fallen(
+                        deps,
+                        env,
+                        Response::new(),
+                        game,
+                        key,
+                        &player,
+                        against_house,
+                    );
+                }
+                Some(clock)
+            }
+            None => None,
+        };
 
-        if game.is_current_player_winner() {
-            game.status = Status::COMPLETED;
-            game.winner = Some(game.player_round.unwrap());
-            game.player_round = None;
-        } else if game.is_full_board() {
-            game.status = Status::COMPLETED;
-            game.player_round = None;
-        } else {
-            game.finish_round();
+        let game = match from {
+            Some(from) => game.move_piece(from, coord)?,
+            None => game.play(coord)?,
+        }
+        .complete_round()?;
+        DRAW_OFFERS.remove(deps.storage, game.id);
+        let mut move_events = vec![match from {
+            Some(from) => events::piece_moved(key.0, key.1, game, &player, from, coord),
+            None => events::move_played(key.0, key.1, game, &player, coord),
+        }];
+
+        let mut house_coord = None;
+        if against_house && game.status == Status::PLAYING {
+            let config = CONFIG.load(deps.storage)?;
+            let coord = house_move(&env, game, config.house_difficulty)?;
+            game.play(coord)?.complete_round()?;
+            house_coord = Some(coord);
+            move_events.push(events::move_played(key.0, key.1, game, key.1, coord));
+            if let (Some(clock), Some(time_control)) = (clock.as_mut(), game.time_control) {
+                clock.punch(false, now, time_control.increment);
+            }
         }
 
         GAMES.save(deps.storage, key, game)?;
+        if let Some(clock) = clock {
+            CLOCKS.save(deps.storage, game.id, &clock)?;
+        }
 
-        let res = Response::new()
+        let mut res = Response::new()
             .add_attribute("method", "play")
             .add_attribute("x", coord.x.to_string())
             .add_attribute("y", coord.y.to_string())
             .add_attribute("status", game.status.to_string())
             .add_attribute("opponent", opponent.clone());
 
-        if game.status == Status::COMPLETED {
-            if game.winner.is_some() {
-                return Ok(res
-                    .add_attribute("winner", game.winner.unwrap().to_string())
-                    .add_message(BankMsg::Send {
-                        to_address: info.sender.to_string(),
-                        amount: game.prize.clone(),
-                    }));
-            } else {
-                let prize = game.get_half_prize();
-
-                return Ok(res.add_messages(vec![
-                    BankMsg::Send {
-                        to_address: info.sender.to_string(),
-                        amount: prize.clone(),
-                    },
-                    BankMsg::Send {
-                        to_address: opponent,
-                        amount: prize.clone(),
-                    },
-                ]));
-            }
+        if let Some(from) = from {
+            res = res
+                .add_attribute("from_x", from.x.to_string())
+                .add_attribute("from_y", from.y.to_string());
+        }
+        if let Some(house_coord) = house_coord {
+            res = res
+                .add_attribute("house_x", house_coord.x.to_string())
+                .add_attribute("house_y", house_coord.y.to_string());
+        }
+        let res = res.add_events(move_events);
+
+        if game.status == Status::COMPLETED && against_house {
+            return settle_house_game(deps, &player, key.1, game, res);
+        } else if game.status == Status::COMPLETED {
+            return complete_game(deps.storage, res, game, key.0, key.1);
         }
 
         Ok(res)
+    } else {
+        Err(ContractError::InvalidGame {
+            host: key.0.clone(),
+            opponent: key.1.clone(),
+        })
+    }
+}
+
+/**
+    Plays on a gravity board the lowest empty cell of the
+    column, where the dropped piece falls (see try_play).
+*/
+fn try_drop_piece(
+    deps: DepsMut,
+    env: Env,
+    player: Addr,
+    session_key: Option<Addr>,
+    as_host: bool,
+    column: u8,
+    opponent: String,
+) -> Result<Response, ContractError> {
+    let opponent_address = deps.api.addr_validate(&opponent)?;
+    let key = if as_host {
+        (&player, &opponent_address)
+    } else {
+        (&opponent_address, &player)
+    };
+
+    let coord = match load_game(deps.storage, key.0, key.1)?
+        .filter(|game| game.status == Status::PLAYING)
+    {
+        Some(game) => game.drop_coord(column)?,
+        None => {
+            return Err(ContractError::InvalidGame {
+                host: key.0.clone(),
+                opponent: key.1.clone(),
+            })
+        }
+    };
+
+    try_play(
+        deps,
+        env,
+        player,
+        session_key,
+        as_host,
+        None,
+        coord,
+        opponent,
+    )
+}
+
+fn try_resign(
+    deps: DepsMut,
+    env: Env,
+    player: Addr,
+    session_key: Option<Addr>,
+    as_host: bool,
+    opponent: String,
+) -> Result<Response, ContractError> {
+    let opponent_address = deps.api.addr_validate(&opponent)?;
+    let key = if as_host {
+        (&player, &opponent_address)
+    } else {
+        (&opponent_address, &player)
+    };
+
+    let game = load_game(deps.storage, key.0, key.1)?.filter(|game| game.status == Status::PLAYING);
+
+    if let Some(mut game) = game {
+        check_session_key(deps.storage, &env, &player, session_key, game.id, false)?;
+        let symbol = if as_host {
+            game.host_symbol
+        } else {
+            game.host_symbol.other()
+        };
+        game.resign(symbol);
+
+        GAMES.save(deps.storage, key, &game)?;
+        DRAW_OFFERS.remove(deps.storage, game.id);
+        CHALLENGES.remove(deps.storage, game.id);
+
+        let res = Response::new()
+            .add_attribute("method", "resign")
+            .add_attribute("status", game.status.to_string())
+            .add_attribute("opponent", opponent);
+
+        if opponent_address == env.contract.address {
+            return settle_house_game(deps, &player, key.1, &game, res);
+        }
+
+        complete_game(deps.storage, res, &game, key.0, key.1)
+    } else {
+        Err(ContractError::InvalidGame {
+            host: key.0.clone(),
+            opponent: key.1.clone(),
+        })
+    }
+}
+
+/**
+    Records a draw offer from the player, the game is completed
+    as a tie when the other player offers a draw before any
+    other move is played. Playing a move withdraws the offer.
+*/
+fn try_offer_draw(
+    deps: DepsMut,
+    env: Env,
+    player: Addr,
+    session_key: Option<Addr>,
+    as_host: bool,
+    opponent: String,
+) -> Result<Response, ContractError> {
+    let opponent_address = deps.api.addr_validate(&opponent)?;
+    let key = if as_host {
+        (&player, &opponent_address)
+    } else {
+        (&opponent_address, &player)
+    };
+
+    let game = load_game(deps.storage, key.0, key.1)?
+        .filter(|game| game.status == Status::PLAYING)
+        .filter(|_| opponent_address != env.contract.address);
+
+    if let Some(mut game) = game {
+        check_session_key(deps.storage, &env, &player, session_key, game.id, false)?;
+        let symbol = if as_host {
+            game.host_symbol
+        } else {
+            game.host_symbol.other()
+        };
+
+        let res = Response::new()
+            .add_attribute("method", "offer_draw")
+            .add_attribute("opponent", opponent);
+
+        match DRAW_OFFERS.may_load(deps.storage, game.id)? {
+            Some(offered_by) if offered_by != symbol => {
+                game.draw();
+                GAMES.save(deps.storage, key, &game)?;
+                DRAW_OFFERS.remove(deps.storage, game.id);
+                CHALLENGES.remove(deps.storage, game.id);
+
+                let res = res.add_attribute("status", game.status.to_string());
+                complete_game(deps.storage, res, &game, key.0, key.1)
+            }
+            _ => {
+                DRAW_OFFERS.save(deps.storage, game.id, &symbol)?;
+
+                Ok(res.add_attribute("status", game.status.to_string()))
+            }
+        }
+    } else {
+        Err(ContractError::InvalidGame {
+            host: key.0.clone(),
+            opponent: key.1.clone(),
+        })
+    }
+}
+
+/**
+    Completes the game as a loss on time of the player in round
+    when its clock has run out, the game cannot stall because
+    the player whose flag has fallen never moves again.
+*/
+fn try_call_flag(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    as_host: bool,
+    opponent: String,
+) -> Result<Response, ContractError> {
+    let opponent_address = deps.api.addr_validate(&opponent)?;
+    let key = if as_host {
+        (&info.sender, &opponent_address)
+    } else {
+        (&opponent_address, &info.sender)
+    };
+
+    let game = load_game(deps.storage, key.0, key.1)?
+        .filter(|game| game.status == Status::PLAYING && game.time_control.is_some())
+        .filter(|_| opponent_address != env.contract.address);
+
+    if let Some(mut game) = game {
+        let running = !game.already_played(!as_host);
+        let now = clock_time(deps.storage, env.block.time)?;
+        let remaining = load_clock(deps.storage, &game)?.remaining(!as_host, running, now);
+        if remaining > 0 {
+            return Err(ContractError::FlagNotFallen { remaining });
+        }
+
+        flag_fallen(
+            deps,
+            env,
+            Response::new(),
+            game,
+            key,
+            &opponent_address,
+            false,
+        )
+    } else {
+        Err(ContractError::InvalidGame {
+            host: key.0.clone(),
+            opponent: key.1.clone(),
+        })
+    }
+}
+
+/**
+    The player whose flag has fallen resigns, the move it
+    tried to play, if any, is not played. Its clock is kept
+    at zero to show how the game was lost.
+*/
+fn flag_fallen(
+    deps: DepsMut,
+    env: Env,
+    res: Response,
+    mut game: Game,
+    key: (&Addr, &Addr),
+    player: &Addr,
+    against_house: bool,
+) -> Result<Response, ContractError> {
+    let mut clock = load_clock(deps.storage, &game)?;
+    let symbol = if player == key.0 {
+        clock.host = 0;
+        game.host_symbol
+    } else {
+        clock.opponent = 0;
+        game.host_symbol.other()
+    };
+    clock.since = clock_time(deps.storage, env.block.time)?;
+    game.resign(symbol);
+
+    GAMES.save(deps.storage, key, &game)?;
+    CLOCKS.save(deps.storage, game.id, &clock)?;
+    DRAW_OFFERS.remove(deps.storage, game.id);
+    CHALLENGES.remove(deps.storage, game.id);
+
+    let res = res
+        .add_attribute("method", "flag_fallen")
+        .add_attribute("player", player)
+        .add_attribute("status", game.status.to_string())
+        .add_event(events::flag_fallen(key.0, key.1, &game, player));
+
+    if against_house {
+        return settle_house_game(deps, key.0, &env.contract.address, &game, res);
+    }
+
+    complete_game(deps.storage, res, &game, key.0, key.1)
+}
+
+fn try_authorize_session_key(
+    deps: DepsMut,
+    info: MessageInfo,
+    session_key: String,
+    expires: Expiration,
+    scope: SessionScope,
+) -> Result<Response, ContractError> {
+    let session_key_address = deps.api.addr_validate(&session_key)?;
+    SESSION_KEYS.save(
+        deps.storage,
+        (&info.sender, &session_key_address),
+        &SessionKey { expires, scope },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "authorize_session_key")
+        .add_attribute("session_key", session_key)
+        .add_attribute("expires", expires.to_string()))
+}
+
+fn try_revoke_session_key(
+    deps: DepsMut,
+    info: MessageInfo,
+    session_key: String,
+) -> Result<Response, ContractError> {
+    let session_key_address = deps.api.addr_validate(&session_key)?;
+    SESSION_KEYS.remove(deps.storage, (&info.sender, &session_key_address));
+
+    Ok(Response::new()
+        .add_attribute("method", "revoke_session_key")
+        .add_attribute("session_key", session_key))
+}
+
+/**
+    Messages sent directly by the player do not have a session key
+    and are always authorised. Otherwise the session key must not be
+    expired and its scope must allow the action on the given game.
+*/
+fn check_session_key(
+    storage: &dyn Storage,
+    env: &Env,
+    player: &Addr,
+    session_key: Option<Addr>,
+    game_id: u64,
+    only_moves: bool,
+) -> Result<(), ContractError> {
+    let session_key = match session_key {
+        Some(session_key) => session_key,
+        None => return Ok(()),
+    };
+
+    let authorized = SESSION_KEYS
+        .may_load(storage, (player, &session_key))?
+        .filter(|key| !key.expires.is_expired(&env.block))
+        .filter(|key| key.scope.allows(game_id, only_moves))
+        .is_some();
+
+    if authorized {
+        Ok(())
+    } else {
+        Err(ContractError::SessionKeyUnauthorized {
+            session_key,
+            player: player.clone(),
+        })
+    }
+}
+
+fn try_fund_house(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let funds = normalize(&info.funds)?;
+    if funds.is_empty() {
+        return Err(ContractError::InvalidReceivedFunds {});
+    }
+
+    let bankroll = add_balance(BANKROLL.load(deps.storage)?, &funds)?;
+    BANKROLL.save(deps.storage, &bankroll)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "fund_house")
+        .add_attribute("bankroll", format_balance(&bankroll)))
+}
+
+fn try_withdraw_house(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Vec<Coin>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let amount = normalize(&amount)?;
+    if amount.is_empty() {
+        return Err(ContractError::EmptyWithdrawal {});
+    }
+
+    let bankroll = (BANKROLL.load(deps.storage)? - amount.clone())
+        .map_err(|_| ContractError::InsufficientBankroll {})?;
+    BANKROLL.save(deps.storage, &bankroll)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "withdraw_house")
+        .add_attribute("bankroll", format_balance(&bankroll))
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount,
+        }))
+}
+
+fn try_update_house(
+    deps: DepsMut,
+    info: MessageInfo,
+    difficulty: Option<Difficulty>,
+    max_exposure: Option<Vec<Coin>>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(difficulty) = difficulty {
+        config.house_difficulty = difficulty;
+    }
+    if let Some(max_exposure) = max_exposure {
+        config.house_max_exposure = max_exposure;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_house")
+        .add_attribute("difficulty", config.house_difficulty.to_string()))
+}
+
+/**
+    Sends all the claims of the recipient to it, anybody
+    can pay the fees of the claim of another address with
+    ClaimFor but the funds always go to the recipient.
+*/
+fn try_claim(deps: DepsMut, recipient: Addr) -> Result<Response, ContractError> {
+    let claims = CLAIMS
+        .may_load(deps.storage, &recipient)?
+        .unwrap_or_default();
+    if claims.is_empty() {
+        return Err(ContractError::NothingToClaim { address: recipient });
+    }
+    CLAIMS.remove(deps.storage, &recipient);
+    let total = sub_balance(CLAIMS_TOTAL.load(deps.storage)?, &claims.0)?;
+    CLAIMS_TOTAL.save(deps.storage, &total)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "claim")
+        .add_attribute("recipient", recipient.clone())
+        .add_attribute("amount", claims.to_string())
+        .add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: claims.into_vec(),
+        }))
+}
+
+/**
+    Sets the circuit breaker, the clocks of every game
+    are stopped while the moves are blocked.
+*/
+fn try_update_pause(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pause: Pause,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.pause = pause;
+    CONFIG.save(deps.storage, &config)?;
+    let mut clock_pause = CLOCK_PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    clock_pause.update(pause, env.block.time);
+    CLOCK_PAUSE.save(deps.storage, &clock_pause)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "update_pause")
+        .add_attribute("pause", pause.to_string()))
+}
+
+/**
+    Voids a game in status INVITED or PLAYING while the contract
+    is paused. The sender receives its own stake directly and the
+    other player is paid following the payout mode, the stake of
+    the house goes back to the bankroll. Nobody wins the prize,
+    a sponsorship not yet used by an INVITED game goes back to
+    the sponsor.
+*/
+fn try_emergency_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    as_host: bool,
+    opponent: String,
+) -> Result<Response, ContractError> {
+    if CONFIG.load(deps.storage)?.pause == Pause::NONE {
+        return Err(ContractError::NotPaused {});
+    }
+    let opponent_address = deps.api.addr_validate(&opponent)?;
+    let key = if as_host {
+        (&info.sender, &opponent_address)
+    } else {
+        (&opponent_address, &info.sender)
+    };
+
+    let game = load_game(deps.storage, key.0, key.1)?
+        .filter(|game| game.status == Status::INVITED || game.status == Status::PLAYING);
+
+    let mut game = match game {
+        Some(game) => game,
+        None => {
+            return Err(ContractError::InvalidGame {
+                host: key.0.clone(),
+                opponent: key.1.clone(),
+            })
+        }
+    };
+
+    let (host_stake, opponent_stake) = game.stakes();
+    let (sender_stake, other_stake) = if as_host {
+        (host_stake, opponent_stake)
+    } else {
+        (opponent_stake, host_stake)
+    };
+    let was_playing = game.status == Status::PLAYING;
+    game.status = Status::VOIDED;
+    game.player_round = None;
+    GAMES.save(deps.storage, key, &game)?;
+    DRAW_OFFERS.remove(deps.storage, game.id);
+    CHALLENGES.remove(deps.storage, game.id);
+    CLOCKS.remove(deps.storage, game.id);
+    release_escrow(deps.storage, &game.prize)?;
+
+    let mut sender_messages = vec![];
+    if !sender_stake.is_empty() {
+        sender_messages.push(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: sender_stake,
+        });
+    }
+    let mut other_messages = vec![];
+    if opponent_address == env.contract.address {
+        if was_playing {
+            let exposure = (HOUSE_EXPOSURE.load(deps.storage)? - other_stake.clone())?;
+            HOUSE_EXPOSURE.save(deps.storage, &exposure)?;
+            let bankroll = add_balance(BANKROLL.load(deps.storage)?, &other_stake)?;
+            BANKROLL.save(deps.storage, &bankroll)?;
+        }
+    } else if !other_stake.is_empty() {
+        other_messages.push(BankMsg::Send {
+            to_address: opponent_address.to_string(),
+            amount: other_stake,
+        });
+    }
+    if let Some(sponsor) = &game.sponsor {
+        other_messages.push(BankMsg::Send {
+            to_address: sponsor.to_string(),
+            amount: game.sponsorship.clone(),
+        });
+    }
+
+    let mut res = Response::new()
+        .add_attribute("method", "emergency_withdraw")
+        .add_attribute("opponent", opponent)
+        .add_event(events::game_voided(key.0, key.1, &game, &info.sender))
+        .add_events(events::prizes_paid(key.0, key.1, &game, &sender_messages))
+        .add_events(events::prizes_paid(key.0, key.1, &game, &other_messages))
+        .add_messages(sender_messages)
+        .add_messages(payouts(deps.storage, other_messages)?);
+
+    if !was_playing {
+        if let Some(sponsorship) = SPONSORSHIPS.may_load(deps.storage, key)? {
+            SPONSORSHIPS.remove(deps.storage, key);
+            res = return_sponsorship(deps.storage, res, key.0, key.1, sponsorship)?;
+        }
+    }
+
+    Ok(res)
+}
+
+/**
+    Holds the sent funds for the next game accepted between
+    host and opponent. Games against the house cannot be
+    sponsored and each pair has one sponsorship at a time.
+*/
+fn try_sponsor(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    host: String,
+    opponent: String,
+    expires: Expiration,
+) -> Result<Response, ContractError> {
+    let host_address = deps.api.addr_validate(&host)?;
+    let opponent_address = deps.api.addr_validate(&opponent)?;
+    let amount = normalize(&info.funds)?;
+
+    let reason = if amount.is_empty() {
+        Some("no funds were sent")
+    } else if host_address == opponent_address {
+        Some("host and opponent must be different players")
+    } else if host_address == env.contract.address || opponent_address == env.contract.address {
+        Some("games against the house cannot be sponsored")
+    } else if expires.is_expired(&env.block) {
+        Some("the sponsorship has already expired")
+    } else {
+        None
+    };
+    if let Some(reason) = reason {
+        return Err(ContractError::InvalidSponsorship {
+            reason: String::from(reason),
+        });
+    }
+
+    let key = (&host_address, &opponent_address);
+    if SPONSORSHIPS.has(deps.storage, key) {
+        return Err(ContractError::SponsorshipExists {
+            host: host_address,
+            opponent: opponent_address,
+        });
+    }
+
+    let sponsorship = Sponsorship {
+        sponsor: info.sender,
+        amount,
+        expires,
+    };
+    SPONSORSHIPS.save(deps.storage, key, &sponsorship)?;
+    lock_escrow(deps.storage, &sponsorship.amount)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "sponsor")
+        .add_attribute("host", host)
+        .add_attribute("opponent", opponent)
+        .add_event(events::sponsorship_added(key.0, key.1, &sponsorship)))
+}
+
+/**
+    Returns an expired sponsorship that no game used
+    to its sponsor, following the payout mode.
+*/
+fn try_reclaim_sponsorship(
+    deps: DepsMut,
+    env: Env,
+    host: String,
+    opponent: String,
+) -> Result<Response, ContractError> {
+    let host_address = deps.api.addr_validate(&host)?;
+    let opponent_address = deps.api.addr_validate(&opponent)?;
+    let key = (&host_address, &opponent_address);
+
+    let sponsorship = match SPONSORSHIPS.may_load(deps.storage, key)? {
+        Some(sponsorship) => sponsorship,
+        None => {
+            return Err(ContractError::SponsorshipNotFound {
+                host: host_address.clone(),
+                opponent: opponent_address.clone(),
+            })
+        }
+    };
+    if !sponsorship.expires.is_expired(&env.block) {
+        return Err(ContractError::SponsorshipNotExpired {
+            expires: sponsorship.expires,
+        });
+    }
+    SPONSORSHIPS.remove(deps.storage, key);
+
+    let res = Response::new()
+        .add_attribute("method", "reclaim_sponsorship")
+        .add_attribute("host", host)
+        .add_attribute("opponent", opponent);
+    return_sponsorship(deps.storage, res, key.0, key.1, sponsorship)
+}
+
+/**
+    Sends the funds held by the contract above its liabilities
+    (e.g. transfers that are not stakes) to the recipient,
+    which defaults to the admin.
+*/
+fn try_sweep_surplus(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender,
+    };
+
+    let surplus = query_solvency(deps.as_ref(), &env.contract.address)?.surplus;
+    if surplus.is_empty() {
+        return Err(ContractError::NoSurplus {});
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "sweep_surplus")
+        .add_attribute("recipient", recipient.clone())
+        .add_attribute("amount", events::format_coins(&surplus))
+        .add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: surplus,
+        }))
+}
+
+/**
+    The house plays deterministically, the seed only depends on
+    the block and on the number of moves already played so the
+    outcome can be reproduced from the chain history.
+*/
+fn house_move(env: &Env, game: &Game, difficulty: Difficulty) -> Result<Coord, ContractError> {
+    let played = game
+        .board
+        .iter()
+        .flatten()
+        .filter(|cell| cell.is_some())
+        .count() as u64;
+    let seed = env.block.height ^ env.block.time.nanos().rotate_left(32) ^ played;
+
+    bot::choose_move(game, difficulty, seed).ok_or(ContractError::HouseCannotPlay {})
+}
+
+/**
+    Release the house stake from the exposure and pay the prize
+    to the player or to the bankroll following the game result,
+    a tie returns each side its own stake.
+*/
+fn settle_house_game(
+    deps: DepsMut,
+    player: &Addr,
+    house: &Addr,
+    game: &Game,
+    res: Response,
+) -> Result<Response, ContractError> {
+    release_escrow(deps.storage, &game.prize)?;
+    let (player_stake, house_stake) = game.refunds();
+    let exposure = (HOUSE_EXPOSURE.load(deps.storage)? - house_stake.clone())?;
+    HOUSE_EXPOSURE.save(deps.storage, &exposure)?;
+
+    let (player_amount, house_amount) = match game.winner {
+        Some(winner) if winner == game.host_symbol => (game.prize.clone(), vec![]),
+        Some(_) => (vec![], game.prize.clone()),
+        None => (player_stake, house_stake),
+    };
+
+    let bankroll = add_balance(BANKROLL.load(deps.storage)?, &house_amount)?;
+    BANKROLL.save(deps.storage, &bankroll)?;
+
+    let mut res = res.add_event(events::game_completed(player, house, game));
+    if let Some(winner) = game.winner_address(player, house) {
+        res = res.add_attribute("winner", winner);
+    }
+    if !player_amount.is_empty() {
+        let message = BankMsg::Send {
+            to_address: player.to_string(),
+            amount: player_amount,
+        };
+        res = res
+            .add_events(events::prizes_paid(
+                player,
+                house,
+                game,
+                std::slice::from_ref(&message),
+            ))
+            .add_messages(payouts(deps.storage, vec![message])?);
+    }
+
+    Ok(res)
+}
+
+fn try_register_pubkey(
+    deps: DepsMut,
+    info: MessageInfo,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    PUBKEYS.save(deps.storage, &info.sender, &pubkey)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "register_pubkey")
+        .add_attribute("pubkey", pubkey.to_base64()))
+}
+
+/**
+    Replay the moves signed off-chain on top of the stored game.
+    Each move must be signed by the player in `player_round`, so the
+    signatures chain proves that both players agreed on every board.
+    The clocks are punched for every settled move at the time of the
+    settlement, a player whose flag has fallen loses before its move.
+    When the game is not completed, on-chain moves are blocked during
+    the challenge window so the other player can submit a later state.
+*/
+fn try_settle_signed_moves(
+    deps: DepsMut,
+    env: Env,
+    host: String,
+    opponent: String,
+    moves: Vec<SignedMove>,
+) -> Result<Response, ContractError> {
+    let host_address = deps.api.addr_validate(&host)?;
+    let opponent_address = deps.api.addr_validate(&opponent)?;
+    if moves.is_empty() {
+        return Err(ContractError::EmptySignedMoves {});
     }
+
+    let game = load_game(deps.storage, &host_address, &opponent_address)?
+        .filter(|game| game.status == Status::PLAYING)
+        .filter(|_| opponent_address != env.contract.address);
+
+    let mut game = match game {
+        Some(game) => game,
+        None => {
+            return Err(ContractError::InvalidGame {
+                host: host_address,
+                opponent: opponent_address,
+            })
+        }
+    };
+
+    let now = clock_time(deps.storage, env.block.time)?;
+    let mut clock = match game.time_control {
+        Some(_) => Some(load_clock(deps.storage, &game)?),
+        None => None,
+    };
+    let mut move_events = vec![];
+    for signed_move in &moves {
+        let coord = signed_move.coord;
+        if game.status != Status::PLAYING {
+            return Err(ContractError::InvalidGame {
+                host: host_address,
+                opponent: opponent_address,
+            });
+        }
+        match signed_move.from {
+            Some(from) => game.check_move_piece(from, coord)?,
+            None => game.check_move(coord)?,
+        }
+
+        let signer = if game.player_round == Some(game.host_symbol) {
+            host_address.clone()
+        } else {
+            opponent_address.clone()
+        };
+        let pubkey = PUBKEYS.may_load(deps.storage, &signer)?.ok_or_else(|| {
+            ContractError::PubkeyNotRegistered {
+                address: signer.clone(),
+            }
+        })?;
+
+        let mut next = game.clone();
+        match signed_move.from {
+            Some(from) => next.move_piece(from, coord)?,
+            None => next.play(coord)?,
+        };
+        let move_index = next.moves_count();
+        let hash = channel::move_hash(
+            next.id,
+            move_index,
+            signed_move.from,
+            &channel::board_hash(&next),
+        );
+        let verified = deps
+            .api
+            .secp256k1_verify(&hash, &signed_move.signature, &pubkey)
+            .unwrap_or(false);
+        if !verified {
+            return Err(ContractError::InvalidSignature { move_index });
+        }
+
+        if let (Some(clock), Some(time_control)) = (clock.as_mut(), game.time_control) {
+            if !clock.punch(signer == host_address, now, time_control.increment) {
+                CLOCKS.save(deps.storage, game.id, clock)?;
+                let res = Response::new().add_events(move_events);
+                let key = (&host_address, &opponent_address);
+                return flag_fallen(deps, env, res, game, key, &signer, false);
+            }
+        }
+        game = next;
+        game.complete_round()?;
+        move_events.push(match signed_move.from {
+            Some(from) => events::piece_moved(
+                &host_address,
+                &opponent_address,
+                &game,
+                &signer,
+                from,
+                coord,
+            ),
+            None => events::move_played(&host_address, &opponent_address, &game, &signer, coord),
+        });
+    }
+
+    GAMES.save(deps.storage, (&host_address, &opponent_address), &game)?;
+    DRAW_OFFERS.remove(deps.storage, game.id);
+
+    let res = Response::new()
+        .add_attribute("method", "settle_signed_moves")
+        .add_attribute("move_index", game.moves_count().to_string())
+        .add_attribute("status", game.status.to_string())
+        .add_events(move_events);
+
+    if game.status == Status::COMPLETED {
+        CHALLENGES.remove(deps.storage, game.id);
+        if let Some(clock) = clock {
+            CLOCKS.save(deps.storage, game.id, &clock)?;
+        }
+        return complete_game(deps.storage, res, &game, &host_address, &opponent_address);
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let deadline = env.block.time.plus_seconds(config.challenge_window);
+    CHALLENGES.save(deps.storage, game.id, &deadline)?;
+    if let Some(mut clock) = clock {
+        clock.since = now.plus_seconds(config.challenge_window);
+        CLOCKS.save(deps.storage, game.id, &clock)?;
+    }
+
+    Ok(res.add_attribute("challenge_deadline", deadline.to_string()))
+}
+
+fn next_game_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = GAME_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    GAME_COUNT.save(storage, &id)?;
+
+    Ok(id)
+}
+
+/**
+    Adds the winner address, the game_completed and prize_paid
+    events and the bank messages paying a COMPLETED game
+    between two players.
+*/
+fn complete_game(
+    storage: &mut dyn Storage,
+    res: Response,
+    game: &Game,
+    host: &Addr,
+    opponent: &Addr,
+) -> Result<Response, ContractError> {
+    release_escrow(storage, &game.prize)?;
+    let messages = prize_messages(game, host, opponent);
+    let res = match game.winner_address(host, opponent) {
+        Some(winner) => res.add_attribute("winner", winner),
+        None => res,
+    };
+
+    Ok(res
+        .add_event(events::game_completed(host, opponent, game))
+        .add_events(events::prizes_paid(host, opponent, game, &messages))
+        .add_messages(payouts(storage, messages)?))
+}
+
+/**
+    In PUSH mode the players are paid with the given bank
+    messages. In PULL mode the amounts are credited to the
+    claims of the recipients instead and no message is sent,
+    so a recipient that cannot receive funds never blocks
+    the game, it can only fail its own Claim. Messages
+    without an amount (e.g. the prize of a practice game
+    where nobody stakes anything) are never sent.
+*/
+fn payouts(
+    storage: &mut dyn Storage,
+    messages: Vec<BankMsg>,
+) -> Result<Vec<BankMsg>, ContractError> {
+    let messages: Vec<BankMsg> = messages
+        .into_iter()
+        .filter(|message| !matches!(message, BankMsg::Send { amount, .. } if amount.is_empty()))
+        .collect();
+    if CONFIG.load(storage)?.payout_mode == PayoutMode::PUSH {
+        return Ok(messages);
+    }
+
+    for message in messages {
+        if let BankMsg::Send { to_address, amount } = message {
+            let recipient = Addr::unchecked(to_address);
+            let claims = CLAIMS.may_load(storage, &recipient)?.unwrap_or_default();
+            CLAIMS.save(storage, &recipient, &add_balance(claims, &amount)?)?;
+            let total = add_balance(CLAIMS_TOTAL.load(storage)?, &amount)?;
+            CLAIMS_TOTAL.save(storage, &total)?;
+        }
+    }
+
+    Ok(vec![])
+}
+
+/**
+    Releases a sponsorship that no game used from the
+    escrow and pays it back to the sponsor.
+*/
+fn return_sponsorship(
+    storage: &mut dyn Storage,
+    res: Response,
+    host: &Addr,
+    opponent: &Addr,
+    sponsorship: Sponsorship,
+) -> Result<Response, ContractError> {
+    release_escrow(storage, &sponsorship.amount)?;
+    let refund = BankMsg::Send {
+        to_address: sponsorship.sponsor.to_string(),
+        amount: sponsorship.amount.clone(),
+    };
+
+    Ok(res
+        .add_event(events::sponsorship_returned(host, opponent, &sponsorship))
+        .add_messages(payouts(storage, vec![refund])?))
+}
+
+/**
+    Messages paying a COMPLETED game between two players. The
+    whole prize goes to the winner and in case of tie each player
+    receives its own stake back (see Game::refunds) and the
+    sponsor its sponsorship.
+*/
+pub(crate) fn prize_messages(game: &Game, host: &Addr, opponent: &Addr) -> Vec<BankMsg> {
+    match game.winner_address(host, opponent) {
+        Some(winner_address) => vec![BankMsg::Send {
+            to_address: winner_address.to_string(),
+            amount: game.prize.clone(),
+        }],
+        None => {
+            let (host_stake, opponent_stake) = game.refunds();
+            let mut refunds = vec![(host, host_stake), (opponent, opponent_stake)];
+            if let Some(sponsor) = &game.sponsor {
+                refunds.push((sponsor, game.sponsorship.clone()));
+            }
+            refunds
+                .into_iter()
+                .filter(|(_, amount)| !amount.is_empty())
+                .map(|(address, amount)| BankMsg::Send {
+                    to_address: address.to_string(),
+                    amount,
+                })
+                .collect()
+        }
+    }
+}
+
+/**
+    Clock of a PLAYING game with a time control, which is
+    started when the game is accepted.
+*/
+fn load_clock(storage: &dyn Storage, game: &Game) -> Result<Clock, ContractError> {
+    CLOCKS
+        .may_load(storage, game.id)?
+        .ok_or_else(|| ContractError::CorruptState {
+            reason: format!("game {} has a time control but no clock", game.id),
+        })
+}
+
+/**
+    Block time shown by the clocks, which do not run
+    while the moves are paused (see ClockPause).
+*/
+fn clock_time(storage: &dyn Storage, now: Timestamp) -> StdResult<Timestamp> {
+    Ok(CLOCK_PAUSE
+        .may_load(storage)?
+        .unwrap_or_default()
+        .clock_time(now))
+}
+
+/**
+    Loads the game between host and opponent, the storage
+    errors (e.g. a value that cannot be deserialized) are
+    reported with the key of the game.
+*/
+fn load_game(
+    storage: &dyn Storage,
+    host: &Addr,
+    opponent: &Addr,
+) -> Result<Option<Game>, ContractError> {
+    GAMES
+        .may_load(storage, (host, opponent))
+        .map_err(|error| ContractError::Storage {
+            host: host.clone(),
+            opponent: opponent.clone(),
+            error,
+        })
+}
+
+/**
+    Attribute values cannot be empty, so an
+    empty balance is written as "0".
+*/
+fn format_balance(balance: &NativeBalance) -> String {
+    if balance.is_empty() {
+        String::from("0")
+    } else {
+        balance.to_string()
+    }
+}
+
+/**
+    Sorts the coins by denom, merging the duplicated denoms
+    and leaving out the coins with no amount. Fails when the
+    merged amounts overflow instead of panicking.
+*/
+fn normalize(coins: &[Coin]) -> Result<Vec<Coin>, ContractError> {
+    Ok(add_balance(NativeBalance::default(), coins)?.into_vec())
+}
+
+/**
+    Adds the stakes received by a game to the escrow.
+*/
+fn lock_escrow(storage: &mut dyn Storage, coins: &[Coin]) -> Result<(), ContractError> {
+    let escrow = add_balance(ESCROW.load(storage)?, coins)?;
+    ESCROW.save(storage, &escrow)?;
+
+    Ok(())
+}
+
+/**
+    Removes the stakes of a game that is no longer in
+    status INVITED or PLAYING from the escrow, once they
+    are paid, credited or added to the bankroll.
+*/
+fn release_escrow(storage: &mut dyn Storage, coins: &[Coin]) -> Result<(), ContractError> {
+    let escrow = sub_balance(ESCROW.load(storage)?, coins)?;
+    ESCROW.save(storage, &escrow)?;
+
+    Ok(())
+}
+
+/**
+    Adds the coins to the balance failing when
+    any of the amounts overflows instead of panicking.
+*/
+fn add_balance(mut balance: NativeBalance, coins: &[Coin]) -> Result<NativeBalance, ContractError> {
+    for coin in coins {
+        match balance.0.iter_mut().find(|held| held.denom == coin.denom) {
+            Some(held) => held.amount = held.amount.checked_add(coin.amount)?,
+            None => balance.0.push(coin.clone()),
+        }
+    }
+    balance.normalize();
+
+    Ok(balance)
+}
+
+/**
+    Subtracts the coins from a ledger of the contract,
+    which can only fail if the ledger is corrupt.
+*/
+fn sub_balance(balance: NativeBalance, coins: &[Coin]) -> Result<NativeBalance, ContractError> {
+    let coins: Vec<Coin> = coins
+        .iter()
+        .filter(|coin| !coin.amount.is_zero())
+        .cloned()
+        .collect();
+
+    (balance - coins).map_err(|_| ContractError::CorruptState {
+        reason: String::from("cannot release more funds than the contract holds"),
+    })
 }
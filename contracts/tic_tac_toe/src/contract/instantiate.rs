@@ -1,20 +1,48 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cw_utils::NativeBalance;
 
-use crate::models::{
-    InstantiateMsg,
-    errors::ContractError
+use crate::{
+    models::{
+        errors::ContractError,
+        state::{Config, Difficulty, Pause, PayoutMode},
+        InstantiateMsg,
+    },
+    BANKROLL, CLAIMS_TOTAL, CONFIG, ESCROW, HOUSE_EXPOSURE,
 };
 
+const DEFAULT_CHALLENGE_WINDOW: u64 = 24 * 60 * 60;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
-    _msg: InstantiateMsg,
+    info: MessageInfo,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(&admin)?,
+        None => info.sender,
+    };
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            admin: admin.clone(),
+            house_difficulty: msg.house_difficulty.unwrap_or(Difficulty::PERFECT),
+            house_max_exposure: msg.house_max_exposure.unwrap_or_default(),
+            challenge_window: msg.challenge_window.unwrap_or(DEFAULT_CHALLENGE_WINDOW),
+            payout_mode: msg.payout_mode.unwrap_or(PayoutMode::PUSH),
+            pause: Pause::NONE,
+        },
+    )?;
+    BANKROLL.save(deps.storage, &NativeBalance::default())?;
+    HOUSE_EXPOSURE.save(deps.storage, &NativeBalance::default())?;
+    ESCROW.save(deps.storage, &NativeBalance::default())?;
+    CLAIMS_TOTAL.save(deps.storage, &NativeBalance::default())?;
 
     Ok(Response::new()
-        .add_attribute("method", "instantiate"))
+        .add_attribute("method", "instantiate")
+        .add_attribute("admin", admin))
 }
@@ -1,17 +1,38 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_binary, Binary, Deps, Env, StdResult};
+use std::collections::{BTreeMap, BTreeSet};
 
-use crate::models::state::Status;
+use cosmwasm_std::{to_binary, Addr, Binary, Coin, Deps, Env, StdError, StdResult, Uint128};
+
+use crate::models::state::{Clock, Status};
 use crate::models::QueryKey;
-use crate::models::{responses::GameResponse, QueryMsg};
-use crate::GAMES;
+use crate::models::{
+    responses::{
+        ChannelResponse, ClaimableResponse, ClockResponse, EvaluationResponse, GameResponse, HouseResponse,
+        PauseResponse, SessionKeyResponse, SolvencyResponse, SponsorshipResponse,
+    },
+    QueryMsg,
+};
+use crate::solver::Solver;
+use crate::{
+    BANKROLL, CHALLENGES, CLAIMS, CLAIMS_TOTAL, CLOCKS, CLOCK_PAUSE, CONFIG, ESCROW, GAMES,
+    HOUSE_EXPOSURE, SESSION_KEYS, SPONSORSHIPS,
+};
 use cosmwasm_std::Order;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Games { key, status } => to_binary(&query_games(deps, key, status)?),
+        QueryMsg::Evaluate { game } => to_binary(&query_evaluate(deps, game)?),
+        QueryMsg::House {} => to_binary(&query_house(deps)?),
+        QueryMsg::Claimable { address } => to_binary(&query_claimable(deps, address)?),
+        QueryMsg::Pause {} => to_binary(&query_pause(deps)?),
+        QueryMsg::Solvency {} => to_binary(&query_solvency(deps, &env.contract.address)?),
+        QueryMsg::Sponsorship { game } => to_binary(&query_sponsorship(deps, game)?),
+        QueryMsg::Channel { game } => to_binary(&query_channel(deps, game)?),
+        QueryMsg::SessionKeys { player } => to_binary(&query_session_keys(deps, env, player)?),
+        QueryMsg::Clock { game } => to_binary(&query_clock(deps, env, game)?),
     }
 }
 
@@ -27,9 +48,7 @@ fn query_games(
             let host_address = deps.api.addr_validate(&addresses.host)?;
             let opponent_address = deps.api.addr_validate(&addresses.opponent)?;
 
-            let game_option = GAMES
-                .may_load(deps.storage, (&host_address, &opponent_address))
-                .unwrap();
+            let game_option = GAMES.may_load(deps.storage, (&host_address, &opponent_address))?;
 
             match game_option {
                 Some(_game) => {
@@ -46,27 +65,215 @@ fn query_games(
             res = GAMES
                 .range(deps.storage, None, None, Order::Ascending)
                 .map(|f| {
-                    let (addresses, game) = f.unwrap();
+                    let (addresses, game) = f?;
 
-                    GameResponse {
-                        game: game,
+                    Ok(GameResponse {
+                        game,
                         host: addresses.0,
                         opponent: addresses.1,
-                    }
+                    })
                 })
-                .collect();
+                .collect::<StdResult<_>>()?;
         }
     }
 
-    match status {
-        Some(status) => {
-            res = res
-                .into_iter()
-                .filter(|res| res.game.status == status)
-                .collect()
-        }
-        None => {}
+    if let Some(status) = status {
+        res.retain(|res| res.game.status == status);
     }
 
     Ok(res)
 }
+
+fn query_evaluate(deps: Deps, key: QueryKey) -> StdResult<EvaluationResponse> {
+    let host_address = deps.api.addr_validate(&key.host)?;
+    let opponent_address = deps.api.addr_validate(&key.opponent)?;
+
+    let game = GAMES
+        .may_load(deps.storage, (&host_address, &opponent_address))?
+        .filter(|game| game.status == Status::PLAYING)
+        .ok_or_else(|| StdError::not_found("PLAYING game"))?;
+
+    let player_round = game
+        .player_round
+        .ok_or_else(|| StdError::generic_err("Game has no player to move"))?;
+
+    match Solver::new().evaluate(&game) {
+        Some((value, optimal_moves)) => Ok(EvaluationResponse {
+            player_round,
+            value,
+            optimal_moves,
+        }),
+        None => Err(StdError::generic_err("Game cannot be evaluated")),
+    }
+}
+
+fn query_house(deps: Deps) -> StdResult<HouseResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(HouseResponse {
+        difficulty: config.house_difficulty,
+        max_exposure: config.house_max_exposure,
+        bankroll: BANKROLL.load(deps.storage)?.into_vec(),
+        exposure: HOUSE_EXPOSURE.load(deps.storage)?.into_vec(),
+    })
+}
+
+fn query_claimable(deps: Deps, address: String) -> StdResult<ClaimableResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let claims = CLAIMS.may_load(deps.storage, &address)?.unwrap_or_default();
+
+    Ok(ClaimableResponse {
+        amount: claims.into_vec(),
+    })
+}
+
+fn query_pause(deps: Deps) -> StdResult<PauseResponse> {
+    Ok(PauseResponse {
+        pause: CONFIG.load(deps.storage)?.pause,
+    })
+}
+
+/**
+    The sponsorship is returned even when it has expired
+    and can only be reclaimed, see `expires`.
+*/
+fn query_sponsorship(deps: Deps, game: QueryKey) -> StdResult<SponsorshipResponse> {
+    let host_address = deps.api.addr_validate(&game.host)?;
+    let opponent_address = deps.api.addr_validate(&game.opponent)?;
+
+    Ok(SponsorshipResponse {
+        sponsorship: SPONSORSHIPS.may_load(deps.storage, (&host_address, &opponent_address))?,
+    })
+}
+
+/**
+    Compares the balance of the contract with the sum of its
+    liabilities per denom, the balance above them is surplus
+    and the liabilities not covered by it are deficit.
+*/
+pub(crate) fn query_solvency(deps: Deps, contract: &Addr) -> StdResult<SolvencyResponse> {
+    let balance = deps.querier.query_all_balances(contract)?;
+    let escrow = ESCROW.load(deps.storage)?.into_vec();
+    let claims = CLAIMS_TOTAL.load(deps.storage)?.into_vec();
+    let bankroll = BANKROLL.load(deps.storage)?.into_vec();
+
+    let mut owed: BTreeMap<&str, Uint128> = BTreeMap::new();
+    for coin in escrow.iter().chain(&claims).chain(&bankroll) {
+        let amount = owed.entry(&coin.denom).or_default();
+        *amount = amount.checked_add(coin.amount)?;
+    }
+    let held: BTreeMap<&str, Uint128> = balance
+        .iter()
+        .map(|coin| (coin.denom.as_str(), coin.amount))
+        .collect();
+
+    let denoms: BTreeSet<&str> = owed.keys().chain(held.keys()).copied().collect();
+    let mut surplus = vec![];
+    let mut deficit = vec![];
+    for denom in denoms {
+        let held_amount = held.get(denom).copied().unwrap_or_default();
+        let owed_amount = owed.get(denom).copied().unwrap_or_default();
+        if held_amount > owed_amount {
+            surplus.push(Coin { denom: denom.to_string(), amount: held_amount - owed_amount });
+        } else if owed_amount > held_amount {
+            deficit.push(Coin { denom: denom.to_string(), amount: owed_amount - held_amount });
+        }
+    }
+    let liabilities = owed
+        .iter()
+        .map(|(denom, amount)| Coin { denom: denom.to_string(), amount: *amount })
+        .collect();
+
+    Ok(SolvencyResponse {
+        solvent: deficit.is_empty(),
+        balance,
+        escrow,
+        claims,
+        bankroll,
+        liabilities,
+        surplus,
+        deficit,
+    })
+}
+
+fn query_channel(deps: Deps, key: QueryKey) -> StdResult<ChannelResponse> {
+    let host_address = deps.api.addr_validate(&key.host)?;
+    let opponent_address = deps.api.addr_validate(&key.opponent)?;
+
+    let game = GAMES
+        .may_load(deps.storage, (&host_address, &opponent_address))?
+        .ok_or_else(|| StdError::not_found("Game"))?;
+
+    Ok(ChannelResponse {
+        game_id: game.id,
+        move_index: game.moves_count(),
+        challenge_deadline: CHALLENGES.may_load(deps.storage, game.id)?,
+    })
+}
+
+/**
+    Before the game is accepted, or once it is voided, both players
+    have the whole time bank. Once it is finished the clock shows the
+    time left after the last move, or zero to the player whose flag
+    has fallen.
+*/
+fn query_clock(deps: Deps, env: Env, key: QueryKey) -> StdResult<ClockResponse> {
+    let host_address = deps.api.addr_validate(&key.host)?;
+    let opponent_address = deps.api.addr_validate(&key.opponent)?;
+
+    let game = GAMES
+        .may_load(deps.storage, (&host_address, &opponent_address))?
+        .ok_or_else(|| StdError::not_found("Game"))?;
+    let time_control = game
+        .time_control
+        .ok_or_else(|| StdError::not_found("Clock"))?;
+
+    let now = CLOCK_PAUSE
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .clock_time(env.block.time);
+    let clock = CLOCKS
+        .may_load(deps.storage, game.id)?
+        .unwrap_or_else(|| Clock::new(time_control, now));
+    let running = game
+        .player_round
+        .filter(|_| game.status == Status::PLAYING);
+    let host_running = running == Some(game.host_symbol);
+    let opponent_running = running == Some(game.host_symbol.other());
+    let host_remaining = clock.remaining(true, host_running, now);
+    let opponent_remaining = clock.remaining(false, opponent_running, now);
+
+    Ok(ClockResponse {
+        game_id: game.id,
+        time_control,
+        host_remaining,
+        opponent_remaining,
+        running,
+        flag_fallen: host_remaining == 0 || opponent_remaining == 0,
+    })
+}
+
+fn query_session_keys(
+    deps: Deps,
+    env: Env,
+    player: String,
+) -> StdResult<Vec<SessionKeyResponse>> {
+    let player_address = deps.api.addr_validate(&player)?;
+
+    SESSION_KEYS
+        .prefix(&player_address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, key)) => !key.expires.is_expired(&env.block),
+            Err(_) => true,
+        })
+        .map(|item| {
+            let (session_key, key) = item?;
+            Ok(SessionKeyResponse {
+                session_key,
+                expires: key.expires,
+                scope: key.scope,
+            })
+        })
+        .collect()
+}
@@ -1,9 +1,27 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Map;
-use models::state::Game;
+use cosmwasm_std::{Addr, Binary, Timestamp};
+use cw_storage_plus::{Item, Map};
+use cw_utils::NativeBalance;
+use models::state::{Clock, ClockPause, Config, Game, PlayerSymbol, SessionKey, Sponsorship};
 
+pub mod bot;
+pub mod channel;
 pub mod contract;
 pub mod models;
+pub mod solver;
 pub mod test;
 
-pub const GAMES: Map<(&Addr, &Addr), Game> = Map::new("games");
\ No newline at end of file
+pub const GAMES: Map<(&Addr, &Addr), Game> = Map::new("games");
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const BANKROLL: Item<NativeBalance> = Item::new("bankroll");
+pub const HOUSE_EXPOSURE: Item<NativeBalance> = Item::new("house_exposure");
+pub const CLAIMS: Map<&Addr, NativeBalance> = Map::new("claims");
+pub const CLAIMS_TOTAL: Item<NativeBalance> = Item::new("claims_total");
+pub const ESCROW: Item<NativeBalance> = Item::new("escrow");
+pub const GAME_COUNT: Item<u64> = Item::new("game_count");
+pub const PUBKEYS: Map<&Addr, Binary> = Map::new("pubkeys");
+pub const CHALLENGES: Map<u64, Timestamp> = Map::new("challenges");
+pub const CLOCKS: Map<u64, Clock> = Map::new("clocks");
+pub const CLOCK_PAUSE: Item<ClockPause> = Item::new("clock_pause");
+pub const DRAW_OFFERS: Map<u64, PlayerSymbol> = Map::new("draw_offers");
+pub const SESSION_KEYS: Map<(&Addr, &Addr), SessionKey> = Map::new("session_keys");
+pub const SPONSORSHIPS: Map<(&Addr, &Addr), Sponsorship> = Map::new("sponsorships");
\ No newline at end of file
@@ -1,37 +1,313 @@
-use cosmwasm_std::{Addr, StdError};
+use std::fmt;
+
+use cosmwasm_std::{Addr, OverflowError, StdError, Timestamp};
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use super::state::Coord;
+use super::state::{Coord, Opening, Pause};
 
+/**
+    Every message starts with the code of the error
+    between brackets, e.g. "[invalid_coord] Invalid
+    coordinate x=3 y=0...", see ErrorCode.
+*/
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
-    #[error("{0}")]
+    #[error("[std] {0}")]
     Std(#[from] StdError),
 
-    #[error("Unauthorized")]
+    #[error("[unauthorized] Unauthorized")]
     Unauthorized {},
 
-    #[error("Game against yourself cannot be started")]
+    #[error("[cannot_start_game] Game against yourself cannot be started")]
     CannotStartGame {},
 
-    #[error("Game between {host} and {opponent} already exists. Complete the previous game to start a new one")]
+    #[error("[game_already_in_progress] Game between {host} and {opponent} already exists. Complete the previous game to start a new one")]
     GameAlreadyInProgress { host: Addr, opponent: Addr },
 
-    #[error("Game between {host} and {opponent} not found. You cannot reject it")]
+    #[error("[game_not_found] Game between {host} and {opponent} not found. You cannot reject it")]
     GameNotFound { host: Addr, opponent: Addr },
 
-    #[error("Game between {host} and {opponent} is invalid. Try starting another game.")]
+    #[error(
+        "[invalid_game] Game between {host} and {opponent} is invalid. Try starting another game."
+    )]
     InvalidGame { host: Addr, opponent: Addr },
 
-    #[error("x={{coord.x}} and y={{coord.y}} already contain symbol. Try using another coordinate.")]
+    #[error("[coordinate_already_played] x={} and y={} already contain symbol. Try using another coordinate.", coord.x, coord.y)]
     CoordinateAlreadyPlayed { coord: Coord },
 
-    #[error("You already played this turn. Wait for '{{second_player}}' to play its turn.")]
-    TurnAlreadyPlayed { second_player: String},
+    #[error("[turn_already_played] You already played this turn. Wait for '{second_player}' to play its turn.")]
+    TurnAlreadyPlayed { second_player: String },
 
-    #[error("Invalid coordinate x={{coord.x}} y={{coord.y}}. Coordinates must be between 0 and 2")]
+    #[error("[invalid_coord] Invalid coordinate x={} y={}. Coordinates must be between 0 and 2", coord.x, coord.y)]
     InvalidCoord { coord: Coord },
 
-    #[error("The funds you send must be equal to the prize of the game")]
+    #[error("[invalid_received_funds] The funds you send must be equal to your stake in the game")]
     InvalidReceivedFunds {},
-}
\ No newline at end of file
+
+    #[error("[practice_game_stake] A practice game is played without stakes")]
+    PracticeGameStake {},
+
+    #[error("[insufficient_bankroll] The house bankroll cannot cover the requested amount")]
+    InsufficientBankroll {},
+
+    #[error("[empty_withdrawal] The amount to withdraw from the bankroll is empty")]
+    EmptyWithdrawal {},
+
+    #[error("[no_surplus] The contract holds no funds above its liabilities")]
+    NoSurplus {},
+
+    #[error("[nothing_to_claim] {address} has nothing to claim")]
+    NothingToClaim { address: Addr },
+
+    #[error("[paused] The contract is paused ({pause}) and this message is blocked")]
+    Paused { pause: Pause },
+
+    #[error("[not_paused] Stakes can only be withdrawn while the contract is paused")]
+    NotPaused {},
+
+    #[error("[house_exposure_exceeded] The game exceeds the maximum exposure of the house")]
+    HouseExposureExceeded {},
+
+    #[error("[house_uneven_stakes] The house only plays games with equal stakes")]
+    HouseUnevenStakes {},
+
+    #[error("[house_cannot_play] The house cannot find a move to play")]
+    HouseCannotPlay {},
+
+    #[error("[pubkey_not_registered] {address} has not registered a public key to sign moves")]
+    PubkeyNotRegistered { address: Addr },
+
+    #[error("[invalid_signature] Invalid signature for move {move_index}")]
+    InvalidSignature { move_index: u32 },
+
+    #[error("[empty_signed_moves] At least one signed move must be submitted")]
+    EmptySignedMoves {},
+
+    #[error("[challenge_window_open] A signed state can be challenged until {deadline}. Moves cannot be played before.")]
+    ChallengeWindowOpen { deadline: Timestamp },
+
+    #[error("[session_key_unauthorized] {session_key} is not authorized to execute this action on behalf of {player}")]
+    SessionKeyUnauthorized { session_key: Addr, player: Addr },
+
+    #[error("[invalid_sponsorship] Invalid sponsorship: {reason}")]
+    InvalidSponsorship { reason: String },
+
+    #[error(
+        "[sponsorship_exists] The next game between {host} and {opponent} is already sponsored"
+    )]
+    SponsorshipExists { host: Addr, opponent: Addr },
+
+    #[error("[sponsorship_not_found] There is no sponsorship for the games between {host} and {opponent}")]
+    SponsorshipNotFound { host: Addr, opponent: Addr },
+
+    #[error("[sponsorship_not_expired] The sponsorship can only be reclaimed once it expires ({expires})")]
+    SponsorshipNotExpired { expires: Expiration },
+
+    #[error("[invalid_rematch] Invalid rematch: {reason}")]
+    InvalidRematch { reason: String },
+
+    #[error("[forbidden_opening] The first move x={} y={} is not allowed by the {} opening", coord.x, coord.y, opening)]
+    ForbiddenOpening { coord: Coord, opening: Opening },
+
+    #[error("[swap_not_allowed] Sides can only be swapped by the opponent of a game with the PIE opening instead of playing the second move")]
+    SwapNotAllowed {},
+
+    #[error("[invalid_time_control] The time bank of a time control must be greater than zero")]
+    InvalidTimeControl {},
+
+    #[error("[flag_not_fallen] The player in round has {remaining} seconds left. Its flag has not fallen")]
+    FlagNotFallen { remaining: u64 },
+
+    #[error("[invalid_variant] Invalid variant: {reason}")]
+    InvalidVariant { reason: String },
+
+    #[error("[invalid_column] The column {column} is not on the board of {columns} columns")]
+    InvalidColumn { column: u8, columns: u8 },
+
+    #[error("[column_full] The column {column} is full")]
+    ColumnFull { column: u8 },
+
+    #[error("[piece_must_fall] A piece played on x={} y={} falls to x={} y={}", coord.x, coord.y, landing.x, landing.y)]
+    PieceMustFall { coord: Coord, landing: Coord },
+
+    #[error("[pieces_placed] Both players have placed their three pieces, move one of them with MovePiece")]
+    PiecesPlaced {},
+
+    #[error("[invalid_piece_move] Invalid piece move: {reason}")]
+    InvalidPieceMove { reason: String },
+
+    #[error("[overflow] Amount overflow: {0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("[corrupt_state] Corrupt game state: {reason}")]
+    CorruptState { reason: String },
+
+    #[error("[storage] Cannot read the game between {host} and {opponent}: {error}")]
+    Storage {
+        host: Addr,
+        opponent: Addr,
+        error: StdError,
+    },
+}
+
+/**
+    Stable code of each ContractError so front-ends can
+    localise the messages. Codes are never renamed or
+    reused, new errors get new codes.
+*/
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Std,
+    Unauthorized,
+    CannotStartGame,
+    GameAlreadyInProgress,
+    GameNotFound,
+    InvalidGame,
+    CoordinateAlreadyPlayed,
+    TurnAlreadyPlayed,
+    InvalidCoord,
+    InvalidReceivedFunds,
+    PracticeGameStake,
+    InsufficientBankroll,
+    EmptyWithdrawal,
+    NoSurplus,
+    NothingToClaim,
+    Paused,
+    NotPaused,
+    HouseExposureExceeded,
+    HouseUnevenStakes,
+    HouseCannotPlay,
+    PubkeyNotRegistered,
+    InvalidSignature,
+    EmptySignedMoves,
+    ChallengeWindowOpen,
+    SessionKeyUnauthorized,
+    InvalidSponsorship,
+    SponsorshipExists,
+    SponsorshipNotFound,
+    SponsorshipNotExpired,
+    InvalidRematch,
+    ForbiddenOpening,
+    SwapNotAllowed,
+    InvalidTimeControl,
+    FlagNotFallen,
+    InvalidVariant,
+    InvalidColumn,
+    ColumnFull,
+    PieceMustFall,
+    PiecesPlaced,
+    InvalidPieceMove,
+    Overflow,
+    CorruptState,
+    Storage,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::Std => "std",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::CannotStartGame => "cannot_start_game",
+            ErrorCode::GameAlreadyInProgress => "game_already_in_progress",
+            ErrorCode::GameNotFound => "game_not_found",
+            ErrorCode::InvalidGame => "invalid_game",
+            ErrorCode::CoordinateAlreadyPlayed => "coordinate_already_played",
+            ErrorCode::TurnAlreadyPlayed => "turn_already_played",
+            ErrorCode::InvalidCoord => "invalid_coord",
+            ErrorCode::InvalidReceivedFunds => "invalid_received_funds",
+            ErrorCode::PracticeGameStake => "practice_game_stake",
+            ErrorCode::InsufficientBankroll => "insufficient_bankroll",
+            ErrorCode::EmptyWithdrawal => "empty_withdrawal",
+            ErrorCode::NoSurplus => "no_surplus",
+            ErrorCode::NothingToClaim => "nothing_to_claim",
+            ErrorCode::Paused => "paused",
+            ErrorCode::NotPaused => "not_paused",
+            ErrorCode::HouseExposureExceeded => "house_exposure_exceeded",
+            ErrorCode::HouseUnevenStakes => "house_uneven_stakes",
+            ErrorCode::HouseCannotPlay => "house_cannot_play",
+            ErrorCode::PubkeyNotRegistered => "pubkey_not_registered",
+            ErrorCode::InvalidSignature => "invalid_signature",
+            ErrorCode::EmptySignedMoves => "empty_signed_moves",
+            ErrorCode::ChallengeWindowOpen => "challenge_window_open",
+            ErrorCode::SessionKeyUnauthorized => "session_key_unauthorized",
+            ErrorCode::InvalidSponsorship => "invalid_sponsorship",
+            ErrorCode::SponsorshipExists => "sponsorship_exists",
+            ErrorCode::SponsorshipNotFound => "sponsorship_not_found",
+            ErrorCode::SponsorshipNotExpired => "sponsorship_not_expired",
+            ErrorCode::InvalidRematch => "invalid_rematch",
+            ErrorCode::ForbiddenOpening => "forbidden_opening",
+            ErrorCode::SwapNotAllowed => "swap_not_allowed",
+            ErrorCode::InvalidTimeControl => "invalid_time_control",
+            ErrorCode::FlagNotFallen => "flag_not_fallen",
+            ErrorCode::InvalidVariant => "invalid_variant",
+            ErrorCode::InvalidColumn => "invalid_column",
+            ErrorCode::ColumnFull => "column_full",
+            ErrorCode::PieceMustFall => "piece_must_fall",
+            ErrorCode::PiecesPlaced => "pieces_placed",
+            ErrorCode::InvalidPieceMove => "invalid_piece_move",
+            ErrorCode::Overflow => "overflow",
+            ErrorCode::CorruptState => "corrupt_state",
+            ErrorCode::Storage => "storage",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ContractError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ContractError::Std(_) => ErrorCode::Std,
+            ContractError::Unauthorized { .. } => ErrorCode::Unauthorized,
+            ContractError::CannotStartGame { .. } => ErrorCode::CannotStartGame,
+            ContractError::GameAlreadyInProgress { .. } => ErrorCode::GameAlreadyInProgress,
+            ContractError::GameNotFound { .. } => ErrorCode::GameNotFound,
+            ContractError::InvalidGame { .. } => ErrorCode::InvalidGame,
+            ContractError::CoordinateAlreadyPlayed { .. } => ErrorCode::CoordinateAlreadyPlayed,
+            ContractError::TurnAlreadyPlayed { .. } => ErrorCode::TurnAlreadyPlayed,
+            ContractError::InvalidCoord { .. } => ErrorCode::InvalidCoord,
+            ContractError::InvalidReceivedFunds { .. } => ErrorCode::InvalidReceivedFunds,
+            ContractError::PracticeGameStake { .. } => ErrorCode::PracticeGameStake,
+            ContractError::InsufficientBankroll { .. } => ErrorCode::InsufficientBankroll,
+            ContractError::EmptyWithdrawal { .. } => ErrorCode::EmptyWithdrawal,
+            ContractError::NoSurplus { .. } => ErrorCode::NoSurplus,
+            ContractError::NothingToClaim { .. } => ErrorCode::NothingToClaim,
+            ContractError::Paused { .. } => ErrorCode::Paused,
+            ContractError::NotPaused { .. } => ErrorCode::NotPaused,
+            ContractError::HouseExposureExceeded { .. } => ErrorCode::HouseExposureExceeded,
+            ContractError::HouseUnevenStakes { .. } => ErrorCode::HouseUnevenStakes,
+            ContractError::HouseCannotPlay { .. } => ErrorCode::HouseCannotPlay,
+            ContractError::PubkeyNotRegistered { .. } => ErrorCode::PubkeyNotRegistered,
+            ContractError::InvalidSignature { .. } => ErrorCode::InvalidSignature,
+            ContractError::EmptySignedMoves { .. } => ErrorCode::EmptySignedMoves,
+            ContractError::ChallengeWindowOpen { .. } => ErrorCode::ChallengeWindowOpen,
+            ContractError::SessionKeyUnauthorized { .. } => ErrorCode::SessionKeyUnauthorized,
+            ContractError::InvalidSponsorship { .. } => ErrorCode::InvalidSponsorship,
+            ContractError::SponsorshipExists { .. } => ErrorCode::SponsorshipExists,
+            ContractError::SponsorshipNotFound { .. } => ErrorCode::SponsorshipNotFound,
+            ContractError::SponsorshipNotExpired { .. } => ErrorCode::SponsorshipNotExpired,
+            ContractError::InvalidRematch { .. } => ErrorCode::InvalidRematch,
+            ContractError::ForbiddenOpening { .. } => ErrorCode::ForbiddenOpening,
+            ContractError::SwapNotAllowed { .. } => ErrorCode::SwapNotAllowed,
+            ContractError::InvalidTimeControl { .. } => ErrorCode::InvalidTimeControl,
+            ContractError::FlagNotFallen { .. } => ErrorCode::FlagNotFallen,
+            ContractError::InvalidVariant { .. } => ErrorCode::InvalidVariant,
+            ContractError::InvalidColumn { .. } => ErrorCode::InvalidColumn,
+            ContractError::ColumnFull { .. } => ErrorCode::ColumnFull,
+            ContractError::PieceMustFall { .. } => ErrorCode::PieceMustFall,
+            ContractError::PiecesPlaced { .. } => ErrorCode::PiecesPlaced,
+            ContractError::InvalidPieceMove { .. } => ErrorCode::InvalidPieceMove,
+            ContractError::Overflow(_) => ErrorCode::Overflow,
+            ContractError::CorruptState { .. } => ErrorCode::CorruptState,
+            ContractError::Storage { .. } => ErrorCode::Storage,
+        }
+    }
+}
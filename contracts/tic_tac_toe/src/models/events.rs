@@ -0,0 +1,270 @@
+use cosmwasm_std::{Addr, BankMsg, Coin, Event};
+
+use crate::channel::board_hash;
+
+use super::state::{Coord, Game, Sponsorship};
+
+/**
+    Typed events emitted by the contract so an indexer can rebuild
+    the state of every game from the events alone. Every game event
+    contains the attributes game_id, host, opponent, status and
+    board_hash (hex encoded sha256 of the board as defined in the
+    channel module) with the values after the action took place.
+*/
+pub const GAME_CREATED: &str = "tic_tac_toe.game_created";
+pub const GAME_ACCEPTED: &str = "tic_tac_toe.game_accepted";
+pub const GAME_REJECTED: &str = "tic_tac_toe.game_rejected";
+pub const SIDES_SWAPPED: &str = "tic_tac_toe.sides_swapped";
+pub const MOVE_PLAYED: &str = "tic_tac_toe.move_played";
+pub const GAME_COMPLETED: &str = "tic_tac_toe.game_completed";
+pub const FLAG_FALLEN: &str = "tic_tac_toe.flag_fallen";
+pub const GAME_VOIDED: &str = "tic_tac_toe.game_voided";
+pub const PRIZE_PAID: &str = "tic_tac_toe.prize_paid";
+pub const SPONSORSHIP_ADDED: &str = "tic_tac_toe.sponsorship_added";
+pub const SPONSORSHIP_RETURNED: &str = "tic_tac_toe.sponsorship_returned";
+
+/**
+    Adds host_symbol, x, y of the first move, the amount
+    staked by the host, the opponent_amount required from
+    the opponent to accept, the opening, the variant (e.g.
+    GRAVITY columns=7 rows=6) and the time_control
+    (base+increment in seconds, e.g. 300+5) when it is set,
+    and practice=true for a practice game.
+*/
+pub fn game_created(host: &Addr, opponent: &Addr, game: &Game, coord: Coord) -> Event {
+    let event = game_event(GAME_CREATED, host, opponent, game)
+        .add_attribute("host_symbol", game.host_symbol.to_string())
+        .add_attribute("x", coord.x.to_string())
+        .add_attribute("y", coord.y.to_string())
+        .add_attribute("amount", format_coins(&game.host_stake))
+        .add_attribute("opponent_amount", format_coins(&game.opponent_stake))
+        .add_attribute("opening", game.opening.to_string())
+        .add_attribute("variant", game.variant.to_string());
+
+    let event = match game.time_control {
+        Some(time_control) => event.add_attribute("time_control", time_control.to_string()),
+        None => event,
+    };
+    if game.practice {
+        event.add_attribute("practice", "true")
+    } else {
+        event
+    }
+}
+
+/**
+    Same attributes as game_created for a rematch, where x and y
+    are omitted when the opponent plays the first move, plus the
+    id of the game rematched and the winnings reinvested from the
+    claims of the host, if any.
+*/
+pub fn rematch_created(
+    host: &Addr,
+    opponent: &Addr,
+    game: &Game,
+    coord: Option<Coord>,
+    rematch_of: u64,
+    reinvested: &[Coin],
+) -> Event {
+    let mut event = game_event(GAME_CREATED, host, opponent, game)
+        .add_attribute("host_symbol", game.host_symbol.to_string());
+    if let Some(coord) = coord {
+        event = event
+            .add_attribute("x", coord.x.to_string())
+            .add_attribute("y", coord.y.to_string());
+    }
+    event = event
+        .add_attribute("amount", format_coins(&game.host_stake))
+        .add_attribute("opponent_amount", format_coins(&game.opponent_stake))
+        .add_attribute("opening", game.opening.to_string())
+        .add_attribute("variant", game.variant.to_string());
+    if let Some(time_control) = game.time_control {
+        event = event.add_attribute("time_control", time_control.to_string());
+    }
+    if game.practice {
+        event = event.add_attribute("practice", "true");
+    }
+    event = event.add_attribute("rematch_of", rematch_of.to_string());
+
+    if reinvested.is_empty() {
+        event
+    } else {
+        event.add_attribute("reinvested", format_coins(reinvested))
+    }
+}
+
+/**
+    Adds the amount staked by the opponent and the resulting
+    prize, and the sponsor and sponsorship added to the prize
+    when the game is sponsored.
+*/
+pub fn game_accepted(host: &Addr, opponent: &Addr, game: &Game, amount: &[Coin]) -> Event {
+    let event = game_event(GAME_ACCEPTED, host, opponent, game)
+        .add_attribute("amount", format_coins(amount))
+        .add_attribute("prize", format_coins(&game.prize));
+
+    match &game.sponsor {
+        Some(sponsor) => event
+            .add_attribute("sponsor", sponsor)
+            .add_attribute("sponsorship", format_coins(&game.sponsorship)),
+        None => event,
+    }
+}
+
+pub fn game_rejected(host: &Addr, opponent: &Addr, game: &Game) -> Event {
+    game_event(GAME_REJECTED, host, opponent, game)
+}
+
+/**
+    The opponent took the first move under the pie rule,
+    adds the host_symbol after the swap.
+*/
+pub fn sides_swapped(host: &Addr, opponent: &Addr, game: &Game) -> Event {
+    game_event(SIDES_SWAPPED, host, opponent, game)
+        .add_attribute("host_symbol", game.host_symbol.to_string())
+}
+
+/**
+    Adds the player address and symbol, x, y and the move_index
+    (number of symbols on the board plus pieces moved after the move).
+*/
+pub fn move_played(
+    host: &Addr,
+    opponent: &Addr,
+    game: &Game,
+    player: &Addr,
+    coord: Coord,
+) -> Event {
+    let symbol = game.board[coord.y as usize][coord.x as usize]
+        .map(|symbol| symbol.to_string())
+        .unwrap_or_default();
+
+    game_event(MOVE_PLAYED, host, opponent, game)
+        .add_attribute("player", player)
+        .add_attribute("symbol", symbol)
+        .add_attribute("x", coord.x.to_string())
+        .add_attribute("y", coord.y.to_string())
+        .add_attribute("move_index", game.moves_count().to_string())
+}
+
+/**
+    Same attributes as move_played, where x and y are the cell
+    the piece moved to, plus from_x and from_y of its origin.
+*/
+pub fn piece_moved(
+    host: &Addr,
+    opponent: &Addr,
+    game: &Game,
+    player: &Addr,
+    from: Coord,
+    to: Coord,
+) -> Event {
+    move_played(host, opponent, game, player, to)
+        .add_attribute("from_x", from.x.to_string())
+        .add_attribute("from_y", from.y.to_string())
+}
+
+/**
+    Adds the prize of the game and the winner address,
+    which is omitted when the game finished as a tie.
+*/
+pub fn game_completed(host: &Addr, opponent: &Addr, game: &Game) -> Event {
+    let event = game_event(GAME_COMPLETED, host, opponent, game)
+        .add_attribute("prize", format_coins(&game.prize));
+
+    match game.winner_address(host, opponent) {
+        Some(winner) => event.add_attribute("winner", winner),
+        None => event,
+    }
+}
+
+/**
+    Adds the player whose flag has fallen, which
+    loses the game on time.
+*/
+pub fn flag_fallen(host: &Addr, opponent: &Addr, game: &Game, player: &Addr) -> Event {
+    game_event(FLAG_FALLEN, host, opponent, game).add_attribute("player", player)
+}
+
+/**
+    Adds the player that withdrew its stake.
+*/
+pub fn game_voided(host: &Addr, opponent: &Addr, game: &Game, player: &Addr) -> Event {
+    game_event(GAME_VOIDED, host, opponent, game).add_attribute("player", player)
+}
+
+/**
+    One event per bank message with the recipient and the
+    amount, messages without an amount are left out.
+*/
+pub fn prizes_paid(host: &Addr, opponent: &Addr, game: &Game, messages: &[BankMsg]) -> Vec<Event> {
+    messages
+        .iter()
+        .filter_map(|message| match message {
+            BankMsg::Send { to_address, amount } if !amount.is_empty() => Some(
+                Event::new(PRIZE_PAID)
+                    .add_attribute("game_id", game.id.to_string())
+                    .add_attribute("host", host)
+                    .add_attribute("opponent", opponent)
+                    .add_attribute("recipient", to_address)
+                    .add_attribute("amount", format_coins(amount)),
+            ),
+            _ => None,
+        })
+        .collect()
+}
+
+/**
+    Funds added for the next game accepted between host and
+    opponent, with the sponsor, amount and expires.
+*/
+pub fn sponsorship_added(host: &Addr, opponent: &Addr, sponsorship: &Sponsorship) -> Event {
+    Event::new(SPONSORSHIP_ADDED)
+        .add_attribute("host", host)
+        .add_attribute("opponent", opponent)
+        .add_attribute("sponsor", &sponsorship.sponsor)
+        .add_attribute("amount", format_coins(&sponsorship.amount))
+        .add_attribute("expires", sponsorship.expires.to_string())
+}
+
+/**
+    Sponsorship that was not used by any game and
+    goes back to the sponsor, with the amount.
+*/
+pub fn sponsorship_returned(host: &Addr, opponent: &Addr, sponsorship: &Sponsorship) -> Event {
+    Event::new(SPONSORSHIP_RETURNED)
+        .add_attribute("host", host)
+        .add_attribute("opponent", opponent)
+        .add_attribute("sponsor", &sponsorship.sponsor)
+        .add_attribute("amount", format_coins(&sponsorship.amount))
+}
+
+/**
+    Comma separated list of coins, e.g. "2token,5uluna".
+    Attribute values cannot be empty, so no coins is "0".
+*/
+pub fn format_coins(coins: &[Coin]) -> String {
+    if coins.is_empty() {
+        return String::from("0");
+    }
+
+    coins
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+fn game_event(ty: &str, host: &Addr, opponent: &Addr, game: &Game) -> Event {
+    let board_hash: String = board_hash(game)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    Event::new(ty)
+        .add_attribute("game_id", game.id.to_string())
+        .add_attribute("host", host)
+        .add_attribute("opponent", opponent)
+        .add_attribute("status", game.status.to_string())
+        .add_attribute("board_hash", board_hash)
+}
@@ -1,15 +1,44 @@
 pub mod errors;
+pub mod events;
 pub mod responses;
 pub mod state;
 
+use cosmwasm_std::{Binary, Coin};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use self::state::{PlayerSymbol, Status, Coord};
+use self::state::{PlayerSymbol, Status, Coord, Difficulty, Opening, Pause, PayoutMode, SessionScope, TimeControl, Variant};
 
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct InstantiateMsg { }
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /**
+        Defaults to the address instantiating the contract.
+    */
+    pub admin: Option<String>,
+
+    /**
+        Defaults to PERFECT.
+    */
+    pub house_difficulty: Option<Difficulty>,
+
+    /**
+        Defaults to no exposure, which means that
+        nobody can play against the house.
+    */
+    pub house_max_exposure: Option<Vec<Coin>>,
+
+    /**
+        Defaults to one day.
+    */
+    pub challenge_window: Option<u64>,
+
+    /**
+        Defaults to PUSH.
+    */
+    pub payout_mode: Option<PayoutMode>,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -17,7 +46,31 @@ pub enum ExecuteMsg {
     Invite {
         coord: Coord,
         host_symbol: PlayerSymbol,
-        opponent: String
+        opponent: String,
+        /**
+            Stake required from the opponent to accept, e.g. to
+            offer odds. Defaults to the funds sent by the host.
+        */
+        opponent_stake: Option<Vec<Coin>>,
+        /**
+            Defaults to the standard opening.
+        */
+        opening: Option<Opening>,
+        /**
+            Chess clock started when the game is accepted, the
+            game has no time limit without it.
+        */
+        time_control: Option<TimeControl>,
+        /**
+            Defaults to the classic 3x3 board. On a gravity
+            board the coord is the cell where the piece falls.
+        */
+        variant: Option<Variant>,
+        /**
+            A practice game is played for nothing, it fails when
+            the host sends funds or sets an opponent_stake.
+        */
+        practice: Option<bool>
     },
     Reject {
         as_host: bool,
@@ -27,10 +80,169 @@ pub enum ExecuteMsg {
         coord: Coord,
         host: String
     },
+    /**
+        Accepts a game with the PIE opening swapping sides
+        instead of playing the second move.
+    */
+    Swap {
+        host: String
+    },
+    /**
+        Invites the other player of a COMPLETED game to a new
+        game with the symbols and the first move swapped. Coord
+        is only given when the sender moves first. The winner can
+        reinvest the prize as a double or nothing stake.
+    */
+    Rematch {
+        game: QueryKey,
+        coord: Option<Coord>,
+        double_or_nothing: bool
+    },
+    Play {
+        as_host: bool,
+        coord: Coord,
+        opponent: String
+    },
+    /**
+        Plays on a gravity board the lowest
+        empty cell of the column.
+    */
+    DropPiece {
+        as_host: bool,
+        column: u8,
+        opponent: String
+    },
+    /**
+        Moves a piece of the sender to an adjacent
+        empty cell once all the pieces of a MORRIS
+        game are placed.
+    */
+    MovePiece {
+        as_host: bool,
+        from: Coord,
+        to: Coord,
+        opponent: String
+    },
+    FundHouse {},
+    WithdrawHouse {
+        amount: Vec<Coin>
+    },
+    UpdateHouse {
+        difficulty: Option<Difficulty>,
+        max_exposure: Option<Vec<Coin>>
+    },
+    Claim {},
+    /**
+        Withdraws the claims of the address, which
+        receives the funds, on behalf of it.
+    */
+    ClaimFor {
+        address: String
+    },
+    UpdatePause {
+        pause: Pause
+    },
+    /**
+        Recipient defaults to the admin. Only the
+        funds above the liabilities can be swept.
+    */
+    SweepSurplus {
+        recipient: Option<String>
+    },
+    /**
+        Only while the contract is paused, returns to each
+        player of a game in status INVITED or PLAYING its own
+        stake and the game is VOIDED.
+    */
+    EmergencyWithdraw {
+        as_host: bool,
+        opponent: String
+    },
+    /**
+        Adds the sent funds to the prize of the next game
+        accepted between host and opponent until it expires.
+    */
+    Sponsor {
+        host: String,
+        opponent: String,
+        expires: Expiration
+    },
+    /**
+        Returns an expired sponsorship to its sponsor,
+        anyone can execute it.
+    */
+    ReclaimSponsorship {
+        host: String,
+        opponent: String
+    },
+    RegisterPubkey {
+        pubkey: Binary
+    },
+    SettleSignedMoves {
+        host: String,
+        opponent: String,
+        moves: Vec<SignedMove>
+    },
+    Resign {
+        as_host: bool,
+        opponent: String
+    },
+    OfferDraw {
+        as_host: bool,
+        opponent: String
+    },
+    /**
+        Wins the game when the flag of the other
+        player, whose clock is running, has fallen.
+    */
+    CallFlag {
+        as_host: bool,
+        opponent: String
+    },
+    AuthorizeSessionKey {
+        session_key: String,
+        expires: Expiration,
+        scope: SessionScope
+    },
+    RevokeSessionKey {
+        session_key: String
+    },
+    Delegate {
+        player: String,
+        msg: DelegatedMsg
+    }
+}
+
+/**
+    Messages that a session key can submit
+    on behalf of the player that authorised it.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DelegatedMsg {
     Play {
         as_host: bool,
         coord: Coord,
         opponent: String
+    },
+    DropPiece {
+        as_host: bool,
+        column: u8,
+        opponent: String
+    },
+    MovePiece {
+        as_host: bool,
+        from: Coord,
+        to: Coord,
+        opponent: String
+    },
+    Resign {
+        as_host: bool,
+        opponent: String
+    },
+    OfferDraw {
+        as_host: bool,
+        opponent: String
     }
 }
 
@@ -40,6 +252,27 @@ pub enum QueryMsg {
     Games {
         key: Option<QueryKey>,
         status: Option<Status>
+    },
+    Evaluate {
+        game: QueryKey
+    },
+    House {},
+    Claimable {
+        address: String
+    },
+    Pause {},
+    Solvency {},
+    Sponsorship {
+        game: QueryKey
+    },
+    Channel {
+        game: QueryKey
+    },
+    SessionKeys {
+        player: String
+    },
+    Clock {
+        game: QueryKey
     }
 }
 
@@ -48,4 +281,18 @@ pub enum QueryMsg {
 pub struct QueryKey {
     pub host: String,
     pub opponent: String,
-}
\ No newline at end of file
+}
+/**
+    Move exchanged off-chain between the players, where the
+    signature is made by the player that played the move over
+    the hash defined in the channel module. `from` is only set
+    to move a piece to coord once the pieces of a MORRIS game
+    are placed.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SignedMove {
+    pub from: Option<Coord>,
+    pub coord: Coord,
+    pub signature: Binary,
+}
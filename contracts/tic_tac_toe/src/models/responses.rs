@@ -1,13 +1,128 @@
-
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Coin, Timestamp};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::Game;
+use cw_utils::Expiration;
+
+use super::state::{Coord, Difficulty, Pause, PlayerSymbol, SessionScope, Sponsorship, TimeControl};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct GameResponse {
     pub game: Game,
     pub host: Addr,
     pub opponent: Addr
-}
\ No newline at end of file
+}
+
+/**
+    Game-theoretic value of a position from
+    the perspective of the player to move,
+    assuming perfect play from both sides.
+*/
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+pub enum GameValue {
+    WIN,
+    DRAW,
+    LOSS,
+}
+
+impl GameValue {
+    pub fn from_score(score: i8) -> GameValue {
+        match score {
+            1 => GameValue::WIN,
+            0 => GameValue::DRAW,
+            _ => GameValue::LOSS,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EvaluationResponse {
+    pub player_round: PlayerSymbol,
+    pub value: GameValue,
+    pub optimal_moves: Vec<Coord>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HouseResponse {
+    pub difficulty: Difficulty,
+    pub max_exposure: Vec<Coin>,
+    pub bankroll: Vec<Coin>,
+    pub exposure: Vec<Coin>
+}
+
+/**
+    Prizes and refunds credited to the address
+    in PULL mode and not claimed yet.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimableResponse {
+    pub amount: Vec<Coin>
+}
+
+/**
+    Sponsorship waiting for the next game accepted
+    between the players, None when there is none.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SponsorshipResponse {
+    pub sponsorship: Option<Sponsorship>
+}
+
+/**
+    Funds held by the contract compared with what it owes:
+    the stakes of the games in status INVITED or PLAYING and
+    the sponsorships not used yet (escrow), the unclaimed
+    payouts and the house bankroll. The surplus
+    can be swept by the admin, a deficit means that the
+    contract cannot pay all its liabilities.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SolvencyResponse {
+    pub balance: Vec<Coin>,
+    pub escrow: Vec<Coin>,
+    pub claims: Vec<Coin>,
+    pub bankroll: Vec<Coin>,
+    pub liabilities: Vec<Coin>,
+    pub surplus: Vec<Coin>,
+    pub deficit: Vec<Coin>,
+    pub solvent: bool
+}
+
+/**
+    Messages blocked by the circuit breaker of the admin.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseResponse {
+    pub pause: Pause
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ChannelResponse {
+    pub game_id: u64,
+    pub move_index: u32,
+    pub challenge_deadline: Option<Timestamp>
+}
+
+/**
+    Time left to each player as of the current block, in seconds,
+    where `running` is the symbol of the player whose clock runs.
+    The clock starts when the game is accepted and stops when it
+    is finished.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClockResponse {
+    pub game_id: u64,
+    pub time_control: TimeControl,
+    pub host_remaining: u64,
+    pub opponent_remaining: u64,
+    pub running: Option<PlayerSymbol>,
+    pub flag_fallen: bool
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SessionKeyResponse {
+    pub session_key: Addr,
+    pub expires: Expiration,
+    pub scope: SessionScope
+}
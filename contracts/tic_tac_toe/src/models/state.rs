@@ -1,11 +1,20 @@
 use std::fmt;
 
-use cosmwasm_std::{Coin, Uint128};
+use cosmwasm_std::{Addr, Coin, Timestamp};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::errors::ContractError;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Game {
+    /**
+        Unique identifier of the game assigned
+        incrementally when the game is created.
+    */
+    pub id: u64,
+
     /**
         Board is defined as following due the
         smart contract optimization process:
@@ -38,12 +47,74 @@ pub struct Game {
     */
     pub prize: Vec<Coin>,
 
+    /**
+        Amount staked by the host when the game was created.
+    */
+    pub host_stake: Vec<Coin>,
+
+    /**
+        Amount the opponent has to stake to accept the game,
+        which can differ from the stake of the host to offer
+        odds. It is part of the prize once the game is PLAYING.
+    */
+    pub opponent_stake: Vec<Coin>,
+
+    /**
+        Address that sponsored the game, which gets
+        its sponsorship back when nobody wins the prize.
+    */
+    pub sponsor: Option<Addr>,
+
+    /**
+        Amount added by the sponsor to the prize when the
+        game was accepted, see Sponsorship.
+    */
+    pub sponsorship: Vec<Coin>,
+
+    /**
+        Whether the host invited to a practice game, where the
+        players stake nothing and only a sponsorship is paid.
+    */
+    pub practice: bool,
+
+    /**
+        Whether the opponent plays the first move instead of
+        the host, which only happens in a rematch requested
+        by the player that moved first in the previous game.
+    */
+    pub opponent_first: bool,
+
+    /**
+        Opening protocol chosen by the host, which restricts
+        the first move or lets the opponent swap sides.
+    */
+    pub opening: Opening,
+
+    /**
+        Time bank and increment of each player, the game
+        has no clock when the host did not set them.
+    */
+    pub time_control: Option<TimeControl>,
+
+    /**
+        Board of the game, classic 3x3 or Connect Four with gravity.
+    */
+    pub variant: Variant,
+
+    /**
+        Positions reached by moving pieces in a MORRIS game, encoded
+        with the player to move (see Game::position), to draw on the
+        third repetition or after MORRIS_MAX_MOVES piece moves.
+    */
+    pub positions: Vec<u32>,
+
     /**
         Determine the game status, where
         - INVITED: only one game can be in this status at a time per host and opponent pair. This status is achieved by creating a new game and the following possible status are PLAYING or REJECTED.
         - PLAYING: only one game can be in this status at a time per host and opponent pair. To achieve this status must mutate from INVITED.
         - COMPLETED: multiple games can be in this status but they have to mutate from PLAYING.
         - REJECTED: multiple games can be in this status but they have to mutate from INVITE. 
+        - VOIDED: multiple games can be in this status but they have to mutate from INVITED or PLAYING when a player withdraws its stake while the contract is paused.
     */
     pub status: Status,
 
@@ -64,6 +135,15 @@ pub enum PlayerSymbol {
     O
 }
 
+impl PlayerSymbol {
+    pub fn other(self) -> PlayerSymbol {
+        match self {
+            PlayerSymbol::X => PlayerSymbol::O,
+            PlayerSymbol::O => PlayerSymbol::X,
+        }
+    }
+}
+
 impl fmt::Display for PlayerSymbol {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -79,6 +159,7 @@ pub enum Status {
     PLAYING,
     COMPLETED,
     REJECTED,
+    VOIDED,
 }
 
 impl fmt::Display for Status {
@@ -88,6 +169,427 @@ impl fmt::Display for Status {
             Status::PLAYING => write!(f, "PLAYING"),
             Status::COMPLETED => write!(f, "COMPLETED"),
             Status::REJECTED => write!(f, "REJECTED"),
+            Status::VOIDED => write!(f, "VOIDED"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /**
+        Address allowed to fund and withdraw the
+        house bankroll and to update its settings.
+    */
+    pub admin: Addr,
+
+    /**
+        Strategy used by the house to choose its
+        moves when a player invites the contract.
+    */
+    pub house_difficulty: Difficulty,
+
+    /**
+        Maximum amount per denom that the house can have at
+        stake across all its games in PLAYING status. Denoms
+        not listed cannot be used to play against the house.
+    */
+    pub house_max_exposure: Vec<Coin>,
+
+    /**
+        Seconds during which on-chain moves are blocked
+        after a signed state is settled, so the other player
+        can submit a later signed state of the same game.
+    */
+    pub challenge_window: u64,
+
+    /**
+        How the prizes and refunds are paid to the players.
+    */
+    pub payout_mode: PayoutMode,
+
+    /**
+        Circuit breaker set by the admin, players can
+        withdraw their stakes while it is not NONE.
+    */
+    pub pause: Pause,
+}
+
+/**
+    Determine how the players receive their prizes and refunds, where
+    - PUSH: the funds are sent in the same transaction that completes or rejects the game,
+    - PULL: the funds are credited to the claims of the player, who withdraws them with Claim.
+*/
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+pub enum PayoutMode {
+    PUSH,
+    PULL,
+}
+
+impl fmt::Display for PayoutMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PayoutMode::PUSH => write!(f, "PUSH"),
+            PayoutMode::PULL => write!(f, "PULL"),
+        }
+    }
+}
+
+/**
+    Determine which messages are blocked by the circuit breaker, where
+    - NONE: the contract is not paused,
+    - INVITES: Invite and Accept are blocked,
+    - MOVES: Play, DropPiece, MovePiece, Resign, OfferDraw, CallFlag, SettleSignedMoves
+      and Delegate are blocked and the clocks are stopped (see ClockPause),
+    - ALL: every message that changes a game is blocked, including Reject.
+
+    Admin messages, claims and EmergencyWithdraw are never blocked.
+*/
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+pub enum Pause {
+    NONE,
+    INVITES,
+    MOVES,
+    ALL,
+}
+
+impl Pause {
+    pub fn blocks_invites(self) -> bool {
+        matches!(self, Pause::INVITES | Pause::ALL)
+    }
+
+    pub fn blocks_moves(self) -> bool {
+        matches!(self, Pause::MOVES | Pause::ALL)
+    }
+}
+
+impl fmt::Display for Pause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pause::NONE => write!(f, "NONE"),
+            Pause::INVITES => write!(f, "INVITES"),
+            Pause::MOVES => write!(f, "MOVES"),
+            Pause::ALL => write!(f, "ALL"),
+        }
+    }
+}
+
+/**
+    Determine how the house chooses its moves, where
+    - RANDOM: any legal move,
+    - MEDIUM: wins when possible, blocks the opponent when needed and otherwise plays any legal move,
+    - PERFECT: any of the optimal moves found by the solver.
+*/
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+pub enum Difficulty {
+    RANDOM,
+    MEDIUM,
+    PERFECT,
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Difficulty::RANDOM => write!(f, "RANDOM"),
+            Difficulty::MEDIUM => write!(f, "MEDIUM"),
+            Difficulty::PERFECT => write!(f, "PERFECT"),
+        }
+    }
+}
+
+/**
+    Chess clock of a game, in seconds: each player starts with
+    the `base` time bank and gets the `increment` after each move.
+*/
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+pub struct TimeControl {
+    pub base: u64,
+    pub increment: u64,
+}
+
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}+{}", self.base, self.increment)
+    }
+}
+
+/**
+    Time left to each player of a game with a time control when
+    the last move was played at `since`. Only the clock of the
+    player in round runs, its flag falls when it reaches zero.
+    Times are read on the clocks (see ClockPause::clock_time),
+    `since` is the end of the challenge window after a signed
+    state is settled so no clock runs during the window.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Clock {
+    pub host: u64,
+    pub opponent: u64,
+    pub since: Timestamp,
+}
+
+impl Clock {
+    pub fn new(time_control: TimeControl, since: Timestamp) -> Clock {
+        Clock {
+            host: time_control.base,
+            opponent: time_control.base,
+            since,
+        }
+    }
+
+    /**
+        Time left to the host or the opponent at `now`,
+        where `running` tells whether its clock runs.
+    */
+    pub fn remaining(&self, as_host: bool, running: bool, now: Timestamp) -> u64 {
+        let left = if as_host { self.host } else { self.opponent };
+        if running {
+            left.saturating_sub(now.seconds().saturating_sub(self.since.seconds()))
+        } else {
+            left
+        }
+    }
+
+    /**
+        Stops the clock of the player that moved at `now`, adding the
+        increment. Returns false without changes if its flag has fallen.
+    */
+    pub fn punch(&mut self, as_host: bool, now: Timestamp, increment: u64) -> bool {
+        let left = self.remaining(as_host, true, now);
+        if left == 0 {
+            return false;
+        }
+        let left = left.saturating_add(increment);
+        if as_host {
+            self.host = left;
+        } else {
+            self.opponent = left;
+        }
+        self.since = now;
+
+        true
+    }
+}
+
+/**
+    Seconds during which the circuit breaker blocked the moves, the
+    clocks of every game are stopped meanwhile. `since` is the start
+    of the current pause, its seconds are not yet in `total`.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ClockPause {
+    pub total: u64,
+    pub since: Option<Timestamp>,
+}
+
+impl ClockPause {
+    /**
+        Time shown by the clocks at `now`, that is the block
+        time without the seconds the moves were blocked.
+    */
+    pub fn clock_time(&self, now: Timestamp) -> Timestamp {
+        let current = self
+            .since
+            .map(|since| now.seconds().saturating_sub(since.seconds()))
+            .unwrap_or_default();
+        Timestamp::from_seconds(now.seconds().saturating_sub(self.total + current))
+    }
+
+    /**
+        Starts or ends a pause at `now` when the circuit
+        breaker starts or stops blocking the moves.
+    */
+    pub fn update(&mut self, pause: Pause, now: Timestamp) {
+        match (self.since, pause.blocks_moves()) {
+            (None, true) => self.since = Some(now),
+            (Some(since), false) => {
+                self.total += now.seconds().saturating_sub(since.seconds());
+                self.since = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+/**
+    Funds added by a sponsor to the prize of the next game
+    accepted between a host and an opponent, so the players
+    can play for a pot without staking anything. They return
+    to the sponsor when the invite is rejected or, if no game
+    is accepted before `expires`, with ReclaimSponsorship.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Sponsorship {
+    pub sponsor: Addr,
+    pub amount: Vec<Coin>,
+    pub expires: Expiration,
+}
+
+/**
+    Address authorised by a player to act on its behalf
+    until `expires`, limited to the actions of the scope.
+    Funds are always paid to the player, never to the key.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SessionKey {
+    pub expires: Expiration,
+    pub scope: SessionScope,
+}
+
+/**
+    Determine what a session key can do, where
+    - Moves: play moves in any game of the player,
+    - Games: play moves, resign and offer draws only in the games with the given ids.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionScope {
+    Moves {},
+    Games { ids: Vec<u64> },
+}
+
+impl SessionScope {
+    pub fn allows(&self, game_id: u64, only_moves: bool) -> bool {
+        match self {
+            SessionScope::Moves {} => only_moves,
+            SessionScope::Games { ids } => ids.contains(&game_id),
+        }
+    }
+}
+
+pub const GRAVITY_COLUMNS: u8 = 7;
+pub const GRAVITY_ROWS: u8 = 6;
+pub const MORRIS_PIECES: u32 = 3;
+pub const MORRIS_MAX_MOVES: usize = 30;
+
+fn gravity_columns() -> u8 {
+    GRAVITY_COLUMNS
+}
+
+fn gravity_rows() -> u8 {
+    GRAVITY_ROWS
+}
+
+/**
+    Determine the board of a game, where
+    - Classic: the 3x3 board where three in a row wins,
+    - Gravity: a Connect Four board of `columns` by `rows` (7x6 by default, from 4 to 10 each) where the players only choose a column, the piece falls to the lowest empty cell and four in a row wins,
+    - Morris: the 3x3 board of Three Men's Morris (Tapatan, Achi) where each player places only three pieces, then moves one of them per turn to an adjacent empty cell along a line. Three in a row wins.
+*/
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Variant {
+    Classic {},
+    Gravity {
+        #[serde(default = "gravity_columns")]
+        columns: u8,
+        #[serde(default = "gravity_rows")]
+        rows: u8,
+    },
+    Morris {},
+}
+
+impl Default for Variant {
+    fn default() -> Self {
+        Variant::Classic {}
+    }
+}
+
+impl Variant {
+    pub fn check(self) -> Result<Variant, ContractError> {
+        let in_range = |size: u8| (4..=10).contains(&size);
+        match self {
+            Variant::Gravity { columns, rows } if !in_range(columns) || !in_range(rows) => {
+                Err(ContractError::InvalidVariant {
+                    reason: String::from("a gravity board has from 4 to 10 columns and rows"),
+                })
+            }
+            _ => Ok(self),
+        }
+    }
+
+    pub fn board(self) -> Vec<Vec<Option<PlayerSymbol>>> {
+        match self {
+            Variant::Classic {} | Variant::Morris {} => vec![vec![None; 3]; 3],
+            Variant::Gravity { columns, rows } => vec![vec![None; columns as usize]; rows as usize],
+        }
+    }
+
+    /**
+        Number of symbols in a row that wins the game.
+    */
+    pub fn line_length(self) -> usize {
+        match self {
+            Variant::Classic {} | Variant::Morris {} => 3,
+            Variant::Gravity { .. } => 4,
+        }
+    }
+
+    /**
+        Checks that the coord is on the board, only its column
+        on a gravity board since the piece falls to its row.
+    */
+    pub fn check_coord(self, coord: Coord) -> Result<(), ContractError> {
+        match self {
+            Variant::Classic {} | Variant::Morris {} if !coord.is_valid() => {
+                Err(ContractError::InvalidCoord { coord })
+            }
+            Variant::Gravity { columns, .. } if coord.x >= columns => {
+                Err(ContractError::InvalidColumn { column: coord.x, columns })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Variant::Classic {} => write!(f, "CLASSIC"),
+            Variant::Gravity { columns, rows } => write!(f, "GRAVITY columns={} rows={}", columns, rows),
+            Variant::Morris {} => write!(f, "MORRIS"),
+        }
+    }
+}
+
+/**
+    Determine the opening protocol of a game, where
+    - Standard: the first move can be played on any cell,
+    - Pie: the opponent can swap sides instead of playing the second move, taking the first move as its own,
+    - NoCenter: the first move cannot be played on the centre,
+    - Cell: the first move has to be played on the given coord.
+*/
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Opening {
+    Standard {},
+    Pie {},
+    NoCenter {},
+    Cell { coord: Coord },
+}
+
+impl Default for Opening {
+    fn default() -> Self {
+        Opening::Standard {}
+    }
+}
+
+impl Opening {
+    pub fn allows_first_move(self, coord: Coord) -> bool {
+        match self {
+            Opening::Standard {} | Opening::Pie {} => true,
+            Opening::NoCenter {} => coord != Coord { x: 1, y: 1 },
+            Opening::Cell { coord: cell } => coord == cell,
+        }
+    }
+}
+
+impl fmt::Display for Opening {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Opening::Standard {} => write!(f, "STANDARD"),
+            Opening::Pie {} => write!(f, "PIE"),
+            Opening::NoCenter {} => write!(f, "NO_CENTER"),
+            Opening::Cell { coord } => write!(f, "CELL x={} y={}", coord.x, coord.y),
         }
     }
 }
@@ -106,87 +608,434 @@ impl Coord {
 
 impl Game {
 
-    pub fn new(coord: Coord, prize: Vec<Coin>, host_symbol: PlayerSymbol) -> Game {
-        let mut symbol_round = PlayerSymbol::O;
-        let mut board = vec![vec![None; 3]; 3];
-        let row = board.get_mut(coord.y as usize).unwrap();
-        row[coord.x as usize] = Some(host_symbol);
+    /**
+        Creates the game in status INVITED with the first
+        move of the host, the opponent plays next. The prize
+        is the host stake until the opponent accepts.
+    */
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: u64,
+        coord: Coord,
+        host_stake: Vec<Coin>,
+        opponent_stake: Vec<Coin>,
+        host_symbol: PlayerSymbol,
+        opening: Opening,
+        time_control: Option<TimeControl>,
+        variant: Variant,
+    ) -> Result<Game, ContractError> {
+        let mut game = Game {
+            id,
+            board: variant.board(),
+            host_symbol,
+            player_round: Some(host_symbol),
+            prize: host_stake.clone(),
+            host_stake,
+            opponent_stake,
+            sponsor: None,
+            sponsorship: vec![],
+            practice: false,
+            opponent_first: false,
+            opening,
+            time_control,
+            variant,
+            positions: vec![],
+            status: Status::INVITED,
+            winner: None,
+        };
+        game.play(coord)?.finish_round()?;
 
-        if host_symbol == PlayerSymbol::O {
-            symbol_round = PlayerSymbol::X;
-        }
+        Ok(game)
+    }
 
-        Game {
-            board,
-            host_symbol,
-            player_round: Some(symbol_round),
-            prize,
+    /**
+        Creates the rematch of this game in status INVITED hosted
+        by the player that requests it. The players swap their
+        symbols and the player that moved second moves first, so
+        the coord of the first move is only given when the host
+        moves first, otherwise the opponent plays it when accepting.
+        The variant, opening and time control of this game apply to the rematch too,
+        which is a practice game when this one is and nobody stakes anything.
+    */
+    pub fn rematch(
+        &self,
+        id: u64,
+        as_host: bool,
+        coord: Option<Coord>,
+        host_stake: Vec<Coin>,
+        opponent_stake: Vec<Coin>,
+    ) -> Result<Game, ContractError> {
+        let first_symbol = if self.opponent_first { self.host_symbol.other() } else { self.host_symbol };
+        let previous_symbol = if as_host { self.host_symbol } else { self.host_symbol.other() };
+        let practice = self.practice && host_stake.is_empty() && opponent_stake.is_empty();
+        let mut game = Game {
+            id,
+            board: self.variant.board(),
+            host_symbol: previous_symbol.other(),
+            player_round: Some(first_symbol),
+            prize: host_stake.clone(),
+            host_stake,
+            opponent_stake,
+            sponsor: None,
+            sponsorship: vec![],
+            practice,
+            opponent_first: previous_symbol == first_symbol,
+            opening: self.opening,
+            time_control: self.time_control,
+            variant: self.variant,
+            positions: vec![],
             status: Status::INVITED,
             winner: None,
+        };
+
+        match (game.opponent_first, coord) {
+            (false, Some(coord)) => {
+                game.play(coord)?.finish_round()?;
+            }
+            (false, None) => {
+                return Err(ContractError::InvalidRematch {
+                    reason: String::from("the host plays the first move of the rematch"),
+                })
+            }
+            (true, Some(_)) => {
+                return Err(ContractError::InvalidRematch {
+                    reason: String::from("the opponent plays the first move of the rematch"),
+                })
+            }
+            (true, None) => {}
         }
+
+        Ok(game)
     }
 
-    pub fn already_played_on(&self, coord: Coord) -> bool {
-        return self
-            .board
+    /**
+        Whether the cell is taken, on a gravity board
+        whether the column of the coord is full.
+    */
+    pub fn already_played_on(&self, coord: Coord) -> Result<bool, ContractError> {
+        self.variant.check_coord(coord)?;
+        let coord = match self.variant {
+            Variant::Classic {} | Variant::Morris {} => coord,
+            Variant::Gravity { .. } => Coord { x: coord.x, y: 0 },
+        };
+        self.board
             .get(coord.y as usize)
-            .unwrap()
-            .get(coord.x as usize)
-            .unwrap()
-            .is_some();
+            .and_then(|row| row.get(coord.x as usize))
+            .map(|cell| cell.is_some())
+            .ok_or(ContractError::InvalidCoord { coord })
     }
 
     pub fn already_played(&mut self, as_host: bool) -> bool {
         match self.player_round {
             Some(current_player_symbol) => {
                 if as_host { 
-                    return current_player_symbol != self.host_symbol 
+                    current_player_symbol != self.host_symbol 
                 } else {
-                    return current_player_symbol == self.host_symbol
+                    current_player_symbol == self.host_symbol
                 }
 
             }
-            None => return false,
+            None => false,
         }
     }
 
-    pub fn double_prize(&mut self) -> &mut Game {
-        for coin in &mut self.prize {
-            coin.amount = coin.amount.checked_mul(Uint128::new(2)).unwrap();
+    /**
+        Adds the opponent stake to the prize when the game is accepted.
+    */
+    pub fn add_opponent_stake(&mut self) -> Result<&mut Game, ContractError> {
+        let stake = self.opponent_stake.clone();
+        self.add_to_prize(&stake)?;
+
+        Ok(self)
+    }
+
+    /**
+        Pie rule: instead of playing the second move the opponent
+        takes the first move of the host, who plays next with
+        the other symbol.
+    */
+    pub fn swap_sides(&mut self) -> Result<&mut Game, ContractError> {
+        if self.opening != (Opening::Pie {}) || self.opponent_first || self.moves_count() != 1 {
+            return Err(ContractError::SwapNotAllowed {});
         }
+        self.host_symbol = self.host_symbol.other();
+        self.opponent_first = true;
 
-        self
+        Ok(self)
+    }
+
+    /**
+        Adds the funds of the sponsor to the prize when the game is accepted.
+    */
+    pub fn add_sponsorship(&mut self, sponsor: Addr, amount: Vec<Coin>) -> Result<&mut Game, ContractError> {
+        self.add_to_prize(&amount)?;
+        self.sponsor = Some(sponsor);
+        self.sponsorship = amount;
+
+        Ok(self)
+    }
+
+    fn add_to_prize(&mut self, amount: &[Coin]) -> Result<(), ContractError> {
+        for added in amount {
+            match self.prize.iter_mut().find(|coin| coin.denom == added.denom) {
+                Some(coin) => coin.amount = coin.amount.checked_add(added.amount)?,
+                None => self.prize.push(added.clone()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+        Amounts returned to the host and to the opponent of an
+        accepted game that nobody wins, which are their own stakes.
+        The sponsorship is not included, it returns to the sponsor,
+        so nothing is refunded to the players of a practice game.
+    */
+    pub fn refunds(&self) -> (Vec<Coin>, Vec<Coin>) {
+        if self.practice {
+            return (vec![], vec![]);
+        }
+        let without_zero = |stake: &[Coin]| -> Vec<Coin> {
+            stake.iter().filter(|coin| !coin.amount.is_zero()).cloned().collect()
+        };
+        (without_zero(&self.host_stake), without_zero(&self.opponent_stake))
+    }
+
+    /**
+        Amounts escrowed by the host and by the opponent. Only the
+        host has paid while the game is INVITED, once it is accepted
+        each player has paid its own stake.
+    */
+    pub fn stakes(&self) -> (Vec<Coin>, Vec<Coin>) {
+        match self.status {
+            Status::INVITED => {
+                let stake = self.prize.iter()
+                    .filter(|coin| !coin.amount.is_zero())
+                    .cloned()
+                    .collect();
+                (stake, vec![])
+            }
+            Status::PLAYING => self.refunds(),
+            _ => (vec![], vec![]),
+        }
+    }
+
+    /**
+        Lowest empty cell of the column of a gravity board,
+        where a piece dropped in the column falls.
+    */
+    pub fn drop_coord(&self, column: u8) -> Result<Coord, ContractError> {
+        if !matches!(self.variant, Variant::Gravity { .. }) {
+            return Err(ContractError::InvalidVariant {
+                reason: String::from("pieces can only be dropped on a gravity board"),
+            });
+        }
+        self.variant.check_coord(Coord { x: column, y: 0 })?;
+
+        (0..self.board.len())
+            .rev()
+            .find(|y| self.board[*y][column as usize].is_none())
+            .map(|y| Coord { x: column, y: y as u8 })
+            .ok_or(ContractError::ColumnFull { column })
+    }
+
+    /**
+        Checks that a symbol can be played on the coord: an empty
+        cell of the board, on a gravity board the lowest empty
+        cell of a column that is not full. In a MORRIS game new
+        pieces are only placed until each player has three.
+    */
+    pub fn check_move(&self, coord: Coord) -> Result<(), ContractError> {
+        match self.variant {
+            Variant::Morris {} if self.moves_count() >= 2 * MORRIS_PIECES => {
+                return Err(ContractError::PiecesPlaced {});
+            }
+            Variant::Classic {} | Variant::Morris {} => {
+                if self.already_played_on(coord)? {
+                    return Err(ContractError::CoordinateAlreadyPlayed { coord });
+                }
+            }
+            Variant::Gravity { .. } => {
+                let landing = self.drop_coord(coord.x)?;
+                if landing != coord {
+                    return Err(ContractError::PieceMustFall { coord, landing });
+                }
+            }
+        }
+
+        Ok(())
     }
 
-    pub fn get_half_prize(&self) -> Vec<Coin> {
-        self.prize.clone()
-            .iter_mut()
-            .map(|coin| {
-                coin.amount = coin.amount.checked_div(Uint128::new(2)).unwrap();
-                coin.clone()
+    /**
+        Checks that the player in round can move its piece on `from`
+        to the empty cell `to` of a MORRIS game once all the pieces
+        are placed. Cells are adjacent horizontally and vertically,
+        diagonally only along the diagonals through the centre.
+    */
+    pub fn check_move_piece(&self, from: Coord, to: Coord) -> Result<(), ContractError> {
+        let invalid = |reason: &str| {
+            Err(ContractError::InvalidPieceMove {
+                reason: String::from(reason),
             })
-            .collect()
-        
+        };
+        if self.variant != (Variant::Morris {}) {
+            return Err(ContractError::InvalidVariant {
+                reason: String::from("pieces can only be moved in a MORRIS game"),
+            });
+        }
+        self.variant.check_coord(from)?;
+        self.variant.check_coord(to)?;
+        if self.moves_count() < 2 * MORRIS_PIECES {
+            return invalid("pieces are moved once all of them are placed");
+        } else if self.board[from.y as usize][from.x as usize] != self.player_round {
+            return invalid("the player in round has no piece to move there");
+        } else if self.already_played_on(to)? {
+            return Err(ContractError::CoordinateAlreadyPlayed { coord: to });
+        }
+
+        let center = Coord { x: 1, y: 1 };
+        let dx = (from.x as i8 - to.x as i8).abs();
+        let dy = (from.y as i8 - to.y as i8).abs();
+        let adjacent = match dx + dy {
+            1 => true,
+            2 => dx == 1 && (from == center || to == center),
+            _ => false,
+        };
+        if !adjacent {
+            return invalid("pieces only move to an adjacent cell along a line");
+        }
+
+        Ok(())
     }
 
-    pub fn play(&mut self, coord: Coord) -> &mut Game {
-        let row = self.board.get_mut(coord.y as usize).unwrap();
-        row[coord.x as usize] = self.player_round;
+    /**
+        Moves the piece of the player in round and records the
+        position reached for the repetition and move cap draws.
+    */
+    pub fn move_piece(&mut self, from: Coord, to: Coord) -> Result<&mut Game, ContractError> {
+        self.check_move_piece(from, to)?;
+        let symbol = self.board[from.y as usize][from.x as usize].take();
+        self.board[to.y as usize][to.x as usize] = symbol;
+        let position = self.position();
+        self.positions.push(position);
 
-        self
+        Ok(self)
+    }
+
+    /**
+        Board in base 3, one digit per cell row by row (0 empty,
+        1 X, 2 O), after a leading digit of the player in round
+        that is next to play once it finishes its round.
+    */
+    pub fn position(&self) -> u32 {
+        let next = match self.player_round.map(PlayerSymbol::other) {
+            None => 0,
+            Some(PlayerSymbol::X) => 1,
+            Some(PlayerSymbol::O) => 2,
+        };
+        self.board.iter().flatten().fold(next, |position, cell| {
+            position * 3
+                + match cell {
+                    None => 0,
+                    Some(PlayerSymbol::X) => 1,
+                    Some(PlayerSymbol::O) => 2,
+                }
+        })
     }
 
-    pub fn finish_round(&mut self) -> &mut Game {
+    pub fn play(&mut self, coord: Coord) -> Result<&mut Game, ContractError> {
+        let symbol = self.player_round.ok_or_else(|| ContractError::CorruptState {
+            reason: String::from("no player can play in a finished game"),
+        })?;
+        self.check_move(coord)?;
+        if self.moves_count() == 0 && !self.opening.allows_first_move(coord) {
+            return Err(ContractError::ForbiddenOpening { coord, opening: self.opening });
+        }
+        self.board[coord.y as usize][coord.x as usize] = Some(symbol);
+
+        Ok(self)
+    }
+
+    pub fn finish_round(&mut self) -> Result<&mut Game, ContractError> {
         match self.player_round {
-            Some(PlayerSymbol::X) => self.player_round = Some(PlayerSymbol::O),
-            Some(PlayerSymbol::O) => self.player_round = Some(PlayerSymbol::X),
-            None => panic!("Invalid player round"),
+            Some(symbol) => self.player_round = Some(symbol.other()),
+            None => {
+                return Err(ContractError::CorruptState {
+                    reason: String::from("no player round to finish"),
+                })
+            }
         };
 
-        self
+        Ok(self)
     }
     
 
+    pub fn winner_address<'a>(&self, host: &'a Addr, opponent: &'a Addr) -> Option<&'a Addr> {
+        self.winner.map(|winner| {
+            if winner == self.host_symbol {
+                host
+            } else {
+                opponent
+            }
+        })
+    }
+
+    /**
+        Symbols placed on the board plus the pieces moved.
+    */
+    pub fn moves_count(&self) -> u32 {
+        self.board.iter().flatten().filter(|cell| cell.is_some()).count() as u32
+            + self.positions.len() as u32
+    }
+
+    pub fn complete_round(&mut self) -> Result<&mut Game, ContractError> {
+        if self.is_current_player_winner() {
+            self.status = Status::COMPLETED;
+            self.winner = self.player_round;
+            self.player_round = None;
+        } else if self.is_draw() {
+            self.status = Status::COMPLETED;
+            self.player_round = None;
+        } else {
+            self.finish_round()?;
+        }
+
+        Ok(self)
+    }
+
+    pub fn resign(&mut self, symbol: PlayerSymbol) -> &mut Game {
+        self.status = Status::COMPLETED;
+        self.winner = Some(symbol.other());
+        self.player_round = None;
+
+        self
+    }
+
+    pub fn draw(&mut self) -> &mut Game {
+        self.status = Status::COMPLETED;
+        self.winner = None;
+        self.player_round = None;
+
+        self
+    }
+
+    /**
+        A full board, in a MORRIS game (where the board is never
+        full) the third repetition of the last position or
+        MORRIS_MAX_MOVES piece moves.
+    */
+    pub fn is_draw(&self) -> bool {
+        match (self.variant, self.positions.last()) {
+            (Variant::Morris {}, Some(last)) => {
+                self.positions.len() >= MORRIS_MAX_MOVES
+                    || self.positions.iter().filter(|position| *position == last).count() >= 3
+            }
+            (Variant::Morris {}, None) => false,
+            _ => self.is_full_board(),
+        }
+    }
+
     pub fn is_full_board(&self) -> bool {
         for row in &self.board {
             for cell in row {
@@ -198,37 +1047,27 @@ impl Game {
         true
     }
 
+    /**
+        Whether the player in round has a line of the length of
+        the variant starting on any cell, horizontally,
+        vertically or on any of both diagonals.
+    */
     pub fn is_current_player_winner(&self) -> bool {
-        macro_rules! has {
-            ($y:expr, $x:expr) => {
-                self.board[$y][$x] == self.player_round
-            };
-        }
-        
-        // Horizontally
-        for row in 0..3 {
-            if has!(row, 0) && has!(row, 1) && has!(row, 2) {
-                return true;
-            }
-        }
-
-        // Vertically
-        for col in 0..3 {
-            if has!(0, col) && has!(1, col) && has!(2, col) {
-                return true;
-            }
-        }
-
-        // Top-left to bottom-right
-        if has!(0, 0) && has!(1, 1) && has!(2, 2) {
-            return true;
-        }
+        let length = self.variant.line_length() as isize;
+        let has = |y: isize, x: isize| {
+            y >= 0 && x >= 0 && self.board
+                .get(y as usize)
+                .and_then(|row| row.get(x as usize))
+                == Some(&self.player_round)
+        };
+        let directions = [(0, 1), (1, 0), (1, 1), (1, -1)];
 
-        // Top-right to bottom-left
-        if has!(0, 2) && has!(1, 1) && has!(2, 0) {
-            return true;
-        }
-        
-        false
+        self.board.iter().enumerate().any(|(y, row)| {
+            (0..row.len()).any(|x| {
+                directions.iter().any(|(dy, dx)| {
+                    (0..length).all(|i| has(y as isize + dy * i, x as isize + dx * i))
+                })
+            })
+        })
     }
 }
@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use crate::models::{
+    responses::GameValue,
+    state::{Coord, Game, PlayerSymbol, Variant},
+};
+
+/**
+    Every line that wins the game on a 3x3 board
+    expressed as a bitmask where the cell (x, y)
+    is stored in the bit y * 3 + x.
+*/
+const LINES: [u16; 8] = [
+    0b000_000_111,
+    0b000_111_000,
+    0b111_000_000,
+    0b001_001_001,
+    0b010_010_010,
+    0b100_100_100,
+    0b100_010_001,
+    0b001_010_100,
+];
+
+const FULL_BOARD: u16 = 0b111_111_111;
+
+/**
+    Cell permutations of the 8 symmetries of the square
+    (identity, rotations and reflections), used to store
+    equivalent positions only once in the memo.
+*/
+const SYMMETRIES: [[u8; 9]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8],
+    [6, 3, 0, 7, 4, 1, 8, 5, 2],
+    [8, 7, 6, 5, 4, 3, 2, 1, 0],
+    [2, 5, 8, 1, 4, 7, 0, 3, 6],
+    [2, 1, 0, 5, 4, 3, 8, 7, 6],
+    [6, 7, 8, 3, 4, 5, 0, 1, 2],
+    [0, 3, 6, 1, 4, 7, 2, 5, 8],
+    [8, 5, 2, 7, 4, 1, 6, 3, 0],
+];
+
+/**
+    Perfect-play solver for 3x3 games based on negamax.
+
+    Positions are represented from the perspective of the
+    side to move (own cells and opponent cells bitmasks) so
+    the same memo entry is valid for both symbols. Values are
+    1 (win), 0 (draw) or -1 (loss) for the side to move.
+*/
+#[derive(Default)]
+pub struct Solver {
+    memo: BTreeMap<u32, i8>,
+}
+
+impl Solver {
+    pub fn new() -> Solver {
+        Solver::default()
+    }
+
+    /**
+        Returns the game-theoretic value for the player in
+        `player_round` and every move that achieves it, or None
+        when the game has no player to move or is not a CLASSIC game.
+    */
+    pub fn evaluate(&mut self, game: &Game) -> Option<(GameValue, Vec<Coord>)> {
+        let player = game.player_round?;
+        let (own, other) = Solver::masks(game, player)?;
+
+        if Solver::has_line(own) {
+            return Some((GameValue::WIN, vec![]));
+        } else if Solver::has_line(other) {
+            return Some((GameValue::LOSS, vec![]));
+        }
+
+        let mut best = -2;
+        let mut moves = vec![];
+        for cell in Solver::empty_cells(own | other) {
+            let value = -self.negamax(other, own | 1 << cell);
+            if value > best {
+                best = value;
+                moves.clear();
+            }
+            if value == best {
+                moves.push(Coord {
+                    x: cell % 3,
+                    y: cell / 3,
+                });
+            }
+        }
+
+        if moves.is_empty() {
+            return Some((GameValue::DRAW, moves));
+        }
+
+        Some((GameValue::from_score(best), moves))
+    }
+
+    fn negamax(&mut self, own: u16, other: u16) -> i8 {
+        if Solver::has_line(other) {
+            return -1;
+        }
+        if own | other == FULL_BOARD {
+            return 0;
+        }
+
+        let key = Solver::canonical(own, other);
+        if let Some(value) = self.memo.get(&key) {
+            return *value;
+        }
+
+        let mut best = -1;
+        for cell in Solver::empty_cells(own | other) {
+            best = best.max(-self.negamax(other, own | 1 << cell));
+            if best == 1 {
+                break;
+            }
+        }
+
+        self.memo.insert(key, best);
+        best
+    }
+
+    fn masks(game: &Game, player: PlayerSymbol) -> Option<(u16, u16)> {
+        if game.variant != (Variant::Classic {})
+            || game.board.len() != 3
+            || game.board.iter().any(|row| row.len() != 3)
+        {
+            return None;
+        }
+
+        let mut own = 0;
+        let mut other = 0;
+        for (y, row) in game.board.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                match cell {
+                    Some(symbol) if *symbol == player => own |= 1 << (y * 3 + x),
+                    Some(_) => other |= 1 << (y * 3 + x),
+                    None => {}
+                }
+            }
+        }
+
+        Some((own, other))
+    }
+
+    fn has_line(mask: u16) -> bool {
+        LINES.iter().any(|line| mask & line == *line)
+    }
+
+    fn empty_cells(occupied: u16) -> impl Iterator<Item = u8> {
+        (0..9).filter(move |cell| occupied & (1 << cell) == 0)
+    }
+
+    fn canonical(own: u16, other: u16) -> u32 {
+        SYMMETRIES
+            .iter()
+            .map(|symmetry| {
+                let mut key = 0u32;
+                for (cell, target) in symmetry.iter().enumerate() {
+                    if own & (1 << cell) != 0 {
+                        key |= 1 << target;
+                    }
+                    if other & (1 << cell) != 0 {
+                        key |= 1 << (target + 9);
+                    }
+                }
+                key
+            })
+            .min()
+            .unwrap_or_default()
+    }
+}
@@ -5,8 +5,8 @@ use crate::contract::execute::execute;
 use crate::contract::instantiate::instantiate;
 use crate::contract::query::query;
 use crate::models::{
-    errors::ContractError, responses::GameResponse, state::Coord, state::Game, state::PlayerSymbol,
-    state::Status, ExecuteMsg, InstantiateMsg, QueryMsg, QueryKey
+    errors::ContractError, events, responses::GameResponse, state::Coord, state::Game, state::PlayerSymbol,
+    state::Opening, state::Status, state::Variant, ExecuteMsg, InstantiateMsg, QueryMsg, QueryKey
 };
 
 #[test]
@@ -19,7 +19,7 @@ fn accept() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -30,6 +30,11 @@ fn accept() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -59,13 +64,18 @@ fn accept() {
 
     // THEN
     let query_value: Vec<GameResponse> = from_binary(&res.unwrap()).unwrap();
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let game = &query_value[0].game;
     assert_eq!(
         opponent_response,
         Response::new()
             .add_attribute("method", "accept")
             .add_attribute("x", "1")
             .add_attribute("y", "1")
-            .add_attribute("opponent", "host")
+            .add_attribute("host", "host")
+            .add_event(events::game_accepted(&host, &opponent, game, &coins(2, "token")))
+            .add_event(events::move_played(&host, &opponent, game, &opponent, Coord { x: 1, y: 1 }))
     );
     assert_eq!(
         query_value,
@@ -73,6 +83,7 @@ fn accept() {
             host: Addr::unchecked("host"),
             opponent: Addr::unchecked("opponent"),
             game: Game {
+                id: 1,
                 board: vec![
                     vec![None, None, Some(PlayerSymbol::X)],
                     vec![None, Some(PlayerSymbol::O), None],
@@ -81,6 +92,16 @@ fn accept() {
                 player_round: Some(PlayerSymbol::X),
                 host_symbol: PlayerSymbol::X,
                 prize: coins(4, "token"),
+                host_stake: coins(2, "token"),
+                opponent_stake: coins(2, "token"),
+                sponsor: None,
+                sponsorship: vec![],
+                practice: false,
+                opponent_first: false,
+                opening: Opening::Standard {},
+                time_control: None,
+                variant: Variant::Classic {},
+                positions: vec![],
                 status: Status::PLAYING,
                 winner: None
             }
@@ -98,7 +119,7 @@ fn accept_with_incorrect_host() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -109,6 +130,11 @@ fn accept_with_incorrect_host() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -144,7 +170,7 @@ fn accept_inexistent() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
 
@@ -164,8 +190,8 @@ fn accept_inexistent() {
     assert_eq!(
         value,
         ContractError::InvalidGame {
-            host: Addr::unchecked("opponent"),
-            opponent: Addr::unchecked("host")
+            host: Addr::unchecked("host"),
+            opponent: Addr::unchecked("opponent")
         }
     );
 }
@@ -180,7 +206,7 @@ fn accept_with_incorrect_coord() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -191,6 +217,11 @@ fn accept_with_incorrect_coord() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -226,7 +257,7 @@ fn accept_on_already_played_coords() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -237,6 +268,11 @@ fn accept_on_already_played_coords() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -272,7 +308,7 @@ fn accept_with_less_funds() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -283,6 +319,11 @@ fn accept_with_less_funds() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -313,7 +354,7 @@ fn accept_with_more_funds() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -324,6 +365,11 @@ fn accept_with_more_funds() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -354,7 +400,7 @@ fn accept_with_different_funds() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -365,6 +411,11 @@ fn accept_with_different_funds() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
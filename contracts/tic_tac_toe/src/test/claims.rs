@@ -0,0 +1,248 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, from_binary, Addr, BankMsg, Coin, Deps, DepsMut, Response};
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::models::{
+    errors::ContractError,
+    events::PRIZE_PAID,
+    responses::ClaimableResponse,
+    state::{Coord, PayoutMode, PlayerSymbol},
+    ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+
+fn setup(deps: DepsMut, payout_mode: Option<PayoutMode>) {
+    instantiate(
+        deps,
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            payout_mode,
+            ..InstantiateMsg::default()
+        },
+    )
+    .unwrap();
+}
+
+fn claimable(deps: Deps, address: &str) -> Vec<Coin> {
+    let res: ClaimableResponse = from_binary(
+        &query(
+            deps,
+            mock_env(),
+            QueryMsg::Claimable {
+                address: String::from(address),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    res.amount
+}
+
+fn invite(deps: DepsMut) {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("host", &coins(2, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+}
+
+/**
+    Invites, accepts and plays a game
+    that the host wins on the last move.
+*/
+fn play_host_wins(mut deps: DepsMut) -> Response {
+    invite(deps.branch());
+    execute(
+        deps.branch(),
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 0, y: 1 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+
+    let moves = [
+        (true, Coord { x: 1, y: 0 }),
+        (false, Coord { x: 1, y: 1 }),
+        (true, Coord { x: 2, y: 0 }),
+    ];
+    let mut res = Response::new();
+    for (as_host, coord) in moves {
+        let (sender, opponent) = if as_host {
+            ("host", "opponent")
+        } else {
+            ("opponent", "host")
+        };
+        res = execute(
+            deps.branch(),
+            mock_env(),
+            mock_info(sender, &[]),
+            ExecuteMsg::Play {
+                as_host,
+                coord,
+                opponent: String::from(opponent),
+            },
+        )
+        .unwrap();
+    }
+
+    res
+}
+
+#[test]
+fn push_mode_is_the_default() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), None);
+
+    // WHEN
+    let res = play_host_wins(deps.as_mut());
+
+    // THEN
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: String::from("host"),
+            amount: coins(4, "token"),
+        }
+        .into()
+    );
+    assert_eq!(claimable(deps.as_ref(), "host"), vec![]);
+}
+
+#[test]
+fn pull_mode_credits_the_winner() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), Some(PayoutMode::PULL));
+
+    // WHEN
+    let res = play_host_wins(deps.as_mut());
+
+    // THEN
+    assert!(res.messages.is_empty());
+    assert!(res.events.iter().any(|event| event.ty == PRIZE_PAID));
+    assert_eq!(claimable(deps.as_ref(), "host"), coins(4, "token"));
+    assert_eq!(claimable(deps.as_ref(), "opponent"), vec![]);
+}
+
+#[test]
+fn pull_mode_credits_the_refund() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), Some(PayoutMode::PULL));
+    invite(deps.as_mut());
+
+    // WHEN
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &[]),
+        ExecuteMsg::Reject {
+            as_host: false,
+            opponent: String::from("host"),
+        },
+    )
+    .unwrap();
+
+    // THEN
+    assert!(res.messages.is_empty());
+    assert_eq!(claimable(deps.as_ref(), "host"), coins(2, "token"));
+}
+
+#[test]
+fn claims_add_up_and_are_withdrawn_once() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), Some(PayoutMode::PULL));
+    invite(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Reject {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+    play_host_wins(deps.as_mut());
+
+    // WHEN
+    let claim_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    let empty_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Claim {},
+    );
+
+    // THEN
+    assert_eq!(
+        claim_res.messages[0].msg,
+        BankMsg::Send {
+            to_address: String::from("host"),
+            amount: coins(6, "token"),
+        }
+        .into()
+    );
+    assert_eq!(
+        empty_res.unwrap_err(),
+        ContractError::NothingToClaim {
+            address: Addr::unchecked("host")
+        }
+    );
+    assert_eq!(claimable(deps.as_ref(), "host"), vec![]);
+}
+
+#[test]
+fn claim_for_pays_the_address() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), Some(PayoutMode::PULL));
+    play_host_wins(deps.as_mut());
+
+    // WHEN
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer", &[]),
+        ExecuteMsg::ClaimFor {
+            address: String::from("host"),
+        },
+    )
+    .unwrap();
+
+    // THEN
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: String::from("host"),
+            amount: coins(4, "token"),
+        }
+        .into()
+    );
+    assert_eq!(claimable(deps.as_ref(), "host"), vec![]);
+    assert_eq!(claimable(deps.as_ref(), "relayer"), vec![]);
+}
@@ -0,0 +1,309 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, from_binary, Addr, BankMsg, Deps, DepsMut, Env, Response};
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::models::{
+    errors::ContractError,
+    events,
+    responses::ClockResponse,
+    state::{Coord, Pause, PlayerSymbol, Status, TimeControl},
+    ExecuteMsg, InstantiateMsg, QueryKey, QueryMsg,
+};
+use crate::CLOCKS;
+
+use super::helpers::query_game;
+
+const TIME_CONTROL: TimeControl = TimeControl {
+    base: 300,
+    increment: 5,
+};
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn key() -> QueryKey {
+    QueryKey {
+        host: String::from("host"),
+        opponent: String::from("opponent"),
+    }
+}
+
+/**
+    The host plays X on 0,0 and the opponent accepts
+    on 1,1 starting the clocks, the host plays next.
+*/
+fn start_timed_game(mut deps: DepsMut) {
+    instantiate(
+        deps.branch(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.branch(),
+        mock_env(),
+        mock_info("host", &coins(2, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: Some(TIME_CONTROL),
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps,
+        env_at(60),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+}
+
+fn host_plays(deps: DepsMut, seconds: u64, coord: Coord) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        env_at(seconds),
+        mock_info("host", &[]),
+        ExecuteMsg::Play {
+            as_host: true,
+            coord,
+            opponent: String::from("opponent"),
+        },
+    )
+}
+
+fn call_flag(deps: DepsMut, seconds: u64, sender: &str) -> Result<Response, ContractError> {
+    let (as_host, opponent) = if sender == "host" {
+        (true, "opponent")
+    } else {
+        (false, "host")
+    };
+    execute(
+        deps,
+        env_at(seconds),
+        mock_info(sender, &[]),
+        ExecuteMsg::CallFlag {
+            as_host,
+            opponent: String::from(opponent),
+        },
+    )
+}
+
+fn update_pause(deps: DepsMut, seconds: u64, pause: Pause) {
+    execute(
+        deps,
+        env_at(seconds),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdatePause { pause },
+    )
+    .unwrap();
+}
+
+fn query_clock(deps: Deps, seconds: u64) -> ClockResponse {
+    from_binary(&query(deps, env_at(seconds), QueryMsg::Clock { game: key() }).unwrap()).unwrap()
+}
+
+#[test]
+fn clock_runs_for_the_player_in_round() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    start_timed_game(deps.as_mut());
+
+    // WHEN
+    let accepted = query_clock(deps.as_ref(), 160);
+    host_plays(deps.as_mut(), 160, Coord { x: 2, y: 2 }).unwrap();
+    let played = query_clock(deps.as_ref(), 210);
+
+    // THEN
+    assert_eq!(accepted.time_control, TIME_CONTROL);
+    assert_eq!(accepted.host_remaining, 200);
+    assert_eq!(accepted.opponent_remaining, 300);
+    assert_eq!(accepted.running, Some(PlayerSymbol::X));
+    assert_eq!(played.host_remaining, 205);
+    assert_eq!(played.opponent_remaining, 250);
+    assert_eq!(played.running, Some(PlayerSymbol::O));
+    assert!(!played.flag_fallen);
+}
+
+#[test]
+fn late_move_loses_on_time() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    start_timed_game(deps.as_mut());
+
+    // WHEN
+    let res = host_plays(deps.as_mut(), 360, Coord { x: 2, y: 2 }).unwrap();
+    let game = query_game(deps.as_ref());
+    let clock = query_clock(deps.as_ref(), 400);
+
+    // THEN
+    assert_eq!(res.attributes[0].value, "flag_fallen");
+    assert_eq!(res.events[0].ty, events::FLAG_FALLEN);
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: String::from("opponent"),
+            amount: coins(4, "token"),
+        }
+        .into()
+    );
+    assert_eq!(game.game.status, Status::COMPLETED);
+    assert_eq!(game.game.winner, Some(PlayerSymbol::O));
+    assert_eq!(game.game.board[2][2], None);
+    assert_eq!(clock.host_remaining, 0);
+    assert_eq!(clock.running, None);
+    assert!(clock.flag_fallen);
+}
+
+#[test]
+fn call_flag_of_the_player_in_round() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    start_timed_game(deps.as_mut());
+    host_plays(deps.as_mut(), 100, Coord { x: 2, y: 2 }).unwrap();
+
+    // WHEN
+    let own_round_res = call_flag(deps.as_mut(), 500, "opponent");
+    let early_res = call_flag(deps.as_mut(), 350, "host");
+    let res = call_flag(deps.as_mut(), 400, "host");
+    let game = query_game(deps.as_ref());
+
+    // THEN
+    assert_eq!(
+        own_round_res.unwrap_err(),
+        ContractError::FlagNotFallen { remaining: 265 }
+    );
+    assert_eq!(
+        early_res.unwrap_err(),
+        ContractError::FlagNotFallen { remaining: 50 }
+    );
+    assert!(res.is_ok());
+    assert_eq!(game.game.status, Status::COMPLETED);
+    assert_eq!(game.game.winner, Some(PlayerSymbol::X));
+}
+
+#[test]
+fn invalid_time_controls() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    let invite = |deps: DepsMut, time_control: Option<TimeControl>| {
+        execute(
+            deps,
+            mock_env(),
+            mock_info("host", &coins(2, "token")),
+            ExecuteMsg::Invite {
+                coord: Coord { x: 0, y: 0 },
+                host_symbol: PlayerSymbol::X,
+                opponent: String::from("opponent"),
+                opponent_stake: None,
+                opening: None,
+                time_control,
+                variant: None,
+                practice: None,
+            },
+        )
+    };
+
+    // WHEN
+    let zero_res = invite(
+        deps.as_mut(),
+        Some(TimeControl {
+            base: 0,
+            increment: 10,
+        }),
+    );
+    invite(deps.as_mut(), None).unwrap();
+    let clock_res = query(deps.as_ref(), mock_env(), QueryMsg::Clock { game: key() });
+    let call_res = call_flag(deps.as_mut(), 0, "host");
+
+    // THEN
+    assert_eq!(zero_res.unwrap_err(), ContractError::InvalidTimeControl {});
+    assert!(clock_res.is_err());
+    assert_eq!(
+        call_res.unwrap_err(),
+        ContractError::InvalidGame {
+            host: Addr::unchecked("host"),
+            opponent: Addr::unchecked("opponent"),
+        }
+    );
+}
+
+#[test]
+fn emergency_withdraw_removes_the_clock() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    start_timed_game(deps.as_mut());
+    update_pause(deps.as_mut(), 100, Pause::MOVES);
+
+    // WHEN
+    execute(
+        deps.as_mut(),
+        env_at(100),
+        mock_info("host", &[]),
+        ExecuteMsg::EmergencyWithdraw {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+    let voided = query_clock(deps.as_ref(), 1000);
+
+    // THEN
+    assert!(!CLOCKS.has(&deps.storage, voided.game_id));
+    assert_eq!(voided.host_remaining, TIME_CONTROL.base);
+    assert_eq!(voided.opponent_remaining, TIME_CONTROL.base);
+    assert_eq!(voided.running, None);
+    assert!(!voided.flag_fallen);
+}
+
+#[test]
+fn clocks_stop_while_the_moves_are_paused() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    start_timed_game(deps.as_mut());
+    update_pause(deps.as_mut(), 100, Pause::MOVES);
+
+    // WHEN
+    let paused = query_clock(deps.as_ref(), 500);
+    let paused_flag_res = call_flag(deps.as_mut(), 500, "opponent");
+    update_pause(deps.as_mut(), 1000, Pause::NONE);
+    let flag_res = call_flag(deps.as_mut(), 1200, "opponent");
+    host_plays(deps.as_mut(), 1250, Coord { x: 2, y: 2 }).unwrap();
+    let played = query_clock(deps.as_ref(), 1250);
+
+    // THEN
+    assert_eq!(paused.host_remaining, 260);
+    assert_eq!(
+        paused_flag_res.unwrap_err(),
+        ContractError::Paused {
+            pause: Pause::MOVES
+        }
+    );
+    assert_eq!(
+        flag_res.unwrap_err(),
+        ContractError::FlagNotFallen { remaining: 60 }
+    );
+    assert_eq!(played.host_remaining, 15);
+    assert_eq!(played.opponent_remaining, 300);
+}
@@ -0,0 +1,378 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, to_vec, Addr, OverflowError, OverflowOperation, StdError, Timestamp};
+use cw_utils::Expiration;
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::models::{
+    errors::{ContractError, ErrorCode},
+    state::{Coord, Opening, Pause, PlayerSymbol},
+    ExecuteMsg, InstantiateMsg,
+};
+
+fn addr(address: &str) -> Addr {
+    Addr::unchecked(address)
+}
+
+/**
+    One value of every ContractError variant
+    with its code and exact rendered message.
+*/
+fn every_error() -> Vec<(ContractError, ErrorCode, &'static str)> {
+    vec![
+        (
+            ContractError::Std(StdError::generic_err("boom")),
+            ErrorCode::Std,
+            "[std] Generic error: boom",
+        ),
+        (
+            ContractError::Unauthorized {},
+            ErrorCode::Unauthorized,
+            "[unauthorized] Unauthorized",
+        ),
+        (
+            ContractError::CannotStartGame {},
+            ErrorCode::CannotStartGame,
+            "[cannot_start_game] Game against yourself cannot be started",
+        ),
+        (
+            ContractError::GameAlreadyInProgress {
+                host: addr("host"),
+                opponent: addr("opponent"),
+            },
+            ErrorCode::GameAlreadyInProgress,
+            "[game_already_in_progress] Game between host and opponent already exists. Complete the previous game to start a new one",
+        ),
+        (
+            ContractError::GameNotFound {
+                host: addr("host"),
+                opponent: addr("opponent"),
+            },
+            ErrorCode::GameNotFound,
+            "[game_not_found] Game between host and opponent not found. You cannot reject it",
+        ),
+        (
+            ContractError::InvalidGame {
+                host: addr("host"),
+                opponent: addr("opponent"),
+            },
+            ErrorCode::InvalidGame,
+            "[invalid_game] Game between host and opponent is invalid. Try starting another game.",
+        ),
+        (
+            ContractError::CoordinateAlreadyPlayed {
+                coord: Coord { x: 1, y: 2 },
+            },
+            ErrorCode::CoordinateAlreadyPlayed,
+            "[coordinate_already_played] x=1 and y=2 already contain symbol. Try using another coordinate.",
+        ),
+        (
+            ContractError::TurnAlreadyPlayed {
+                second_player: String::from("opponent"),
+            },
+            ErrorCode::TurnAlreadyPlayed,
+            "[turn_already_played] You already played this turn. Wait for 'opponent' to play its turn.",
+        ),
+        (
+            ContractError::InvalidCoord {
+                coord: Coord { x: 3, y: 0 },
+            },
+            ErrorCode::InvalidCoord,
+            "[invalid_coord] Invalid coordinate x=3 y=0. Coordinates must be between 0 and 2",
+        ),
+        (
+            ContractError::InvalidReceivedFunds {},
+            ErrorCode::InvalidReceivedFunds,
+            "[invalid_received_funds] The funds you send must be equal to your stake in the game",
+        ),
+        (
+            ContractError::PracticeGameStake {},
+            ErrorCode::PracticeGameStake,
+            "[practice_game_stake] A practice game is played without stakes",
+        ),
+        (
+            ContractError::InsufficientBankroll {},
+            ErrorCode::InsufficientBankroll,
+            "[insufficient_bankroll] The house bankroll cannot cover the requested amount",
+        ),
+        (
+            ContractError::EmptyWithdrawal {},
+            ErrorCode::EmptyWithdrawal,
+            "[empty_withdrawal] The amount to withdraw from the bankroll is empty",
+        ),
+        (
+            ContractError::NoSurplus {},
+            ErrorCode::NoSurplus,
+            "[no_surplus] The contract holds no funds above its liabilities",
+        ),
+        (
+            ContractError::NothingToClaim {
+                address: addr("host"),
+            },
+            ErrorCode::NothingToClaim,
+            "[nothing_to_claim] host has nothing to claim",
+        ),
+        (
+            ContractError::Paused { pause: Pause::MOVES },
+            ErrorCode::Paused,
+            "[paused] The contract is paused (MOVES) and this message is blocked",
+        ),
+        (
+            ContractError::NotPaused {},
+            ErrorCode::NotPaused,
+            "[not_paused] Stakes can only be withdrawn while the contract is paused",
+        ),
+        (
+            ContractError::HouseExposureExceeded {},
+            ErrorCode::HouseExposureExceeded,
+            "[house_exposure_exceeded] The game exceeds the maximum exposure of the house",
+        ),
+        (
+            ContractError::HouseUnevenStakes {},
+            ErrorCode::HouseUnevenStakes,
+            "[house_uneven_stakes] The house only plays games with equal stakes",
+        ),
+        (
+            ContractError::HouseCannotPlay {},
+            ErrorCode::HouseCannotPlay,
+            "[house_cannot_play] The house cannot find a move to play",
+        ),
+        (
+            ContractError::PubkeyNotRegistered {
+                address: addr("host"),
+            },
+            ErrorCode::PubkeyNotRegistered,
+            "[pubkey_not_registered] host has not registered a public key to sign moves",
+        ),
+        (
+            ContractError::InvalidSignature { move_index: 4 },
+            ErrorCode::InvalidSignature,
+            "[invalid_signature] Invalid signature for move 4",
+        ),
+        (
+            ContractError::EmptySignedMoves {},
+            ErrorCode::EmptySignedMoves,
+            "[empty_signed_moves] At least one signed move must be submitted",
+        ),
+        (
+            ContractError::ChallengeWindowOpen {
+                deadline: Timestamp::from_seconds(60),
+            },
+            ErrorCode::ChallengeWindowOpen,
+            "[challenge_window_open] A signed state can be challenged until 60.000000000. Moves cannot be played before.",
+        ),
+        (
+            ContractError::SessionKeyUnauthorized {
+                session_key: addr("session"),
+                player: addr("host"),
+            },
+            ErrorCode::SessionKeyUnauthorized,
+            "[session_key_unauthorized] session is not authorized to execute this action on behalf of host",
+        ),
+        (
+            ContractError::InvalidSponsorship {
+                reason: String::from("no funds were sent"),
+            },
+            ErrorCode::InvalidSponsorship,
+            "[invalid_sponsorship] Invalid sponsorship: no funds were sent",
+        ),
+        (
+            ContractError::SponsorshipExists {
+                host: addr("host"),
+                opponent: addr("opponent"),
+            },
+            ErrorCode::SponsorshipExists,
+            "[sponsorship_exists] The next game between host and opponent is already sponsored",
+        ),
+        (
+            ContractError::SponsorshipNotFound {
+                host: addr("host"),
+                opponent: addr("opponent"),
+            },
+            ErrorCode::SponsorshipNotFound,
+            "[sponsorship_not_found] There is no sponsorship for the games between host and opponent",
+        ),
+        (
+            ContractError::SponsorshipNotExpired {
+                expires: Expiration::AtHeight(12355),
+            },
+            ErrorCode::SponsorshipNotExpired,
+            "[sponsorship_not_expired] The sponsorship can only be reclaimed once it expires (expiration height: 12355)",
+        ),
+        (
+            ContractError::InvalidRematch {
+                reason: String::from("the house does not play rematches"),
+            },
+            ErrorCode::InvalidRematch,
+            "[invalid_rematch] Invalid rematch: the house does not play rematches",
+        ),
+        (
+            ContractError::ForbiddenOpening {
+                coord: Coord { x: 1, y: 1 },
+                opening: Opening::NoCenter {},
+            },
+            ErrorCode::ForbiddenOpening,
+            "[forbidden_opening] The first move x=1 y=1 is not allowed by the NO_CENTER opening",
+        ),
+        (
+            ContractError::SwapNotAllowed {},
+            ErrorCode::SwapNotAllowed,
+            "[swap_not_allowed] Sides can only be swapped by the opponent of a game with the PIE opening instead of playing the second move",
+        ),
+        (
+            ContractError::InvalidTimeControl {},
+            ErrorCode::InvalidTimeControl,
+            "[invalid_time_control] The time bank of a time control must be greater than zero",
+        ),
+        (
+            ContractError::FlagNotFallen { remaining: 42 },
+            ErrorCode::FlagNotFallen,
+            "[flag_not_fallen] The player in round has 42 seconds left. Its flag has not fallen",
+        ),
+        (
+            ContractError::InvalidVariant {
+                reason: String::from("the house only plays classic games"),
+            },
+            ErrorCode::InvalidVariant,
+            "[invalid_variant] Invalid variant: the house only plays classic games",
+        ),
+        (
+            ContractError::InvalidColumn { column: 7, columns: 7 },
+            ErrorCode::InvalidColumn,
+            "[invalid_column] The column 7 is not on the board of 7 columns",
+        ),
+        (
+            ContractError::ColumnFull { column: 3 },
+            ErrorCode::ColumnFull,
+            "[column_full] The column 3 is full",
+        ),
+        (
+            ContractError::PieceMustFall {
+                coord: Coord { x: 3, y: 0 },
+                landing: Coord { x: 3, y: 5 },
+            },
+            ErrorCode::PieceMustFall,
+            "[piece_must_fall] A piece played on x=3 y=0 falls to x=3 y=5",
+        ),
+        (
+            ContractError::PiecesPlaced {},
+            ErrorCode::PiecesPlaced,
+            "[pieces_placed] Both players have placed their three pieces, move one of them with MovePiece",
+        ),
+        (
+            ContractError::InvalidPieceMove {
+                reason: String::from("pieces only move to an adjacent cell along a line"),
+            },
+            ErrorCode::InvalidPieceMove,
+            "[invalid_piece_move] Invalid piece move: pieces only move to an adjacent cell along a line",
+        ),
+        (
+            ContractError::Overflow(OverflowError::new(OverflowOperation::Mul, 3, 2)),
+            ErrorCode::Overflow,
+            "[overflow] Amount overflow: Cannot Mul with 3 and 2",
+        ),
+        (
+            ContractError::CorruptState {
+                reason: String::from("no player round to finish"),
+            },
+            ErrorCode::CorruptState,
+            "[corrupt_state] Corrupt game state: no player round to finish",
+        ),
+        (
+            ContractError::Storage {
+                host: addr("host"),
+                opponent: addr("opponent"),
+                error: StdError::generic_err("boom"),
+            },
+            ErrorCode::Storage,
+            "[storage] Cannot read the game between host and opponent: Generic error: boom",
+        ),
+    ]
+}
+
+#[test]
+fn render_every_error() {
+    for (error, code, message) in every_error() {
+        // WHEN
+        let rendered = error.to_string();
+
+        // THEN
+        assert_eq!(rendered, message);
+        assert_eq!(error.code(), code);
+        assert!(rendered.starts_with(&format!("[{}] ", code)));
+        assert_eq!(to_vec(&code).unwrap(), format!("\"{}\"", code).into_bytes());
+    }
+}
+
+#[test]
+fn render_errors_returned_by_the_contract() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &coins(2, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 1, y: 1 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let played_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    );
+    let invalid_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &[]),
+        ExecuteMsg::Play {
+            as_host: false,
+            coord: Coord { x: 0, y: 0 },
+            opponent: String::from("host"),
+        },
+    );
+    let not_found_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("stranger", &[]),
+        ExecuteMsg::Reject {
+            as_host: false,
+            opponent: String::from("host"),
+        },
+    );
+
+    // THEN
+    assert_eq!(
+        played_res.unwrap_err().to_string(),
+        "[coordinate_already_played] x=1 and y=1 already contain symbol. Try using another coordinate."
+    );
+    assert_eq!(
+        invalid_res.unwrap_err().to_string(),
+        "[invalid_game] Game between host and opponent is invalid. Try starting another game."
+    );
+    assert_eq!(
+        not_found_res.unwrap_err().to_string(),
+        "[game_not_found] Game between host and stranger not found. You cannot reject it"
+    );
+}
@@ -0,0 +1,250 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, DepsMut, Storage};
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::models::{
+    errors::ContractError,
+    state::{Coord, Game, Opening, PlayerSymbol, Status, Variant},
+    ExecuteMsg, InstantiateMsg, QueryKey, QueryMsg,
+};
+use crate::GAMES;
+
+fn setup(deps: DepsMut) {
+    instantiate(
+        deps,
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+}
+
+fn play(deps: DepsMut, coord: Coord) -> Result<(), ContractError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Play {
+            as_host: true,
+            coord,
+            opponent: String::from("opponent"),
+        },
+    )
+    .map(|_| ())
+}
+
+fn query_game() -> QueryMsg {
+    QueryMsg::Games {
+        key: Some(QueryKey {
+            host: String::from("host"),
+            opponent: String::from("opponent"),
+        }),
+        status: None,
+    }
+}
+
+/**
+    Stores a game between host and opponent
+    bypassing the checks of the contract.
+*/
+fn save_game(storage: &mut dyn Storage, game: &Game) {
+    GAMES
+        .save(
+            storage,
+            (&Addr::unchecked("host"), &Addr::unchecked("opponent")),
+            game,
+        )
+        .unwrap();
+}
+
+fn playing_game() -> Game {
+    let x = Some(PlayerSymbol::X);
+    let o = Some(PlayerSymbol::O);
+
+    Game {
+        id: 1,
+        board: vec![vec![x, o, x], vec![x, o, o], vec![o, x, None]],
+        host_symbol: PlayerSymbol::X,
+        player_round: x,
+        prize: coins(4, "token"),
+        host_stake: coins(2, "token"),
+        opponent_stake: coins(2, "token"),
+        sponsor: None,
+        sponsorship: vec![],
+        practice: false,
+        opponent_first: false,
+        opening: Opening::Standard {},
+        time_control: None,
+        variant: Variant::Classic {},
+        positions: vec![],
+        status: Status::PLAYING,
+        winner: None,
+    }
+}
+
+#[test]
+fn accept_overflows_the_prize() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &coins(u128::MAX, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &coins(u128::MAX, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    );
+
+    // THEN
+    assert!(matches!(res, Err(ContractError::Overflow(_))));
+}
+
+#[test]
+fn fund_house_overflows_the_bankroll() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &coins(u128::MAX, "token")),
+        ExecuteMsg::FundHouse {},
+    )
+    .unwrap();
+
+    // WHEN
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &coins(1, "token")),
+        ExecuteMsg::FundHouse {},
+    );
+
+    // THEN
+    assert!(matches!(res, Err(ContractError::Overflow(_))));
+}
+
+#[test]
+fn corrupt_game_is_a_storage_error() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    let key = GAMES.key((&Addr::unchecked("host"), &Addr::unchecked("opponent")));
+    deps.storage.set(&key, b"not a game");
+
+    // WHEN
+    let play_res = play(deps.as_mut(), Coord { x: 0, y: 0 });
+    let query_res = query(deps.as_ref(), mock_env(), query_game());
+    let query_all_res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Games {
+            key: None,
+            status: None,
+        },
+    );
+
+    // THEN
+    match play_res.unwrap_err() {
+        ContractError::Storage { host, opponent, .. } => {
+            assert_eq!(host, Addr::unchecked("host"));
+            assert_eq!(opponent, Addr::unchecked("opponent"));
+        }
+        error => panic!("unexpected error {:?}", error),
+    }
+    assert!(query_res.is_err());
+    assert!(query_all_res.is_err());
+}
+
+#[test]
+fn playing_game_without_player_round_is_corrupt() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    let mut game = playing_game();
+    game.player_round = None;
+    save_game(&mut deps.storage, &game);
+
+    // WHEN
+    let res = play(deps.as_mut(), Coord { x: 2, y: 2 });
+
+    // THEN
+    assert!(matches!(res, Err(ContractError::CorruptState { .. })));
+}
+
+#[test]
+fn game_methods_return_errors() {
+    // GIVEN
+    let mut game = Game::new(
+        1,
+        Coord { x: 1, y: 1 },
+        coins(3, "token"),
+        coins(3, "token"),
+        PlayerSymbol::X,
+        Opening::Standard {},
+        None,
+        Variant::Classic {},
+    )
+    .unwrap();
+    let outside = Coord { x: 3, y: 0 };
+
+    // WHEN
+    let new_res = Game::new(
+        1,
+        outside,
+        vec![],
+        vec![],
+        PlayerSymbol::X,
+        Opening::Standard {},
+        None,
+        Variant::Classic {},
+    );
+    let already_played_res = game.already_played_on(outside);
+    let occupied_res = game.clone().play(Coord { x: 1, y: 1 }).map(|_| ());
+    game.resign(PlayerSymbol::O);
+    let finish_res = game.clone().finish_round().map(|_| ());
+    let play_res = game.play(Coord { x: 0, y: 0 }).map(|_| ());
+
+    // THEN
+    assert_eq!(
+        new_res.unwrap_err(),
+        ContractError::InvalidCoord { coord: outside }
+    );
+    assert_eq!(
+        already_played_res.unwrap_err(),
+        ContractError::InvalidCoord { coord: outside }
+    );
+    assert_eq!(
+        occupied_res.unwrap_err(),
+        ContractError::CoordinateAlreadyPlayed {
+            coord: Coord { x: 1, y: 1 }
+        }
+    );
+    assert!(matches!(
+        finish_res,
+        Err(ContractError::CorruptState { .. })
+    ));
+    assert!(matches!(play_res, Err(ContractError::CorruptState { .. })));
+}
@@ -0,0 +1,285 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, from_binary, StdError};
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::models::{
+    responses::{EvaluationResponse, GameValue},
+    state::Coord,
+    state::PlayerSymbol,
+    ExecuteMsg, InstantiateMsg, QueryKey, QueryMsg,
+};
+
+#[test]
+fn evaluate_winning_position() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let host_info = mock_info("host", &coins(2, "token"));
+    let opponent_info = mock_info("opponent", &coins(2, "token"));
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        opponent_info.clone(),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 0, y: 1 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::Play {
+            as_host: true,
+            coord: Coord { x: 1, y: 0 },
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        opponent_info.clone(),
+        ExecuteMsg::Play {
+            as_host: false,
+            coord: Coord { x: 1, y: 1 },
+            opponent: String::from("host"),
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Evaluate {
+            game: QueryKey {
+                host: String::from("host"),
+                opponent: String::from("opponent"),
+            },
+        },
+    );
+
+    // THEN
+    let value: EvaluationResponse = from_binary(&res.unwrap()).unwrap();
+    assert_eq!(
+        value,
+        EvaluationResponse {
+            player_round: PlayerSymbol::X,
+            value: GameValue::WIN,
+            optimal_moves: vec![Coord { x: 2, y: 0 }],
+        }
+    );
+}
+
+#[test]
+fn evaluate_drawn_position() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let host_info = mock_info("host", &coins(2, "token"));
+    let opponent_info = mock_info("opponent", &coins(2, "token"));
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 1, y: 1 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        opponent_info.clone(),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 0, y: 0 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Evaluate {
+            game: QueryKey {
+                host: String::from("host"),
+                opponent: String::from("opponent"),
+            },
+        },
+    );
+
+    // THEN
+    let value: EvaluationResponse = from_binary(&res.unwrap()).unwrap();
+    assert_eq!(
+        value,
+        EvaluationResponse {
+            player_round: PlayerSymbol::X,
+            value: GameValue::DRAW,
+            optimal_moves: vec![
+                Coord { x: 1, y: 0 },
+                Coord { x: 2, y: 0 },
+                Coord { x: 0, y: 1 },
+                Coord { x: 2, y: 1 },
+                Coord { x: 0, y: 2 },
+                Coord { x: 1, y: 2 },
+                Coord { x: 2, y: 2 },
+            ],
+        }
+    );
+}
+
+#[test]
+fn evaluate_losing_position() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let host_info = mock_info("host", &coins(2, "token"));
+    let opponent_info = mock_info("opponent", &coins(2, "token"));
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        opponent_info.clone(),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 0 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::Play {
+            as_host: true,
+            coord: Coord { x: 1, y: 1 },
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Evaluate {
+            game: QueryKey {
+                host: String::from("host"),
+                opponent: String::from("opponent"),
+            },
+        },
+    );
+
+    // THEN
+    let value: EvaluationResponse = from_binary(&res.unwrap()).unwrap();
+    assert_eq!(value.player_round, PlayerSymbol::O);
+    assert_eq!(value.value, GameValue::LOSS);
+    assert_eq!(value.optimal_moves.len(), 6);
+}
+
+#[test]
+fn evaluate_invited_game() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let host_info = mock_info("host", &coins(2, "token"));
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Evaluate {
+            game: QueryKey {
+                host: String::from("host"),
+                opponent: String::from("opponent"),
+            },
+        },
+    );
+
+    // THEN
+    assert_eq!(res.unwrap_err(), StdError::not_found("PLAYING game"));
+}
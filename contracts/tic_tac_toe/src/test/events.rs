@@ -0,0 +1,176 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, DepsMut, Event};
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::models::{
+    events::{GAME_ACCEPTED, GAME_COMPLETED, GAME_CREATED, MOVE_PLAYED, PRIZE_PAID},
+    state::{Coord, PlayerSymbol},
+    ExecuteMsg, InstantiateMsg,
+};
+
+const INVITED_BOARD_HASH: &str = "f4a0d55fa069ac77aa9399222a1bb04d34137853bfe3d6cf0d80c1aec627ed86";
+const ACCEPTED_BOARD_HASH: &str =
+    "d9427a1f48cfba317f2d101a6bfb22e457dc3851e38872e8336263b03a8dfdc4";
+
+fn invite(deps: DepsMut) -> Vec<Event> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("host", &coins(2, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 2, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap()
+    .events
+}
+
+#[test]
+fn invite_and_accept_events() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+
+    // WHEN
+    let invite_events = invite(deps.as_mut());
+    let accept_events = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap()
+    .events;
+
+    // THEN
+    assert_eq!(
+        invite_events,
+        vec![Event::new(GAME_CREATED)
+            .add_attribute("game_id", "1")
+            .add_attribute("host", "host")
+            .add_attribute("opponent", "opponent")
+            .add_attribute("status", "INVITED")
+            .add_attribute("board_hash", INVITED_BOARD_HASH)
+            .add_attribute("host_symbol", "X")
+            .add_attribute("x", "2")
+            .add_attribute("y", "0")
+            .add_attribute("amount", "2token")
+            .add_attribute("opponent_amount", "2token")
+            .add_attribute("opening", "STANDARD")
+            .add_attribute("variant", "CLASSIC")]
+    );
+    assert_eq!(
+        accept_events,
+        vec![
+            Event::new(GAME_ACCEPTED)
+                .add_attribute("game_id", "1")
+                .add_attribute("host", "host")
+                .add_attribute("opponent", "opponent")
+                .add_attribute("status", "PLAYING")
+                .add_attribute("board_hash", ACCEPTED_BOARD_HASH)
+                .add_attribute("amount", "2token")
+                .add_attribute("prize", "4token"),
+            Event::new(MOVE_PLAYED)
+                .add_attribute("game_id", "1")
+                .add_attribute("host", "host")
+                .add_attribute("opponent", "opponent")
+                .add_attribute("status", "PLAYING")
+                .add_attribute("board_hash", ACCEPTED_BOARD_HASH)
+                .add_attribute("player", "opponent")
+                .add_attribute("symbol", "O")
+                .add_attribute("x", "1")
+                .add_attribute("y", "1")
+                .add_attribute("move_index", "2"),
+        ]
+    );
+}
+
+#[test]
+fn draw_events_have_no_winner() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    invite(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::OfferDraw {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let draw_events = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &[]),
+        ExecuteMsg::OfferDraw {
+            as_host: false,
+            opponent: String::from("host"),
+        },
+    )
+    .unwrap()
+    .events;
+
+    // THEN
+    assert_eq!(
+        draw_events,
+        vec![
+            Event::new(GAME_COMPLETED)
+                .add_attribute("game_id", "1")
+                .add_attribute("host", "host")
+                .add_attribute("opponent", "opponent")
+                .add_attribute("status", "COMPLETED")
+                .add_attribute("board_hash", ACCEPTED_BOARD_HASH)
+                .add_attribute("prize", "4token"),
+            Event::new(PRIZE_PAID)
+                .add_attribute("game_id", "1")
+                .add_attribute("host", "host")
+                .add_attribute("opponent", "opponent")
+                .add_attribute("recipient", "host")
+                .add_attribute("amount", "2token"),
+            Event::new(PRIZE_PAID)
+                .add_attribute("game_id", "1")
+                .add_attribute("host", "host")
+                .add_attribute("opponent", "opponent")
+                .add_attribute("recipient", "opponent")
+                .add_attribute("amount", "2token"),
+        ]
+    );
+}
@@ -0,0 +1,194 @@
+use cosmwasm_std::{coins, Addr, BankMsg, Coin, Uint128};
+
+use crate::contract::execute::prize_messages;
+use crate::models::state::{Coord, Game, Opening, PlayerSymbol, Status, Variant};
+
+const LINES: [[(usize, usize); 3]; 8] = [
+    [(0, 0), (0, 1), (0, 2)],
+    [(1, 0), (1, 1), (1, 2)],
+    [(2, 0), (2, 1), (2, 2)],
+    [(0, 0), (1, 0), (2, 0)],
+    [(0, 1), (1, 1), (2, 1)],
+    [(0, 2), (1, 2), (2, 2)],
+    [(0, 0), (1, 1), (2, 2)],
+    [(0, 2), (1, 1), (2, 0)],
+];
+
+/**
+    Completed games counted by how they ended.
+*/
+#[derive(Default, Debug, PartialEq)]
+struct Outcomes {
+    x_wins: u32,
+    o_wins: u32,
+    ties: u32,
+}
+
+fn coords() -> Vec<Coord> {
+    (0..9)
+        .map(|cell| Coord {
+            x: cell % 3,
+            y: cell / 3,
+        })
+        .collect()
+}
+
+/**
+    Reference implementation of the rules,
+    written independently from the Game methods.
+*/
+fn has_line(board: &[Vec<Option<PlayerSymbol>>], symbol: PlayerSymbol) -> bool {
+    LINES
+        .iter()
+        .any(|line| line.iter().all(|(y, x)| board[*y][*x] == Some(symbol)))
+}
+
+fn amount(msgs: &[BankMsg], address: &Addr) -> Uint128 {
+    msgs.iter()
+        .map(|msg| match msg {
+            BankMsg::Send { to_address, amount } if to_address == address.as_str() => {
+                amount.iter().map(|coin| coin.amount).sum()
+            }
+            _ => Uint128::zero(),
+        })
+        .sum()
+}
+
+fn check_payouts(game: &Game, stake: &[Coin]) {
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let msgs = prize_messages(game, &host, &opponent);
+    let stake = stake[0].amount;
+
+    // Funds are conserved: both stakes are paid out, nothing more
+    assert_eq!(
+        amount(&msgs, &host) + amount(&msgs, &opponent),
+        stake * Uint128::new(2)
+    );
+    match game.winner_address(&host, &opponent) {
+        // Exactly one winner is paid
+        Some(winner) => {
+            assert_eq!(msgs.len(), 1);
+            assert_eq!(amount(&msgs, winner), stake * Uint128::new(2));
+        }
+        None => {
+            assert_eq!(amount(&msgs, &host), stake);
+            assert_eq!(amount(&msgs, &opponent), stake);
+        }
+    }
+}
+
+fn explore(game: &Game, stake: &[Coin], outcomes: &mut Outcomes) {
+    assert_eq!(game.status, Status::PLAYING);
+
+    for coord in coords() {
+        if game.already_played_on(coord).unwrap() {
+            continue;
+        }
+        let mut next = game.clone();
+        let symbol = next.player_round.unwrap();
+        next.play(coord).unwrap();
+
+        let wins = has_line(&next.board, symbol);
+        let full = next.board.iter().flatten().all(|cell| cell.is_some());
+        assert_eq!(next.is_current_player_winner(), wins);
+        assert_eq!(next.is_full_board(), full);
+
+        next.complete_round().unwrap();
+        if wins || full {
+            assert_eq!(next.status, Status::COMPLETED);
+            assert_eq!(next.player_round, None);
+            assert_eq!(next.winner, if wins { Some(symbol) } else { None });
+            check_payouts(&next, stake);
+            match next.winner {
+                Some(PlayerSymbol::X) => outcomes.x_wins += 1,
+                Some(PlayerSymbol::O) => outcomes.o_wins += 1,
+                None => outcomes.ties += 1,
+            }
+        } else {
+            assert_eq!(next.status, Status::PLAYING);
+            assert_eq!(next.player_round, Some(symbol.other()));
+            explore(&next, stake, outcomes);
+        }
+    }
+}
+
+/**
+    Plays every possible game: the invite and the accept
+    as the contract does, then every legal sequence of moves.
+*/
+fn explore_all(host_symbol: PlayerSymbol) -> Outcomes {
+    let stake = coins(3, "token");
+    let mut outcomes = Outcomes::default();
+
+    for invite in coords() {
+        let game = Game::new(
+            1,
+            invite,
+            stake.clone(),
+            stake.clone(),
+            host_symbol,
+            Opening::Standard {},
+            None,
+            Variant::Classic {},
+        )
+        .unwrap();
+        assert_eq!(game.status, Status::INVITED);
+        assert_eq!(game.player_round, Some(host_symbol.other()));
+
+        for accept in coords() {
+            if game.already_played_on(accept).unwrap() {
+                continue;
+            }
+            let mut accepted = game.clone();
+            accepted.add_opponent_stake().unwrap().play(accept).unwrap();
+            assert!(!accepted.is_current_player_winner());
+            accepted.finish_round().unwrap();
+            accepted.status = Status::PLAYING;
+            assert_eq!(accepted.prize, coins(6, "token"));
+
+            explore(&accepted, &stake, &mut outcomes);
+        }
+    }
+
+    outcomes
+}
+
+#[test]
+fn every_game_with_host_as_x() {
+    // GIVEN
+    let host_symbol = PlayerSymbol::X;
+
+    // WHEN
+    let outcomes = explore_all(host_symbol);
+
+    // THEN
+    assert_eq!(
+        outcomes,
+        Outcomes {
+            x_wins: 131_184,
+            o_wins: 77_904,
+            ties: 46_080,
+        }
+    );
+    assert_eq!(outcomes.x_wins + outcomes.o_wins + outcomes.ties, 255_168);
+}
+
+#[test]
+fn every_game_with_host_as_o() {
+    // GIVEN
+    let host_symbol = PlayerSymbol::O;
+
+    // WHEN
+    let outcomes = explore_all(host_symbol);
+
+    // THEN
+    assert_eq!(
+        outcomes,
+        Outcomes {
+            x_wins: 77_904,
+            o_wins: 131_184,
+            ties: 46_080,
+        }
+    );
+}
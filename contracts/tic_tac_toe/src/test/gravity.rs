@@ -0,0 +1,250 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, DepsMut, Response};
+
+use crate::contract::execute::execute;
+use crate::models::{
+    errors::ContractError,
+    state::{Coord, Opening, PlayerSymbol, Status, Variant},
+    ExecuteMsg,
+};
+
+use super::helpers::{query_game, setup};
+
+const CONNECT_FOUR: Variant = Variant::Gravity {
+    columns: 7,
+    rows: 6,
+};
+
+fn invite(
+    deps: DepsMut,
+    coord: Coord,
+    opponent: &str,
+    opening: Option<Opening>,
+    variant: Variant,
+) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("host", &coins(2, "token")),
+        ExecuteMsg::Invite {
+            coord,
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from(opponent),
+            opponent_stake: None,
+            opening,
+            time_control: None,
+            variant: Some(variant),
+            practice: None,
+        },
+    )
+}
+
+fn accept(deps: DepsMut, coord: Coord) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord,
+            host: String::from("host"),
+        },
+    )
+}
+
+fn drop_piece(deps: DepsMut, as_host: bool, column: u8) -> Result<Response, ContractError> {
+    let sender = if as_host { "host" } else { "opponent" };
+    let opponent = if as_host { "opponent" } else { "host" };
+    execute(
+        deps,
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::DropPiece {
+            as_host,
+            column,
+            opponent: String::from(opponent),
+        },
+    )
+}
+
+#[test]
+fn pieces_fall_to_the_lowest_empty_cell() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    invite(
+        deps.as_mut(),
+        Coord { x: 3, y: 5 },
+        "opponent",
+        None,
+        CONNECT_FOUR,
+    )
+    .unwrap();
+    accept(deps.as_mut(), Coord { x: 3, y: 4 }).unwrap();
+
+    // WHEN
+    let res = drop_piece(deps.as_mut(), true, 3).unwrap();
+    let game = query_game(deps.as_ref()).game;
+
+    // THEN
+    assert_eq!(res.attributes[1].value, "3");
+    assert_eq!(res.attributes[2].value, "3");
+    assert_eq!(game.board.len(), 6);
+    assert!(game.board.iter().all(|row| row.len() == 7));
+    assert_eq!(game.board[5][3], Some(PlayerSymbol::X));
+    assert_eq!(game.board[4][3], Some(PlayerSymbol::O));
+    assert_eq!(game.board[3][3], Some(PlayerSymbol::X));
+    assert_eq!(game.player_round, Some(PlayerSymbol::O));
+}
+
+#[test]
+fn four_in_a_row_wins_a_gravity_game() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    invite(
+        deps.as_mut(),
+        Coord { x: 0, y: 5 },
+        "opponent",
+        None,
+        CONNECT_FOUR,
+    )
+    .unwrap();
+    accept(deps.as_mut(), Coord { x: 0, y: 4 }).unwrap();
+
+    // WHEN
+    for column in 1..3 {
+        drop_piece(deps.as_mut(), true, column).unwrap();
+        drop_piece(deps.as_mut(), false, 0).unwrap();
+    }
+    let three_in_a_row = query_game(deps.as_ref()).game;
+    drop_piece(deps.as_mut(), true, 3).unwrap();
+    let game = query_game(deps.as_ref()).game;
+
+    // THEN
+    assert_eq!(three_in_a_row.status, Status::PLAYING);
+    assert_eq!(game.status, Status::COMPLETED);
+    assert_eq!(game.winner, Some(PlayerSymbol::X));
+}
+
+#[test]
+fn gravity_moves_must_land_on_the_board() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    invite(
+        deps.as_mut(),
+        Coord { x: 0, y: 5 },
+        "opponent",
+        None,
+        CONNECT_FOUR,
+    )
+    .unwrap();
+
+    // WHEN
+    let floating_res = accept(deps.as_mut(), Coord { x: 0, y: 0 });
+    let outside_res = accept(deps.as_mut(), Coord { x: 7, y: 5 });
+    accept(deps.as_mut(), Coord { x: 0, y: 4 }).unwrap();
+    for _ in 0..2 {
+        drop_piece(deps.as_mut(), true, 0).unwrap();
+        drop_piece(deps.as_mut(), false, 0).unwrap();
+    }
+    let full_res = drop_piece(deps.as_mut(), true, 0);
+    let outside_drop_res = drop_piece(deps.as_mut(), true, 9);
+
+    // THEN
+    assert_eq!(
+        floating_res.unwrap_err(),
+        ContractError::PieceMustFall {
+            coord: Coord { x: 0, y: 0 },
+            landing: Coord { x: 0, y: 4 },
+        }
+    );
+    assert_eq!(
+        outside_res.unwrap_err(),
+        ContractError::InvalidColumn {
+            column: 7,
+            columns: 7
+        }
+    );
+    assert_eq!(
+        full_res.unwrap_err(),
+        ContractError::ColumnFull { column: 0 }
+    );
+    assert_eq!(
+        outside_drop_res.unwrap_err(),
+        ContractError::InvalidColumn {
+            column: 9,
+            columns: 7
+        }
+    );
+}
+
+#[test]
+fn invalid_variants_are_rejected() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    let house = mock_env().contract.address;
+
+    // WHEN
+    let small_res = invite(
+        deps.as_mut(),
+        Coord { x: 0, y: 2 },
+        "opponent",
+        None,
+        Variant::Gravity {
+            columns: 3,
+            rows: 3,
+        },
+    );
+    let no_center_res = invite(
+        deps.as_mut(),
+        Coord { x: 0, y: 5 },
+        "opponent",
+        Some(Opening::NoCenter {}),
+        CONNECT_FOUR,
+    );
+    let house_res = invite(
+        deps.as_mut(),
+        Coord { x: 0, y: 5 },
+        house.as_str(),
+        None,
+        CONNECT_FOUR,
+    );
+    invite(
+        deps.as_mut(),
+        Coord { x: 1, y: 1 },
+        "opponent",
+        None,
+        Variant::Classic {},
+    )
+    .unwrap();
+    accept(deps.as_mut(), Coord { x: 0, y: 0 }).unwrap();
+    let classic_drop_res = drop_piece(deps.as_mut(), true, 0);
+
+    // THEN
+    assert_eq!(
+        small_res.unwrap_err(),
+        ContractError::InvalidVariant {
+            reason: String::from("a gravity board has from 4 to 10 columns and rows"),
+        }
+    );
+    assert_eq!(
+        no_center_res.unwrap_err(),
+        ContractError::InvalidVariant {
+            reason: String::from("a gravity board has no center to forbid"),
+        }
+    );
+    assert_eq!(
+        house_res.unwrap_err(),
+        ContractError::InvalidVariant {
+            reason: String::from("the house only plays classic games"),
+        }
+    );
+    assert_eq!(
+        classic_drop_res.unwrap_err(),
+        ContractError::InvalidVariant {
+            reason: String::from("pieces can only be dropped on a gravity board"),
+        }
+    );
+}
@@ -5,8 +5,8 @@ use crate::contract::execute::execute;
 use crate::contract::instantiate::instantiate;
 use crate::contract::query::query;
 use crate::models::{
-    responses::GameResponse, state::Coord, state::Game, state::PlayerSymbol,
-    state::Status, ExecuteMsg, InstantiateMsg, QueryMsg, QueryKey
+    events, responses::GameResponse, state::Coord, state::Game, state::PlayerSymbol,
+    state::Opening, state::Status, state::Variant, ExecuteMsg, InstantiateMsg, QueryMsg, QueryKey
 };
 
 #[test]
@@ -19,7 +19,7 @@ fn tie() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
 
@@ -31,6 +31,11 @@ fn tie() {
             coord: Coord { x: 1, y: 1 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -138,6 +143,19 @@ fn tie() {
 
     // THEN
     let query_value: Vec<GameResponse> = from_binary(&res.unwrap()).unwrap();
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let game = &query_value[0].game;
+    let prize = vec![
+        BankMsg::Send {
+            to_address: String::from("host"),
+            amount: coins(2, "token"),
+        },
+        BankMsg::Send {
+            to_address: String::from("opponent"),
+            amount: coins(2, "token"),
+        },
+    ];
     assert_eq!(
         play_res,
         Response::new()
@@ -146,6 +164,9 @@ fn tie() {
             .add_attribute("y", "1")
             .add_attribute("status", "COMPLETED")
             .add_attribute("opponent", "opponent")
+            .add_event(events::move_played(&host, &opponent, game, &host, Coord { x: 0, y: 1 }))
+            .add_event(events::game_completed(&host, &opponent, game))
+            .add_events(events::prizes_paid(&host, &opponent, game, &prize))
             .add_messages(vec![
                 BankMsg::Send {
                     to_address: String::from("host"),
@@ -163,6 +184,7 @@ fn tie() {
             host: Addr::unchecked("host"),
             opponent: Addr::unchecked("opponent"),
             game: Game {
+                id: 1,
                 board: vec![
                     vec![Some(PlayerSymbol::X), Some(PlayerSymbol::O), Some(PlayerSymbol::X)],
                     vec![Some(PlayerSymbol::X), Some(PlayerSymbol::X), Some(PlayerSymbol::O)],
@@ -171,6 +193,16 @@ fn tie() {
                 player_round: None,
                 host_symbol: PlayerSymbol::X,
                 prize: coins(4, "token"),
+                host_stake: coins(2, "token"),
+                opponent_stake: coins(2, "token"),
+                sponsor: None,
+                sponsorship: vec![],
+                practice: false,
+                opponent_first: false,
+                opening: Opening::Standard {},
+                time_control: None,
+                variant: Variant::Classic {},
+                positions: vec![],
                 status: Status::COMPLETED,
                 winner: None
             }
@@ -188,7 +220,7 @@ fn host_wins() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
 
@@ -200,6 +232,11 @@ fn host_wins() {
             coord: Coord { x: 1, y: 1 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -263,6 +300,13 @@ fn host_wins() {
 
     // THEN
     let query_value: Vec<GameResponse> = from_binary(&res.unwrap()).unwrap();
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let game = &query_value[0].game;
+    let prize = vec![BankMsg::Send {
+        to_address: String::from("host"),
+        amount: coins(4, "token"),
+    }];
     assert_eq!(
         play_res,
         Response::new()
@@ -271,7 +315,10 @@ fn host_wins() {
             .add_attribute("y", "2")
             .add_attribute("status", "COMPLETED")
             .add_attribute("opponent", "opponent")
-            .add_attribute("winner", "X")
+            .add_attribute("winner", "host")
+            .add_event(events::move_played(&host, &opponent, game, &host, Coord { x: 1, y: 2 }))
+            .add_event(events::game_completed(&host, &opponent, game))
+            .add_events(events::prizes_paid(&host, &opponent, game, &prize))
             .add_messages(vec![
                 BankMsg::Send {
                     to_address: String::from("host"),
@@ -285,6 +332,7 @@ fn host_wins() {
             host: Addr::unchecked("host"),
             opponent: Addr::unchecked("opponent"),
             game:Game {
+                id: 1,
                 board: vec![
                     vec![None, Some(PlayerSymbol::X), None], 
                     vec![None, Some(PlayerSymbol::X), None], 
@@ -293,6 +341,16 @@ fn host_wins() {
                 player_round: None,
                 host_symbol: PlayerSymbol::X,
                 prize: coins(4, "token"),
+                host_stake: coins(2, "token"),
+                opponent_stake: coins(2, "token"),
+                sponsor: None,
+                sponsorship: vec![],
+                practice: false,
+                opponent_first: false,
+                opening: Opening::Standard {},
+                time_control: None,
+                variant: Variant::Classic {},
+                positions: vec![],
                 status: Status::COMPLETED,
                 winner: Some(PlayerSymbol::X)
             }
@@ -311,7 +369,7 @@ fn opponent_wins() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
 
@@ -323,6 +381,11 @@ fn opponent_wins() {
             coord: Coord { x: 1, y: 1 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -398,6 +461,13 @@ fn opponent_wins() {
 
     // THEN
     let query_value: Vec<GameResponse> = from_binary(&res.unwrap()).unwrap();
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let game = &query_value[0].game;
+    let prize = vec![BankMsg::Send {
+        to_address: String::from("opponent"),
+        amount: coins(4, "token"),
+    }];
     assert_eq!(
         play_res,
         Response::new()
@@ -406,7 +476,10 @@ fn opponent_wins() {
             .add_attribute("y", "2")
             .add_attribute("status", "COMPLETED")
             .add_attribute("opponent", "host")
-            .add_attribute("winner", "O")
+            .add_attribute("winner", "opponent")
+            .add_event(events::move_played(&host, &opponent, game, &opponent, Coord { x: 1, y: 2 }))
+            .add_event(events::game_completed(&host, &opponent, game))
+            .add_events(events::prizes_paid(&host, &opponent, game, &prize))
             .add_messages(vec![
                 BankMsg::Send {
                     to_address: String::from("opponent"),
@@ -420,6 +493,7 @@ fn opponent_wins() {
             host: Addr::unchecked("host"),
             opponent: Addr::unchecked("opponent"),
             game:Game {
+                id: 1,
                 board: vec![
                     vec![None, Some(PlayerSymbol::X), None], 
                     vec![None, Some(PlayerSymbol::X), Some(PlayerSymbol::X)], 
@@ -428,6 +502,16 @@ fn opponent_wins() {
                 player_round: None,
                 host_symbol: PlayerSymbol::X,
                 prize: coins(4, "token"),
+                host_stake: coins(2, "token"),
+                opponent_stake: coins(2, "token"),
+                sponsor: None,
+                sponsorship: vec![],
+                practice: false,
+                opponent_first: false,
+                opening: Opening::Standard {},
+                time_control: None,
+                variant: Variant::Classic {},
+                positions: vec![],
                 status: Status::COMPLETED,
                 winner: Some(PlayerSymbol::O)
             }
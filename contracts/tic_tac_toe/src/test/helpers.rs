@@ -0,0 +1,42 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_binary, Deps, DepsMut};
+
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::models::{responses::GameResponse, InstantiateMsg, QueryKey, QueryMsg};
+
+/**
+    Instantiates the contract with the default config
+    and admin as the admin.
+*/
+pub fn setup(deps: DepsMut) {
+    instantiate(
+        deps,
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+}
+
+/**
+    Returns the game between host and opponent.
+*/
+pub fn query_game(deps: Deps) -> GameResponse {
+    let res: Vec<GameResponse> = from_binary(
+        &query(
+            deps,
+            mock_env(),
+            QueryMsg::Games {
+                key: Some(QueryKey {
+                    host: String::from("host"),
+                    opponent: String::from("opponent"),
+                }),
+                status: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    res[0].clone()
+}
@@ -0,0 +1,481 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{coin, coins, from_binary, BankMsg, Coin, Deps, DepsMut, Response};
+
+use crate::bot::choose_move;
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::models::{
+    errors::ContractError,
+    responses::{EvaluationResponse, GameResponse, HouseResponse},
+    state::{Coord, Difficulty, Game, Opening, PlayerSymbol, Status, Variant},
+    ExecuteMsg, InstantiateMsg, QueryKey, QueryMsg,
+};
+
+fn house_game(deps: Deps) -> Game {
+    let res = query(
+        deps,
+        mock_env(),
+        QueryMsg::Games {
+            key: Some(QueryKey {
+                host: String::from("player"),
+                opponent: String::from(MOCK_CONTRACT_ADDR),
+            }),
+            status: None,
+        },
+    );
+    let value: Vec<GameResponse> = from_binary(&res.unwrap()).unwrap();
+    value[0].game.clone()
+}
+
+fn house(deps: Deps) -> HouseResponse {
+    from_binary(&query(deps, mock_env(), QueryMsg::House {}).unwrap()).unwrap()
+}
+
+#[test]
+fn fund_and_withdraw_house() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &coins(100, "token")),
+        ExecuteMsg::FundHouse {},
+    )
+    .unwrap();
+
+    // WHEN
+    let withdraw_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::WithdrawHouse {
+            amount: coins(40, "token"),
+        },
+    )
+    .unwrap();
+
+    // THEN
+    assert_eq!(
+        withdraw_res,
+        Response::new()
+            .add_attribute("method", "withdraw_house")
+            .add_attribute("bankroll", "token60")
+            .add_message(BankMsg::Send {
+                to_address: String::from("admin"),
+                amount: coins(40, "token"),
+            })
+    );
+    assert_eq!(house(deps.as_ref()).bankroll, coins(60, "token"));
+}
+
+#[test]
+fn fund_and_withdraw_house_without_permissions() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+
+    // WHEN
+    let fund_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("player", &coins(100, "token")),
+        ExecuteMsg::FundHouse {},
+    )
+    .unwrap_err();
+    let withdraw_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::WithdrawHouse {
+            amount: coins(1, "token"),
+        },
+    )
+    .unwrap_err();
+
+    // THEN
+    assert_eq!(fund_res, ContractError::Unauthorized {});
+    assert_eq!(withdraw_res, ContractError::InsufficientBankroll {});
+}
+
+#[test]
+fn fund_and_withdraw_house_normalize_the_amount() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    let withdraw = |deps: DepsMut, amount: Vec<Coin>| {
+        execute(
+            deps,
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::WithdrawHouse { amount },
+        )
+    };
+
+    // WHEN
+    let fund_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[coin(60, "token"), coin(40, "token")]),
+        ExecuteMsg::FundHouse {},
+    )
+    .unwrap();
+    let zero_fund_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[coin(0, "token")]),
+        ExecuteMsg::FundHouse {},
+    )
+    .unwrap_err();
+    let empty_res = withdraw(deps.as_mut(), vec![]).unwrap_err();
+    let zero_res = withdraw(deps.as_mut(), vec![coin(0, "token")]).unwrap_err();
+    let res = withdraw(
+        deps.as_mut(),
+        vec![coin(20, "token"), coin(0, "other"), coin(20, "token")],
+    )
+    .unwrap();
+
+    // THEN
+    assert_eq!(
+        fund_res,
+        Response::new()
+            .add_attribute("method", "fund_house")
+            .add_attribute("bankroll", "token100")
+    );
+    assert_eq!(zero_fund_res, ContractError::InvalidReceivedFunds {});
+    assert_eq!(empty_res, ContractError::EmptyWithdrawal {});
+    assert_eq!(zero_res, ContractError::EmptyWithdrawal {});
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("method", "withdraw_house")
+            .add_attribute("bankroll", "token60")
+            .add_message(BankMsg::Send {
+                to_address: String::from("admin"),
+                amount: coins(40, "token"),
+            })
+    );
+}
+
+#[test]
+fn invite_house_over_max_exposure() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            house_max_exposure: Some(coins(5, "token")),
+            ..InstantiateMsg::default()
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &coins(100, "token")),
+        ExecuteMsg::FundHouse {},
+    )
+    .unwrap();
+
+    // WHEN
+    let invite_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("player", &coins(10, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 1, y: 1 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from(MOCK_CONTRACT_ADDR),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap_err();
+
+    // THEN
+    assert_eq!(invite_res, ContractError::HouseExposureExceeded {});
+}
+
+#[test]
+fn invite_house() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            house_max_exposure: Some(coins(50, "token")),
+            ..InstantiateMsg::default()
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &coins(100, "token")),
+        ExecuteMsg::FundHouse {},
+    )
+    .unwrap();
+
+    // WHEN
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("player", &coins(10, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 1, y: 1 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from(MOCK_CONTRACT_ADDR),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+
+    // THEN
+    let game = house_game(deps.as_ref());
+    let corners = [(0, 0), (2, 0), (0, 2), (2, 2)];
+    assert_eq!(game.status, Status::PLAYING);
+    assert_eq!(game.prize, coins(20, "token"));
+    assert_eq!(game.player_round, Some(PlayerSymbol::X));
+    assert!(corners
+        .iter()
+        .any(|(x, y)| game.board[*y][*x] == Some(PlayerSymbol::O)));
+    assert_eq!(
+        house(deps.as_ref()),
+        HouseResponse {
+            difficulty: Difficulty::PERFECT,
+            max_exposure: coins(50, "token"),
+            bankroll: coins(90, "token"),
+            exposure: coins(10, "token"),
+        }
+    );
+}
+
+#[test]
+fn lose_against_perfect_house() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            house_max_exposure: Some(coins(50, "token")),
+            ..InstantiateMsg::default()
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &coins(100, "token")),
+        ExecuteMsg::FundHouse {},
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("player", &coins(10, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 1 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from(MOCK_CONTRACT_ADDR),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    while house_game(deps.as_ref()).status == Status::PLAYING {
+        let game = house_game(deps.as_ref());
+        let coord = (0..9)
+            .map(|cell| Coord {
+                x: cell % 3,
+                y: cell / 3,
+            })
+            .find(|coord| !game.already_played_on(*coord).unwrap())
+            .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("player", &[]),
+            ExecuteMsg::Play {
+                as_host: true,
+                coord,
+                opponent: String::from(MOCK_CONTRACT_ADDR),
+            },
+        )
+        .unwrap();
+    }
+
+    // THEN
+    assert_eq!(house_game(deps.as_ref()).winner, Some(PlayerSymbol::O));
+    assert_eq!(house(deps.as_ref()).bankroll, coins(110, "token"));
+    assert_eq!(house(deps.as_ref()).exposure, vec![]);
+}
+
+#[test]
+fn perfect_play_against_random_house() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            house_difficulty: Some(Difficulty::RANDOM),
+            house_max_exposure: Some(coins(50, "token")),
+            ..InstantiateMsg::default()
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &coins(100, "token")),
+        ExecuteMsg::FundHouse {},
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("player", &coins(10, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 1, y: 1 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from(MOCK_CONTRACT_ADDR),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let mut last_res = Response::new();
+    while house_game(deps.as_ref()).status == Status::PLAYING {
+        let evaluation: EvaluationResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Evaluate {
+                    game: QueryKey {
+                        host: String::from("player"),
+                        opponent: String::from(MOCK_CONTRACT_ADDR),
+                    },
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        last_res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("player", &[]),
+            ExecuteMsg::Play {
+                as_host: true,
+                coord: evaluation.optimal_moves[0],
+                opponent: String::from(MOCK_CONTRACT_ADDR),
+            },
+        )
+        .unwrap();
+    }
+
+    // THEN
+    let game = house_game(deps.as_ref());
+    let house = house(deps.as_ref());
+    assert_eq!(house.exposure, vec![]);
+    match game.winner {
+        Some(winner) => {
+            assert_eq!(winner, PlayerSymbol::X);
+            assert_eq!(house.bankroll, coins(90, "token"));
+            assert_eq!(
+                last_res.messages[0].msg,
+                BankMsg::Send {
+                    to_address: String::from("player"),
+                    amount: coins(20, "token"),
+                }
+                .into()
+            );
+        }
+        None => assert_eq!(house.bankroll, coins(100, "token")),
+    }
+}
+
+#[test]
+fn medium_bot_wins_or_blocks() {
+    // GIVEN
+    let mut winning = Game::new(
+        1,
+        Coord { x: 0, y: 0 },
+        vec![],
+        vec![],
+        PlayerSymbol::X,
+        Opening::Standard {},
+        None,
+        Variant::Classic {},
+    )
+    .unwrap();
+    winning.board[0][1] = Some(PlayerSymbol::X);
+    winning.board[1][1] = Some(PlayerSymbol::O);
+    winning.board[2][1] = Some(PlayerSymbol::O);
+    winning.player_round = Some(PlayerSymbol::X);
+
+    let mut blocking = Game::new(
+        1,
+        Coord { x: 0, y: 0 },
+        vec![],
+        vec![],
+        PlayerSymbol::X,
+        Opening::Standard {},
+        None,
+        Variant::Classic {},
+    )
+    .unwrap();
+    blocking.board[1][1] = Some(PlayerSymbol::X);
+    blocking.board[0][2] = Some(PlayerSymbol::O);
+    blocking.player_round = Some(PlayerSymbol::O);
+
+    // WHEN
+    let win_move = choose_move(&winning, Difficulty::MEDIUM, 7);
+    let block_move = choose_move(&blocking, Difficulty::MEDIUM, 7);
+
+    // THEN
+    assert_eq!(win_move, Some(Coord { x: 2, y: 0 }));
+    assert_eq!(block_move, Some(Coord { x: 2, y: 2 }));
+}
@@ -0,0 +1,572 @@
+use cosmwasm_std::{coin, coins, Addr, Coin, Empty};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::models::responses::{GameResponse, SolvencyResponse};
+use crate::models::{
+    state::{Coord, Game, Pause, PayoutMode, PlayerSymbol, Status},
+    ExecuteMsg, InstantiateMsg, QueryKey, QueryMsg,
+};
+
+fn tic_tac_toe_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+/**
+    Contract stored and instantiated in a cw-multi-test
+    app where the players start with the given balances.
+*/
+struct Suite {
+    app: App,
+    contract: Addr,
+}
+
+impl Suite {
+    fn new(balances: &[(&str, Vec<Coin>)]) -> Suite {
+        Suite::with_msg(balances, InstantiateMsg::default())
+    }
+
+    fn with_msg(balances: &[(&str, Vec<Coin>)], msg: InstantiateMsg) -> Suite {
+        let mut app = App::new(|router, _, storage| {
+            for (address, amount) in balances {
+                router
+                    .bank
+                    .init_balance(storage, &Addr::unchecked(*address), amount.clone())
+                    .unwrap();
+            }
+        });
+        let code_id = app.store_code(tic_tac_toe_contract());
+        let contract = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("admin"),
+                &msg,
+                &[],
+                "tic_tac_toe",
+                None,
+            )
+            .unwrap();
+
+        Suite { app, contract }
+    }
+
+    fn execute(
+        &mut self,
+        sender: &str,
+        msg: ExecuteMsg,
+        funds: &[Coin],
+    ) -> anyhow::Result<AppResponse> {
+        self.app
+            .execute_contract(Addr::unchecked(sender), self.contract.clone(), &msg, funds)
+    }
+
+    fn invite(&mut self, funds: &[Coin], coord: Coord) -> anyhow::Result<AppResponse> {
+        self.execute(
+            "host",
+            ExecuteMsg::Invite {
+                coord,
+                host_symbol: PlayerSymbol::X,
+                opponent: String::from("opponent"),
+                opponent_stake: None,
+                opening: None,
+                time_control: None,
+                variant: None,
+                practice: None,
+            },
+            funds,
+        )
+    }
+
+    fn accept(&mut self, funds: &[Coin], coord: Coord) -> anyhow::Result<AppResponse> {
+        self.execute(
+            "opponent",
+            ExecuteMsg::Accept {
+                coord,
+                host: String::from("host"),
+            },
+            funds,
+        )
+    }
+
+    fn play(&mut self, as_host: bool, coord: Coord) -> anyhow::Result<AppResponse> {
+        let (sender, opponent) = if as_host {
+            ("host", "opponent")
+        } else {
+            ("opponent", "host")
+        };
+        self.execute(
+            sender,
+            ExecuteMsg::Play {
+                as_host,
+                coord,
+                opponent: String::from(opponent),
+            },
+            &[],
+        )
+    }
+
+    fn pause(&mut self, pause: Pause) {
+        self.execute("admin", ExecuteMsg::UpdatePause { pause }, &[])
+            .unwrap();
+    }
+
+    fn emergency_withdraw(&mut self, as_host: bool, opponent: &str) -> anyhow::Result<AppResponse> {
+        let sender = if as_host { "host" } else { "opponent" };
+        self.execute(
+            sender,
+            ExecuteMsg::EmergencyWithdraw {
+                as_host,
+                opponent: String::from(opponent),
+            },
+            &[],
+        )
+    }
+
+    fn game(&self, host: &str, opponent: &str) -> Game {
+        let games: Vec<GameResponse> = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                self.contract.clone(),
+                &QueryMsg::Games {
+                    key: Some(QueryKey {
+                        host: String::from(host),
+                        opponent: String::from(opponent),
+                    }),
+                    status: None,
+                },
+            )
+            .unwrap();
+        games[0].game.clone()
+    }
+
+    fn solvency(&self) -> SolvencyResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), &QueryMsg::Solvency {})
+            .unwrap()
+    }
+
+    /**
+        Asserts the balances of host, opponent and contract,
+        denoms that are not listed must have no funds.
+    */
+    fn assert_balances(&self, host: &[Coin], opponent: &[Coin], contract: &[Coin]) {
+        let balances = |address: &str| self.app.wrap().query_all_balances(address).unwrap();
+
+        assert_eq!(balances("host"), host, "balance of host");
+        assert_eq!(balances("opponent"), opponent, "balance of opponent");
+        assert_eq!(
+            balances(self.contract.as_str()),
+            contract,
+            "balance of contract"
+        );
+    }
+}
+
+fn multi_denom(token: u128, uluna: u128) -> Vec<Coin> {
+    vec![coin(token, "token"), coin(uluna, "uluna")]
+}
+
+#[test]
+fn host_wins() {
+    // GIVEN
+    let mut suite = Suite::new(&[
+        ("host", coins(10, "token")),
+        ("opponent", coins(10, "token")),
+    ]);
+    suite.assert_balances(&coins(10, "token"), &coins(10, "token"), &[]);
+
+    // WHEN
+    suite
+        .invite(&coins(3, "token"), Coord { x: 1, y: 1 })
+        .unwrap();
+    suite.assert_balances(&coins(7, "token"), &coins(10, "token"), &coins(3, "token"));
+
+    suite
+        .accept(&coins(3, "token"), Coord { x: 2, y: 2 })
+        .unwrap();
+    suite.assert_balances(&coins(7, "token"), &coins(7, "token"), &coins(6, "token"));
+
+    suite.play(true, Coord { x: 1, y: 0 }).unwrap();
+    suite.play(false, Coord { x: 0, y: 2 }).unwrap();
+    suite.assert_balances(&coins(7, "token"), &coins(7, "token"), &coins(6, "token"));
+
+    suite.play(true, Coord { x: 1, y: 2 }).unwrap();
+
+    // THEN
+    suite.assert_balances(&coins(13, "token"), &coins(7, "token"), &[]);
+}
+
+#[test]
+fn opponent_wins_multi_denom() {
+    // GIVEN
+    let mut suite = Suite::new(&[
+        ("host", multi_denom(10, 20)),
+        ("opponent", multi_denom(10, 20)),
+    ]);
+    suite
+        .invite(&multi_denom(2, 5), Coord { x: 1, y: 1 })
+        .unwrap();
+    suite.assert_balances(
+        &multi_denom(8, 15),
+        &multi_denom(10, 20),
+        &multi_denom(2, 5),
+    );
+    suite
+        .accept(&multi_denom(2, 5), Coord { x: 2, y: 2 })
+        .unwrap();
+    suite.assert_balances(
+        &multi_denom(8, 15),
+        &multi_denom(8, 15),
+        &multi_denom(4, 10),
+    );
+    suite.play(true, Coord { x: 1, y: 0 }).unwrap();
+    suite.play(false, Coord { x: 0, y: 2 }).unwrap();
+    suite.play(true, Coord { x: 2, y: 1 }).unwrap();
+
+    // WHEN
+    suite.play(false, Coord { x: 1, y: 2 }).unwrap();
+
+    // THEN
+    suite.assert_balances(&multi_denom(8, 15), &multi_denom(12, 25), &[]);
+}
+
+#[test]
+fn tie_splits_the_prize() {
+    // GIVEN
+    let mut suite = Suite::new(&[
+        ("host", multi_denom(10, 20)),
+        ("opponent", multi_denom(10, 20)),
+    ]);
+    suite
+        .invite(&multi_denom(3, 7), Coord { x: 1, y: 1 })
+        .unwrap();
+    suite
+        .accept(&multi_denom(3, 7), Coord { x: 2, y: 2 })
+        .unwrap();
+    let moves = [
+        (true, Coord { x: 2, y: 0 }),
+        (false, Coord { x: 0, y: 2 }),
+        (true, Coord { x: 1, y: 2 }),
+        (false, Coord { x: 1, y: 0 }),
+        (true, Coord { x: 0, y: 0 }),
+        (false, Coord { x: 2, y: 1 }),
+    ];
+    for (as_host, coord) in moves {
+        suite.play(as_host, coord).unwrap();
+        suite.assert_balances(
+            &multi_denom(7, 13),
+            &multi_denom(7, 13),
+            &multi_denom(6, 14),
+        );
+    }
+
+    // WHEN
+    suite.play(true, Coord { x: 0, y: 1 }).unwrap();
+
+    // THEN
+    suite.assert_balances(&multi_denom(10, 20), &multi_denom(10, 20), &[]);
+}
+
+#[test]
+fn reject_refunds_the_host() {
+    // GIVEN
+    let mut suite = Suite::new(&[
+        ("host", multi_denom(10, 20)),
+        ("opponent", coins(10, "token")),
+    ]);
+    suite
+        .invite(&multi_denom(4, 9), Coord { x: 0, y: 0 })
+        .unwrap();
+    suite.assert_balances(&multi_denom(6, 11), &coins(10, "token"), &multi_denom(4, 9));
+
+    // WHEN
+    suite
+        .execute(
+            "opponent",
+            ExecuteMsg::Reject {
+                as_host: false,
+                opponent: String::from("host"),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // THEN
+    suite.assert_balances(&multi_denom(10, 20), &coins(10, "token"), &[]);
+}
+
+#[test]
+fn failed_messages_keep_the_balances() {
+    // GIVEN
+    let mut suite = Suite::new(&[
+        ("host", multi_denom(10, 20)),
+        ("opponent", multi_denom(10, 20)),
+    ]);
+    suite
+        .invite(&multi_denom(2, 5), Coord { x: 1, y: 1 })
+        .unwrap();
+
+    // WHEN
+    let wrong_amount = suite.accept(&multi_denom(2, 4), Coord { x: 2, y: 2 });
+    let missing_denom = suite.accept(&coins(2, "token"), Coord { x: 2, y: 2 });
+    let played_coord = suite.accept(&multi_denom(2, 5), Coord { x: 1, y: 1 });
+    let not_playing = suite.play(true, Coord { x: 0, y: 0 });
+
+    // THEN
+    assert!(wrong_amount.is_err());
+    assert!(missing_denom.is_err());
+    assert!(played_coord.is_err());
+    assert!(not_playing.is_err());
+    suite.assert_balances(
+        &multi_denom(8, 15),
+        &multi_denom(10, 20),
+        &multi_denom(2, 5),
+    );
+}
+
+#[test]
+fn emergency_withdraw_returns_both_stakes() {
+    // GIVEN
+    let mut suite = Suite::new(&[
+        ("host", multi_denom(10, 20)),
+        ("opponent", multi_denom(10, 20)),
+    ]);
+    suite
+        .invite(&multi_denom(3, 7), Coord { x: 1, y: 1 })
+        .unwrap();
+    suite
+        .accept(&multi_denom(3, 7), Coord { x: 2, y: 2 })
+        .unwrap();
+    suite.play(true, Coord { x: 0, y: 0 }).unwrap();
+    suite.pause(Pause::MOVES);
+
+    // WHEN
+    let blocked_move = suite.play(false, Coord { x: 2, y: 0 });
+    suite.emergency_withdraw(false, "host").unwrap();
+
+    // THEN
+    assert!(blocked_move
+        .unwrap_err()
+        .root_cause()
+        .to_string()
+        .starts_with("[paused]"));
+    assert_eq!(suite.game("host", "opponent").status, Status::VOIDED);
+    suite.assert_balances(&multi_denom(10, 20), &multi_denom(10, 20), &[]);
+}
+
+#[test]
+fn emergency_withdraw_returns_the_invite_stake() {
+    // GIVEN
+    let mut suite = Suite::new(&[
+        ("host", coins(10, "token")),
+        ("opponent", coins(10, "token")),
+    ]);
+    suite
+        .invite(&coins(4, "token"), Coord { x: 0, y: 0 })
+        .unwrap();
+    suite.pause(Pause::ALL);
+
+    // WHEN
+    let blocked_accept = suite.accept(&coins(4, "token"), Coord { x: 1, y: 1 });
+    let opponent_res = suite.emergency_withdraw(false, "host");
+    let second_res = suite.emergency_withdraw(true, "opponent");
+
+    // THEN
+    assert!(blocked_accept.is_err());
+    opponent_res.unwrap();
+    assert!(second_res.is_err());
+    assert_eq!(suite.game("host", "opponent").status, Status::VOIDED);
+    suite.assert_balances(&coins(10, "token"), &coins(10, "token"), &[]);
+}
+
+#[test]
+fn emergency_withdraw_against_the_house() {
+    // GIVEN
+    let mut suite = Suite::with_msg(
+        &[("host", coins(10, "token")), ("admin", coins(10, "token"))],
+        InstantiateMsg {
+            house_max_exposure: Some(coins(10, "token")),
+            ..InstantiateMsg::default()
+        },
+    );
+    suite
+        .execute("admin", ExecuteMsg::FundHouse {}, &coins(10, "token"))
+        .unwrap();
+    let house = suite.contract.to_string();
+    suite
+        .execute(
+            "host",
+            ExecuteMsg::Invite {
+                coord: Coord { x: 1, y: 1 },
+                host_symbol: PlayerSymbol::X,
+                opponent: house.clone(),
+                opponent_stake: None,
+                opening: None,
+                time_control: None,
+                variant: None,
+                practice: None,
+            },
+            &coins(4, "token"),
+        )
+        .unwrap();
+    suite.pause(Pause::MOVES);
+
+    // WHEN
+    suite.emergency_withdraw(true, &house).unwrap();
+    suite
+        .execute(
+            "admin",
+            ExecuteMsg::WithdrawHouse {
+                amount: coins(10, "token"),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // THEN
+    assert_eq!(suite.game("host", &house).status, Status::VOIDED);
+    suite.assert_balances(&coins(10, "token"), &[], &[]);
+}
+
+#[test]
+fn emergency_withdraw_in_pull_mode() {
+    // GIVEN
+    let mut suite = Suite::with_msg(
+        &[
+            ("host", coins(10, "token")),
+            ("opponent", coins(10, "token")),
+        ],
+        InstantiateMsg {
+            payout_mode: Some(PayoutMode::PULL),
+            ..InstantiateMsg::default()
+        },
+    );
+    suite
+        .invite(&coins(5, "token"), Coord { x: 1, y: 1 })
+        .unwrap();
+    suite
+        .accept(&coins(5, "token"), Coord { x: 2, y: 2 })
+        .unwrap();
+    suite.pause(Pause::ALL);
+
+    // WHEN
+    suite.emergency_withdraw(true, "opponent").unwrap();
+    suite.assert_balances(&coins(10, "token"), &coins(5, "token"), &coins(5, "token"));
+    suite
+        .execute("opponent", ExecuteMsg::Claim {}, &[])
+        .unwrap();
+
+    // THEN
+    suite.assert_balances(&coins(10, "token"), &coins(10, "token"), &[]);
+}
+
+#[test]
+fn solvency_tracks_the_escrow() {
+    // GIVEN
+    let mut suite = Suite::new(&[
+        ("host", multi_denom(10, 20)),
+        ("opponent", multi_denom(10, 20)),
+    ]);
+    suite
+        .invite(&multi_denom(3, 7), Coord { x: 1, y: 1 })
+        .unwrap();
+    let invited = suite.solvency();
+    suite
+        .accept(&multi_denom(3, 7), Coord { x: 2, y: 2 })
+        .unwrap();
+    let playing = suite.solvency();
+
+    // WHEN
+    suite.play(true, Coord { x: 1, y: 0 }).unwrap();
+    suite.play(false, Coord { x: 0, y: 2 }).unwrap();
+    suite.play(true, Coord { x: 1, y: 2 }).unwrap();
+
+    // THEN
+    assert_eq!(invited.escrow, multi_denom(3, 7));
+    assert_eq!(invited.liabilities, invited.balance);
+    assert!(invited.solvent && invited.surplus.is_empty());
+    assert_eq!(playing.escrow, multi_denom(6, 14));
+    assert_eq!(playing.liabilities, multi_denom(6, 14));
+    assert!(playing.solvent && playing.surplus.is_empty());
+    let completed = suite.solvency();
+    assert_eq!(completed.escrow, vec![]);
+    assert_eq!(completed.liabilities, vec![]);
+    assert!(completed.solvent);
+    suite.assert_balances(&multi_denom(13, 27), &multi_denom(7, 13), &[]);
+}
+
+#[test]
+fn admin_sweeps_only_the_surplus() {
+    // GIVEN
+    let mut suite = Suite::with_msg(
+        &[
+            ("host", coins(10, "token")),
+            ("opponent", coins(10, "token")),
+            ("admin", coins(10, "token")),
+        ],
+        InstantiateMsg {
+            payout_mode: Some(PayoutMode::PULL),
+            ..InstantiateMsg::default()
+        },
+    );
+    suite
+        .execute("admin", ExecuteMsg::FundHouse {}, &coins(5, "token"))
+        .unwrap();
+    suite
+        .invite(&coins(2, "token"), Coord { x: 1, y: 1 })
+        .unwrap();
+    suite
+        .execute(
+            "opponent",
+            ExecuteMsg::Reject {
+                as_host: false,
+                opponent: String::from("host"),
+            },
+            &[],
+        )
+        .unwrap();
+    suite
+        .invite(&coins(3, "token"), Coord { x: 1, y: 1 })
+        .unwrap();
+    let contract = suite.contract.clone();
+    suite
+        .app
+        .send_tokens(Addr::unchecked("opponent"), contract, &coins(4, "token"))
+        .unwrap();
+    let solvency = suite.solvency();
+
+    // WHEN
+    let unauthorized_res = suite.execute("host", ExecuteMsg::SweepSurplus { recipient: None }, &[]);
+    suite
+        .execute(
+            "admin",
+            ExecuteMsg::SweepSurplus {
+                recipient: Some(String::from("opponent")),
+            },
+            &[],
+        )
+        .unwrap();
+    let empty_res = suite.execute("admin", ExecuteMsg::SweepSurplus { recipient: None }, &[]);
+
+    // THEN
+    assert_eq!(solvency.balance, coins(14, "token"));
+    assert_eq!(solvency.escrow, coins(3, "token"));
+    assert_eq!(solvency.claims, coins(2, "token"));
+    assert_eq!(solvency.bankroll, coins(5, "token"));
+    assert_eq!(solvency.liabilities, coins(10, "token"));
+    assert_eq!(solvency.surplus, coins(4, "token"));
+    assert!(solvency.solvent && solvency.deficit.is_empty());
+    assert!(unauthorized_res.is_err());
+    assert!(empty_res
+        .unwrap_err()
+        .root_cause()
+        .to_string()
+        .starts_with("[no_surplus]"));
+    suite.assert_balances(&coins(5, "token"), &coins(10, "token"), &coins(10, "token"));
+    let solvency = suite.solvency();
+    assert_eq!(solvency.liabilities, solvency.balance);
+}
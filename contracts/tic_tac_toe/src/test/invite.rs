@@ -5,15 +5,15 @@ use crate::contract::execute::execute;
 use crate::contract::instantiate::instantiate;
 use crate::contract::query::query;
 use crate::models::{
-    errors::ContractError, responses::GameResponse, state::Coord, state::Game, state::PlayerSymbol,
-    state::Status, ExecuteMsg, InstantiateMsg, QueryMsg, QueryKey
+    errors::ContractError, events, responses::GameResponse, state::Coord, state::Game, state::PlayerSymbol,
+    state::Opening, state::Status, state::Variant, ExecuteMsg, InstantiateMsg, QueryMsg, QueryKey
 };
 
 #[test]
 fn invite() {
     // GIVEN
     let mut deps = mock_dependencies();
-    let msg = InstantiateMsg {};
+    let msg = InstantiateMsg::default();
     let info = mock_info("host", &coins(2, "token"));
     instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -26,6 +26,11 @@ fn invite() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     );
     let execute_value: Response = res_x.unwrap();
@@ -43,12 +48,16 @@ fn invite() {
 
     // THEN
     let query_value: Vec<GameResponse> = from_binary(&res.unwrap()).unwrap();
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let game = &query_value[0].game;
     assert_eq!(
         query_value,
         vec![GameResponse {
             host: Addr::unchecked("host"),
             opponent: Addr::unchecked("opponent"),
             game: Game {
+                id: 1,
                 board: vec![
                     vec![None, None, Some(PlayerSymbol::X)],
                     vec![None, None, None],
@@ -57,6 +66,16 @@ fn invite() {
                 player_round: Some(PlayerSymbol::O),
                 host_symbol: PlayerSymbol::X,
                 prize: coins(2, "token"),
+                host_stake: coins(2, "token"),
+                opponent_stake: coins(2, "token"),
+                sponsor: None,
+                sponsorship: vec![],
+                practice: false,
+                opponent_first: false,
+                opening: Opening::Standard {},
+                time_control: None,
+                variant: Variant::Classic {},
+                positions: vec![],
                 status: Status::INVITED,
                 winner: None
             }
@@ -70,13 +89,14 @@ fn invite() {
             .add_attribute("y", "0")
             .add_attribute("host_symbol", "X")
             .add_attribute("opponent", "opponent")
+            .add_event(events::game_created(&host, &opponent, game, Coord { x: 2, y: 0 }))
     );
 }
 #[test]
 fn invite_when_already_in_progress_game() {
     // GIVEN
     let mut deps = mock_dependencies();
-    let msg = InstantiateMsg {};
+    let msg = InstantiateMsg::default();
     let info = mock_info("host", &coins(2, "token"));
     instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -89,6 +109,11 @@ fn invite_when_already_in_progress_game() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -100,6 +125,11 @@ fn invite_when_already_in_progress_game() {
             coord: Coord { x: 2, y: 2 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     );
     let execute_value: ContractError = res_x.unwrap_err();
@@ -118,7 +148,7 @@ fn invite_when_already_in_progress_game() {
 fn invite_when_wrong_coordinate() {
     // GIVEN
     let mut deps = mock_dependencies();
-    let msg = InstantiateMsg {};
+    let msg = InstantiateMsg::default();
     let info = mock_info("host", &coins(2, "token"));
     instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -131,6 +161,11 @@ fn invite_when_wrong_coordinate() {
             coord: Coord { x: 3, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     );
 
@@ -142,6 +177,11 @@ fn invite_when_wrong_coordinate() {
             coord: Coord { x: 0, y: 3 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     );
 
@@ -167,7 +207,7 @@ fn invite_when_wrong_coordinate() {
 fn invite_against_itself() {
     // GIVEN
     let mut deps = mock_dependencies();
-    let msg = InstantiateMsg {};
+    let msg = InstantiateMsg::default();
     let info = mock_info("host", &coins(2, "token"));
     instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -180,6 +220,11 @@ fn invite_against_itself() {
             coord: Coord { x: 0, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("host"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     );
 
@@ -18,3 +18,69 @@ mod play;
 
 #[cfg(test)]
 mod happy_paths;
+
+#[cfg(test)]
+mod evaluate;
+
+#[cfg(test)]
+mod house;
+
+#[cfg(test)]
+mod settle;
+
+#[cfg(test)]
+mod resign;
+
+#[cfg(test)]
+mod session_keys;
+
+#[cfg(test)]
+mod events;
+
+#[cfg(test)]
+mod game_tree;
+
+#[cfg(test)]
+mod properties;
+
+#[cfg(test)]
+mod integration;
+
+#[cfg(test)]
+mod errors;
+
+#[cfg(test)]
+mod error_messages;
+
+#[cfg(test)]
+mod claims;
+
+#[cfg(test)]
+mod pause;
+
+#[cfg(test)]
+mod solvency;
+
+#[cfg(test)]
+mod stakes;
+
+#[cfg(test)]
+mod sponsorship;
+
+#[cfg(test)]
+mod rematch;
+
+#[cfg(test)]
+mod opening;
+
+#[cfg(test)]
+mod clock;
+
+#[cfg(test)]
+mod gravity;
+
+#[cfg(test)]
+mod morris;
+
+#[cfg(test)]
+mod helpers;
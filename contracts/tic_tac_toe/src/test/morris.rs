@@ -0,0 +1,278 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, DepsMut, Response};
+
+use crate::contract::execute::execute;
+use crate::models::{
+    errors::ContractError,
+    state::{Coord, Game, Opening, PlayerSymbol, Status, Variant, MORRIS_MAX_MOVES},
+    ExecuteMsg,
+};
+
+use super::helpers::{query_game, setup};
+
+/**
+    Invites and accepts a MORRIS game, then places the pieces
+    left alternating the players, the host places first.
+*/
+fn place(mut deps: DepsMut, pieces: [(u8, u8); 6]) {
+    let coord = |(x, y): (u8, u8)| Coord { x, y };
+    execute(
+        deps.branch(),
+        mock_env(),
+        mock_info("host", &coins(2, "token")),
+        ExecuteMsg::Invite {
+            coord: coord(pieces[0]),
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: Some(Variant::Morris {}),
+            practice: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.branch(),
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord: coord(pieces[1]),
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+    for (index, piece) in pieces.iter().enumerate().skip(2) {
+        let as_host = index % 2 == 0;
+        execute(
+            deps.branch(),
+            mock_env(),
+            mock_info(if as_host { "host" } else { "opponent" }, &[]),
+            ExecuteMsg::Play {
+                as_host,
+                coord: coord(*piece),
+                opponent: String::from(if as_host { "opponent" } else { "host" }),
+            },
+        )
+        .unwrap();
+    }
+}
+
+fn move_piece(
+    deps: DepsMut,
+    as_host: bool,
+    from: (u8, u8),
+    to: (u8, u8),
+) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info(if as_host { "host" } else { "opponent" }, &[]),
+        ExecuteMsg::MovePiece {
+            as_host,
+            from: Coord {
+                x: from.0,
+                y: from.1,
+            },
+            to: Coord { x: to.0, y: to.1 },
+            opponent: String::from(if as_host { "opponent" } else { "host" }),
+        },
+    )
+}
+
+#[test]
+fn pieces_move_once_all_of_them_are_placed() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    // X . . / O O . / X O X after placing
+    place(
+        deps.as_mut(),
+        [(0, 0), (0, 1), (0, 2), (1, 1), (2, 2), (1, 2)],
+    );
+
+    // WHEN
+    let place_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Play {
+            as_host: true,
+            coord: Coord { x: 1, y: 0 },
+            opponent: String::from("opponent"),
+        },
+    );
+    let res = move_piece(deps.as_mut(), true, (0, 0), (1, 0)).unwrap();
+    let game = query_game(deps.as_ref()).game;
+
+    // THEN
+    assert_eq!(place_res.unwrap_err(), ContractError::PiecesPlaced {});
+    assert_eq!(res.attributes[1].value, "1");
+    assert_eq!(res.attributes[5].key, "from_x");
+    assert_eq!(game.board[0][0], None);
+    assert_eq!(game.board[0][1], Some(PlayerSymbol::X));
+    assert_eq!(game.positions.len(), 1);
+    assert_eq!(game.moves_count(), 7);
+    assert_eq!(game.player_round, Some(PlayerSymbol::O));
+}
+
+#[test]
+fn moving_a_piece_into_a_line_wins() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    // X O . / . X O / X O . after placing
+    place(
+        deps.as_mut(),
+        [(0, 0), (1, 0), (0, 2), (1, 2), (1, 1), (2, 1)],
+    );
+
+    // WHEN
+    move_piece(deps.as_mut(), true, (1, 1), (0, 1)).unwrap();
+    let game = query_game(deps.as_ref()).game;
+
+    // THEN
+    assert_eq!(game.status, Status::COMPLETED);
+    assert_eq!(game.winner, Some(PlayerSymbol::X));
+}
+
+#[test]
+fn third_repetition_draws_a_morris_game() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    place(
+        deps.as_mut(),
+        [(0, 0), (1, 0), (0, 2), (1, 2), (1, 1), (2, 1)],
+    );
+
+    // WHEN
+    for _ in 0..2 {
+        move_piece(deps.as_mut(), true, (1, 1), (2, 0)).unwrap();
+        move_piece(deps.as_mut(), false, (2, 1), (2, 2)).unwrap();
+        move_piece(deps.as_mut(), true, (2, 0), (1, 1)).unwrap();
+        move_piece(deps.as_mut(), false, (2, 2), (2, 1)).unwrap();
+    }
+    let repeated_twice = query_game(deps.as_ref()).game;
+    move_piece(deps.as_mut(), true, (1, 1), (2, 0)).unwrap();
+    let game = query_game(deps.as_ref()).game;
+
+    // THEN
+    assert_eq!(repeated_twice.status, Status::PLAYING);
+    assert_eq!(game.status, Status::COMPLETED);
+    assert_eq!(game.winner, None);
+    assert_eq!(game.positions.len(), 9);
+}
+
+#[test]
+fn pieces_only_move_to_adjacent_empty_cells() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    place(
+        deps.as_mut(),
+        [(0, 0), (1, 0), (0, 2), (1, 2), (2, 2), (0, 1)],
+    );
+
+    // WHEN
+    // X O . / O . . / X O X
+    let far_res = move_piece(deps.as_mut(), true, (0, 0), (2, 0));
+    let other_piece_res = move_piece(deps.as_mut(), true, (1, 0), (1, 1));
+    let occupied_res = move_piece(deps.as_mut(), true, (0, 0), (1, 0));
+    let corner_res = move_piece(deps.as_mut(), true, (2, 2), (1, 1));
+    let edge_res = move_piece(deps.as_mut(), false, (1, 0), (2, 0));
+
+    // THEN
+    assert_eq!(
+        far_res.unwrap_err(),
+        ContractError::InvalidPieceMove {
+            reason: String::from("pieces only move to an adjacent cell along a line"),
+        }
+    );
+    assert_eq!(
+        other_piece_res.unwrap_err(),
+        ContractError::InvalidPieceMove {
+            reason: String::from("the player in round has no piece to move there"),
+        }
+    );
+    assert_eq!(
+        occupied_res.unwrap_err(),
+        ContractError::CoordinateAlreadyPlayed {
+            coord: Coord { x: 1, y: 0 }
+        }
+    );
+    assert!(corner_res.is_ok());
+    assert!(edge_res.is_ok());
+}
+
+#[test]
+fn edge_cells_are_not_diagonally_adjacent() {
+    // GIVEN
+    let mut game = Game::new(
+        1,
+        Coord { x: 1, y: 0 },
+        vec![],
+        vec![],
+        PlayerSymbol::X,
+        Opening::Standard {},
+        None,
+        Variant::Morris {},
+    )
+    .unwrap();
+    for coord in [(0, 0), (0, 1), (2, 2), (1, 2), (2, 0)] {
+        game.play(Coord {
+            x: coord.0,
+            y: coord.1,
+        })
+        .unwrap()
+        .finish_round()
+        .unwrap();
+    }
+
+    // WHEN
+    // O X O / X . . / . X O with X in round
+    let edge_res = game.check_move_piece(Coord { x: 1, y: 0 }, Coord { x: 2, y: 1 });
+    let center_res = game.check_move_piece(Coord { x: 1, y: 0 }, Coord { x: 1, y: 1 });
+    let classic_res = Game {
+        variant: Variant::Classic {},
+        ..game.clone()
+    }
+    .check_move_piece(Coord { x: 1, y: 0 }, Coord { x: 1, y: 1 });
+
+    // THEN
+    assert!(matches!(
+        edge_res,
+        Err(ContractError::InvalidPieceMove { .. })
+    ));
+    assert!(center_res.is_ok());
+    assert!(matches!(
+        classic_res,
+        Err(ContractError::InvalidVariant { .. })
+    ));
+}
+
+#[test]
+fn move_cap_draws_a_morris_game() {
+    // GIVEN
+    let mut game = Game::new(
+        1,
+        Coord { x: 0, y: 0 },
+        vec![],
+        vec![],
+        PlayerSymbol::X,
+        Opening::Standard {},
+        None,
+        Variant::Morris {},
+    )
+    .unwrap();
+
+    // WHEN
+    game.positions = (1..MORRIS_MAX_MOVES as u32).collect();
+    let below_cap = game.is_draw();
+    game.positions.push(0);
+    let at_cap = game.is_draw();
+
+    // THEN
+    assert!(!below_cap);
+    assert!(at_cap);
+}
@@ -0,0 +1,208 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, DepsMut, Response};
+
+use crate::contract::execute::execute;
+use crate::models::{
+    errors::ContractError,
+    events,
+    state::{Coord, Opening, PlayerSymbol, Status},
+    ExecuteMsg, QueryKey,
+};
+
+use super::helpers::{query_game, setup};
+
+fn invite(deps: DepsMut, coord: Coord, opening: Opening) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("host", &coins(2, "token")),
+        ExecuteMsg::Invite {
+            coord,
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: Some(opening),
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+}
+
+fn swap(deps: DepsMut) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Swap {
+            host: String::from("host"),
+        },
+    )
+}
+
+#[test]
+fn pie_swap_gives_the_first_move_to_the_opponent() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    invite(deps.as_mut(), Coord { x: 1, y: 1 }, Opening::Pie {}).unwrap();
+
+    // WHEN
+    let res = swap(deps.as_mut()).unwrap();
+    let swapped = query_game(deps.as_ref());
+    let play_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Play {
+            as_host: true,
+            coord: Coord { x: 0, y: 0 },
+            opponent: String::from("opponent"),
+        },
+    );
+    let played = query_game(deps.as_ref());
+
+    // THEN
+    assert_eq!(res.attributes[0].value, "swap");
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == events::SIDES_SWAPPED));
+    assert_eq!(swapped.game.status, Status::PLAYING);
+    assert_eq!(swapped.game.host_symbol, PlayerSymbol::O);
+    assert_eq!(swapped.game.player_round, Some(PlayerSymbol::O));
+    assert_eq!(swapped.game.prize, coins(4, "token"));
+    assert!(play_res.is_ok());
+    assert_eq!(played.game.board[1][1], Some(PlayerSymbol::X));
+    assert_eq!(played.game.board[0][0], Some(PlayerSymbol::O));
+}
+
+#[test]
+fn swap_requires_the_pie_opening() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    invite(deps.as_mut(), Coord { x: 1, y: 1 }, Opening::Standard {}).unwrap();
+
+    // WHEN
+    let res = swap(deps.as_mut());
+
+    // THEN
+    assert_eq!(res.unwrap_err(), ContractError::SwapNotAllowed {});
+    assert_eq!(query_game(deps.as_ref()).game.status, Status::INVITED);
+}
+
+#[test]
+fn no_center_forbids_the_center_as_first_move() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+
+    // WHEN
+    let center_res = invite(deps.as_mut(), Coord { x: 1, y: 1 }, Opening::NoCenter {});
+    let corner_res = invite(deps.as_mut(), Coord { x: 0, y: 0 }, Opening::NoCenter {});
+    let accept_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    );
+
+    // THEN
+    assert_eq!(
+        center_res.unwrap_err(),
+        ContractError::ForbiddenOpening {
+            coord: Coord { x: 1, y: 1 },
+            opening: Opening::NoCenter {},
+        }
+    );
+    assert!(corner_res.is_ok());
+    assert!(accept_res.is_ok());
+}
+
+#[test]
+fn cell_forces_the_first_move() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    let opening = Opening::Cell {
+        coord: Coord { x: 2, y: 0 },
+    };
+
+    // WHEN
+    let other_res = invite(deps.as_mut(), Coord { x: 0, y: 0 }, opening);
+    let cell_res = invite(deps.as_mut(), Coord { x: 2, y: 0 }, opening);
+    let game = query_game(deps.as_ref());
+
+    // THEN
+    assert_eq!(
+        other_res.unwrap_err(),
+        ContractError::ForbiddenOpening {
+            coord: Coord { x: 0, y: 0 },
+            opening,
+        }
+    );
+    assert!(cell_res.is_ok());
+    assert_eq!(game.game.opening, opening);
+    assert_eq!(game.game.board[0][2], Some(PlayerSymbol::X));
+}
+
+#[test]
+fn opening_applies_to_the_first_move_of_a_rematch() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    invite(deps.as_mut(), Coord { x: 0, y: 0 }, Opening::NoCenter {}).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Resign {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let rematch = |deps: DepsMut, coord: Coord| {
+        execute(
+            deps,
+            mock_env(),
+            mock_info("opponent", &coins(2, "token")),
+            ExecuteMsg::Rematch {
+                game: QueryKey {
+                    host: String::from("host"),
+                    opponent: String::from("opponent"),
+                },
+                coord: Some(coord),
+                double_or_nothing: false,
+            },
+        )
+    };
+    let center_res = rematch(deps.as_mut(), Coord { x: 1, y: 1 });
+    let corner_res = rematch(deps.as_mut(), Coord { x: 2, y: 2 });
+
+    // THEN
+    assert_eq!(
+        center_res.unwrap_err(),
+        ContractError::ForbiddenOpening {
+            coord: Coord { x: 1, y: 1 },
+            opening: Opening::NoCenter {},
+        }
+    );
+    assert!(corner_res.is_ok());
+}
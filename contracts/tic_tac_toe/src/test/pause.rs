@@ -0,0 +1,223 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, from_binary, BankMsg, Deps, DepsMut, Response};
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::models::{
+    errors::ContractError,
+    events::GAME_VOIDED,
+    responses::PauseResponse,
+    state::{Coord, Pause, PlayerSymbol},
+    ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+
+fn setup(deps: DepsMut) {
+    instantiate(
+        deps,
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+}
+
+fn pause(deps: DepsMut, pause: Pause) {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdatePause { pause },
+    )
+    .unwrap();
+}
+
+fn query_pause(deps: Deps) -> Pause {
+    let res: PauseResponse =
+        from_binary(&query(deps, mock_env(), QueryMsg::Pause {}).unwrap()).unwrap();
+    res.pause
+}
+
+fn invite(deps: DepsMut) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("host", &coins(2, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+}
+
+fn accept(deps: DepsMut) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    )
+}
+
+fn emergency_withdraw(deps: DepsMut, as_host: bool) -> Result<Response, ContractError> {
+    let (sender, opponent) = if as_host {
+        ("host", "opponent")
+    } else {
+        ("opponent", "host")
+    };
+    execute(
+        deps,
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::EmergencyWithdraw {
+            as_host,
+            opponent: String::from(opponent),
+        },
+    )
+}
+
+#[test]
+fn only_the_admin_updates_the_pause() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+
+    // WHEN
+    let unauthorized_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::UpdatePause { pause: Pause::ALL },
+    );
+    let default_pause = query_pause(deps.as_ref());
+    pause(deps.as_mut(), Pause::MOVES);
+
+    // THEN
+    assert_eq!(
+        unauthorized_res.unwrap_err(),
+        ContractError::Unauthorized {}
+    );
+    assert_eq!(default_pause, Pause::NONE);
+    assert_eq!(query_pause(deps.as_ref()), Pause::MOVES);
+}
+
+#[test]
+fn pause_levels_block_their_messages() {
+    let cases = [
+        (Pause::NONE, false, false, false),
+        (Pause::INVITES, true, false, false),
+        (Pause::MOVES, false, true, false),
+        (Pause::ALL, true, true, true),
+    ];
+
+    for (level, blocks_invites, blocks_moves, blocks_reject) in cases {
+        // GIVEN
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+        invite(deps.as_mut()).unwrap();
+        pause(deps.as_mut(), level);
+
+        // WHEN
+        let accept_res = accept(deps.as_mut());
+        let resign_res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("host", &[]),
+            ExecuteMsg::Resign {
+                as_host: true,
+                opponent: String::from("opponent"),
+            },
+        );
+        let reject_res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("host", &[]),
+            ExecuteMsg::Reject {
+                as_host: true,
+                opponent: String::from("opponent"),
+            },
+        );
+
+        // THEN
+        let paused = |res: Result<Response, ContractError>| {
+            res.err() == Some(ContractError::Paused { pause: level })
+        };
+        assert_eq!(paused(accept_res), blocks_invites, "accept with {}", level);
+        assert_eq!(paused(resign_res), blocks_moves, "resign with {}", level);
+        assert_eq!(paused(reject_res), blocks_reject, "reject with {}", level);
+    }
+}
+
+#[test]
+fn emergency_withdraw_requires_a_pause() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    invite(deps.as_mut()).unwrap();
+
+    // WHEN
+    let res = emergency_withdraw(deps.as_mut(), true);
+
+    // THEN
+    assert_eq!(res.unwrap_err(), ContractError::NotPaused {});
+}
+
+#[test]
+fn emergency_withdraw_voids_the_game() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    invite(deps.as_mut()).unwrap();
+    accept(deps.as_mut()).unwrap();
+    pause(deps.as_mut(), Pause::INVITES);
+
+    // WHEN
+    let res = emergency_withdraw(deps.as_mut(), true).unwrap();
+    let second_res = emergency_withdraw(deps.as_mut(), false);
+    pause(deps.as_mut(), Pause::NONE);
+    let new_game_res = invite(deps.as_mut());
+
+    // THEN
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|sub_msg| sub_msg.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            BankMsg::Send {
+                to_address: String::from("host"),
+                amount: coins(2, "token"),
+            }
+            .into(),
+            BankMsg::Send {
+                to_address: String::from("opponent"),
+                amount: coins(2, "token"),
+            }
+            .into(),
+        ]
+    );
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == GAME_VOIDED)
+        .unwrap();
+    assert!(event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "status" && attr.value == "VOIDED"));
+    assert!(event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "player" && attr.value == "host"));
+    assert!(matches!(second_res, Err(ContractError::InvalidGame { .. })));
+    assert!(new_game_res.is_ok());
+}
@@ -5,8 +5,8 @@ use crate::contract::execute::execute;
 use crate::contract::instantiate::instantiate;
 use crate::contract::query::query;
 use crate::models::{
-    errors::ContractError, responses::GameResponse, state::Coord, state::Game, state::PlayerSymbol,
-    state::Status, ExecuteMsg, InstantiateMsg, QueryMsg, QueryKey
+    errors::ContractError, events, responses::GameResponse, state::Coord, state::Game, state::PlayerSymbol,
+    state::Opening, state::Status, state::Variant, ExecuteMsg, InstantiateMsg, QueryMsg, QueryKey
 };
 
 #[test]
@@ -19,7 +19,7 @@ fn play_round() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -30,6 +30,11 @@ fn play_round() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -71,6 +76,9 @@ fn play_round() {
 
     // THEN
     let query_value: Vec<GameResponse> = from_binary(&res.unwrap()).unwrap();
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let game = &query_value[0].game;
     assert_eq!(
         play_res,
         Response::new()
@@ -79,6 +87,7 @@ fn play_round() {
             .add_attribute("y", "2")
             .add_attribute("status", "PLAYING")
             .add_attribute("opponent", "opponent")
+            .add_event(events::move_played(&host, &opponent, game, &host, Coord { x: 2, y: 2 }))
     );
     assert_eq!(
         query_value,
@@ -86,6 +95,7 @@ fn play_round() {
             host: Addr::unchecked("host"),
             opponent: Addr::unchecked("opponent"),
             game: Game {
+                id: 1,
                 board: vec![
                     vec![None, None, Some(PlayerSymbol::X)],
                     vec![None, Some(PlayerSymbol::O), None],
@@ -94,6 +104,16 @@ fn play_round() {
                 player_round: Some(PlayerSymbol::O),
                 host_symbol: PlayerSymbol::X,
                 prize: coins(4, "token"),
+                host_stake: coins(2, "token"),
+                opponent_stake: coins(2, "token"),
+                sponsor: None,
+                sponsorship: vec![],
+                practice: false,
+                opponent_first: false,
+                opening: Opening::Standard {},
+                time_control: None,
+                variant: Variant::Classic {},
+                positions: vec![],
                 status: Status::PLAYING,
                 winner: None
             }
@@ -111,7 +131,7 @@ fn play_round_with_invalid_coords() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -122,6 +142,11 @@ fn play_round_with_invalid_coords() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -168,7 +193,7 @@ fn play_inexistent_round() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -179,6 +204,11 @@ fn play_inexistent_round() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -226,7 +256,7 @@ fn play_invited_round() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -237,6 +267,11 @@ fn play_invited_round() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -274,7 +309,7 @@ fn play_round_on_existent_symbol() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -285,6 +320,11 @@ fn play_round_on_existent_symbol() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -331,7 +371,7 @@ fn play_two_rounds_as_host() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -342,6 +382,11 @@ fn play_two_rounds_as_host() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -400,7 +445,7 @@ fn play_two_rounds_as_opponent() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -411,6 +456,11 @@ fn play_two_rounds_as_opponent() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -0,0 +1,287 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, BankMsg, CosmosMsg, Uint128};
+use proptest::prelude::*;
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::models::{
+    errors::ContractError,
+    state::{Coord, PlayerSymbol},
+    ExecuteMsg, InstantiateMsg,
+};
+
+const STAKE: u128 = 3;
+
+/**
+    Minimal model of the rules used to predict
+    which moves the contract must accept.
+*/
+struct Model {
+    board: [[Option<PlayerSymbol>; 3]; 3],
+    host_symbol: PlayerSymbol,
+    round: PlayerSymbol,
+    finished: bool,
+    winner: Option<PlayerSymbol>,
+}
+
+impl Model {
+    fn new(invite: Coord, accept: Coord, host_symbol: PlayerSymbol) -> Model {
+        let mut board = [[None; 3]; 3];
+        board[invite.y as usize][invite.x as usize] = Some(host_symbol);
+        board[accept.y as usize][accept.x as usize] = Some(host_symbol.other());
+
+        Model {
+            board,
+            host_symbol,
+            round: host_symbol,
+            finished: false,
+            winner: None,
+        }
+    }
+
+    fn is_legal(&self, as_host: bool, coord: Coord) -> bool {
+        let symbol = if as_host {
+            self.host_symbol
+        } else {
+            self.host_symbol.other()
+        };
+
+        !self.finished
+            && coord.x < 3
+            && coord.y < 3
+            && self.board[coord.y as usize][coord.x as usize].is_none()
+            && symbol == self.round
+    }
+
+    fn play(&mut self, coord: Coord) {
+        self.board[coord.y as usize][coord.x as usize] = Some(self.round);
+
+        let b = &self.board;
+        let s = Some(self.round);
+        let wins = (0..3).any(|i| b[i][0] == s && b[i][1] == s && b[i][2] == s)
+            || (0..3).any(|i| b[0][i] == s && b[1][i] == s && b[2][i] == s)
+            || (b[0][0] == s && b[1][1] == s && b[2][2] == s)
+            || (b[0][2] == s && b[1][1] == s && b[2][0] == s);
+
+        if wins {
+            self.finished = true;
+            self.winner = s;
+        } else if b.iter().flatten().all(|cell| cell.is_some()) {
+            self.finished = true;
+        } else {
+            self.round = self.round.other();
+        }
+    }
+}
+
+fn coord() -> impl Strategy<Value = Coord> {
+    (0u8..3, 0u8..3).prop_map(|(x, y)| Coord { x, y })
+}
+
+/**
+    Mostly valid coordinates with some outside of the board.
+*/
+fn any_coord() -> impl Strategy<Value = Coord> {
+    (0u8..4, 0u8..4).prop_map(|(x, y)| Coord { x, y })
+}
+
+fn symbol() -> impl Strategy<Value = PlayerSymbol> {
+    prop_oneof![Just(PlayerSymbol::X), Just(PlayerSymbol::O)]
+}
+
+fn paid(msgs: &[CosmosMsg], address: &str) -> u128 {
+    msgs.iter()
+        .map(|msg| match msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) if to_address == address => {
+                amount
+                    .iter()
+                    .map(|coin| coin.amount)
+                    .sum::<Uint128>()
+                    .u128()
+            }
+            _ => 0,
+        })
+        .sum()
+}
+
+proptest! {
+    #[test]
+    fn random_move_sequences(
+        invite in coord(),
+        accept in coord(),
+        host_symbol in symbol(),
+        moves in prop::collection::vec((any::<bool>(), any_coord()), 0..24),
+    ) {
+        // GIVEN
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("host", &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("host", &coins(STAKE, "token")),
+            ExecuteMsg::Invite {
+                coord: invite,
+                host_symbol,
+                opponent: String::from("opponent"),
+                opponent_stake: None,
+                opening: None,
+                time_control: None,
+                variant: None,
+                practice: None,
+            },
+        )
+        .unwrap();
+        let accept_res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("opponent", &coins(STAKE, "token")),
+            ExecuteMsg::Accept {
+                coord: accept,
+                host: String::from("host"),
+            },
+        );
+        if invite == accept {
+            prop_assert_eq!(
+                accept_res.unwrap_err(),
+                ContractError::CoordinateAlreadyPlayed { coord: accept }
+            );
+            return Ok(());
+        }
+        accept_res.unwrap();
+        let mut model = Model::new(invite, accept, host_symbol);
+        let mut msgs = vec![];
+
+        // WHEN
+        for (as_host, coord) in moves {
+            let (sender, opponent) = if as_host {
+                ("host", "opponent")
+            } else {
+                ("opponent", "host")
+            };
+            let legal = model.is_legal(as_host, coord);
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(sender, &[]),
+                ExecuteMsg::Play {
+                    as_host,
+                    coord,
+                    opponent: String::from(opponent),
+                },
+            );
+
+            // THEN
+            prop_assert_eq!(res.is_ok(), legal, "move {:?} by {}", coord, sender);
+            if let Ok(res) = res {
+                model.play(coord);
+                msgs.extend(res.messages.into_iter().map(|sub_msg| sub_msg.msg));
+            }
+        }
+
+        // THEN
+        let host_paid = paid(&msgs, "host");
+        let opponent_paid = paid(&msgs, "opponent");
+        if !model.finished {
+            prop_assert!(msgs.is_empty());
+            return Ok(());
+        }
+        prop_assert_eq!(host_paid + opponent_paid, 2 * STAKE);
+        match model.winner {
+            Some(symbol) if symbol == host_symbol => prop_assert_eq!(host_paid, 2 * STAKE),
+            Some(_) => prop_assert_eq!(opponent_paid, 2 * STAKE),
+            None => {
+                prop_assert_eq!(host_paid, STAKE);
+                prop_assert_eq!(opponent_paid, STAKE);
+            }
+        }
+    }
+
+    #[test]
+    fn no_move_after_completed(
+        first in any::<bool>(),
+        coord in any_coord(),
+    ) {
+        // GIVEN
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("host", &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        let moves = [
+            ("host", true, Coord { x: 0, y: 0 }),
+            ("opponent", false, Coord { x: 0, y: 1 }),
+            ("host", true, Coord { x: 1, y: 0 }),
+            ("opponent", false, Coord { x: 1, y: 1 }),
+            ("host", true, Coord { x: 2, y: 0 }),
+        ];
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("host", &coins(STAKE, "token")),
+            ExecuteMsg::Invite {
+                coord: moves[0].2,
+                host_symbol: PlayerSymbol::X,
+                opponent: String::from("opponent"),
+                opponent_stake: None,
+                opening: None,
+                time_control: None,
+                variant: None,
+                practice: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("opponent", &coins(STAKE, "token")),
+            ExecuteMsg::Accept {
+                coord: moves[1].2,
+                host: String::from("host"),
+            },
+        )
+        .unwrap();
+        for (sender, as_host, coord) in &moves[2..] {
+            let opponent = if *as_host { "opponent" } else { "host" };
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(sender, &[]),
+                ExecuteMsg::Play {
+                    as_host: *as_host,
+                    coord: *coord,
+                    opponent: String::from(opponent),
+                },
+            )
+            .unwrap();
+        }
+
+        // WHEN
+        let (sender, opponent) = if first {
+            ("host", "opponent")
+        } else {
+            ("opponent", "host")
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &[]),
+            ExecuteMsg::Play {
+                as_host: first,
+                coord,
+                opponent: String::from(opponent),
+            },
+        );
+
+        // THEN
+        prop_assert!(res.is_err());
+    }
+}
@@ -9,7 +9,7 @@ use crate::models::{InstantiateMsg, QueryMsg, QueryKey};
 fn query_by_invalid_host() {
     // GIVEN
     let mut deps = mock_dependencies();
-    let msg = InstantiateMsg {};
+    let msg = InstantiateMsg::default();
     let info = mock_info("host", &coins(2, "token"));
     instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -39,7 +39,7 @@ fn query_by_invalid_host() {
 fn query_by_invalid_opponent() {
     // GIVEN
     let mut deps = mock_dependencies();
-    let msg = InstantiateMsg {};
+    let msg = InstantiateMsg::default();
     let info = mock_info("host", &coins(2, "token"));
     instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -9,7 +9,7 @@ use crate::models::{responses::GameResponse, InstantiateMsg, QueryMsg, QueryKey}
 fn empty_games_with_both_users() {
     // GIVEN
     let mut deps = mock_dependencies();
-    let msg = InstantiateMsg {};
+    let msg = InstantiateMsg::default();
     let info = mock_info("host", &coins(2, "token"));
     instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -38,7 +38,7 @@ fn empty_games_with_both_users() {
 fn empty_games_with_no_users() {
     // GIVEN
     let mut deps = mock_dependencies();
-    let msg = InstantiateMsg {};
+    let msg = InstantiateMsg::default();
     let info = mock_info("host", &coins(2, "token"));
     instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
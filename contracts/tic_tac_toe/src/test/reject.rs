@@ -3,8 +3,9 @@ use cosmwasm_std::{coins, Addr, Response, SubMsg, CosmosMsg, BankMsg};
 
 use crate::contract::execute::execute;
 use crate::contract::instantiate::instantiate;
+use crate::GAMES;
 use crate::models::{
-    errors::ContractError, state::Coord, state::PlayerSymbol, ExecuteMsg, InstantiateMsg,
+    errors::ContractError, events, state::Coord, state::PlayerSymbol, ExecuteMsg, InstantiateMsg,
 };
 
 #[test]
@@ -17,7 +18,7 @@ fn reject_round() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     execute(
@@ -28,6 +29,11 @@ fn reject_round() {
             coord: Coord { x: 2, y: 0 },
             host_symbol: PlayerSymbol::X,
             opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
         },
     )
     .unwrap();
@@ -46,11 +52,20 @@ fn reject_round() {
     .unwrap();
 
     // THEN
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let game = GAMES.load(&deps.storage, (&host, &opponent)).unwrap();
+    let refund = BankMsg::Send {
+        to_address: String::from("host"),
+        amount: coins(2, "token"),
+    };
     assert_eq!(
         reject_res,
         Response::new()
             .add_attribute("method", "reject")
             .add_attribute("opponent", "host")
+            .add_event(events::game_rejected(&host, &opponent, &game))
+            .add_events(events::prizes_paid(&host, &opponent, &game, &[refund]))
             .add_submessage(SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
                 to_address: String::from("host"),
                 amount: coins(2, "token"),
@@ -69,7 +84,7 @@ fn reject_inexistent_existent_round() {
         deps.as_mut(),
         mock_env(),
         host_info.clone(),
-        InstantiateMsg {},
+        InstantiateMsg::default(),
     )
     .unwrap();
     
@@ -88,8 +103,8 @@ fn reject_inexistent_existent_round() {
 
     // THEN
     assert_eq!(reject_res, ContractError::GameNotFound {
-        host: Addr::unchecked("opponent"),
-        opponent: Addr::unchecked("host"),
+        host: Addr::unchecked("host"),
+        opponent: Addr::unchecked("opponent"),
     });
     
 }
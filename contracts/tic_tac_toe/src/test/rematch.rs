@@ -0,0 +1,350 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, from_binary, Addr, Coin, Deps, DepsMut, Response};
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::models::{
+    errors::ContractError,
+    responses::{ClaimableResponse, GameResponse},
+    state::{Coord, PayoutMode, PlayerSymbol, Status},
+    ExecuteMsg, InstantiateMsg, QueryKey, QueryMsg,
+};
+
+fn setup(deps: DepsMut, payout_mode: Option<PayoutMode>) {
+    instantiate(
+        deps,
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            payout_mode,
+            ..InstantiateMsg::default()
+        },
+    )
+    .unwrap();
+}
+
+fn key(host: &str, opponent: &str) -> QueryKey {
+    QueryKey {
+        host: String::from(host),
+        opponent: String::from(opponent),
+    }
+}
+
+/**
+    The host plays X and stakes 10 like the opponent,
+    then resigns so the opponent wins the prize of 20.
+*/
+fn play_opponent_wins(mut deps: DepsMut) {
+    execute(
+        deps.branch(),
+        mock_env(),
+        mock_info("host", &coins(10, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.branch(),
+        mock_env(),
+        mock_info("opponent", &coins(10, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+    execute(
+        deps,
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Resign {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+}
+
+fn rematch(
+    deps: DepsMut,
+    sender: &str,
+    funds: &[Coin],
+    coord: Option<Coord>,
+    double_or_nothing: bool,
+) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info(sender, funds),
+        ExecuteMsg::Rematch {
+            game: key("host", "opponent"),
+            coord,
+            double_or_nothing,
+        },
+    )
+}
+
+fn query_game(deps: Deps, host: &str, opponent: &str) -> GameResponse {
+    let res: Vec<GameResponse> = from_binary(
+        &query(
+            deps,
+            mock_env(),
+            QueryMsg::Games {
+                key: Some(key(host, opponent)),
+                status: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    res[0].clone()
+}
+
+fn claimable(deps: Deps, address: &str) -> Vec<Coin> {
+    let res: ClaimableResponse = from_binary(
+        &query(
+            deps,
+            mock_env(),
+            QueryMsg::Claimable {
+                address: String::from(address),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    res.amount
+}
+
+#[test]
+fn rematch_swaps_symbols_and_first_move() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), None);
+    play_opponent_wins(deps.as_mut());
+
+    // WHEN
+    rematch(deps.as_mut(), "host", &coins(10, "token"), None, false).unwrap();
+    let invited = query_game(deps.as_ref(), "host", "opponent");
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &coins(10, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Play {
+            as_host: true,
+            coord: Coord { x: 0, y: 0 },
+            opponent: String::from("opponent"),
+        },
+    );
+    let playing = query_game(deps.as_ref(), "host", "opponent");
+
+    // THEN
+    assert_eq!(invited.game.id, 2);
+    assert_eq!(invited.game.status, Status::INVITED);
+    assert_eq!(invited.game.host_symbol, PlayerSymbol::O);
+    assert_eq!(invited.game.player_round, Some(PlayerSymbol::X));
+    assert!(invited.game.opponent_first);
+    assert_eq!(invited.game.moves_count(), 0);
+    assert_eq!(invited.game.prize, coins(10, "token"));
+    assert!(res.is_ok());
+    assert_eq!(playing.game.board[1][1], Some(PlayerSymbol::X));
+    assert_eq!(playing.game.board[0][0], Some(PlayerSymbol::O));
+    assert_eq!(playing.game.prize, coins(20, "token"));
+}
+
+#[test]
+fn player_that_moved_second_hosts_with_the_first_move() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), None);
+    play_opponent_wins(deps.as_mut());
+
+    // WHEN
+    let missing_move_res = rematch(deps.as_mut(), "opponent", &coins(10, "token"), None, false);
+    let coord = Some(Coord { x: 2, y: 2 });
+    rematch(deps.as_mut(), "opponent", &coins(10, "token"), coord, false).unwrap();
+    let invited = query_game(deps.as_ref(), "opponent", "host");
+
+    // THEN
+    assert_eq!(
+        missing_move_res.unwrap_err(),
+        ContractError::InvalidRematch {
+            reason: String::from("the host plays the first move of the rematch"),
+        }
+    );
+    assert_eq!(invited.game.host_symbol, PlayerSymbol::X);
+    assert_eq!(invited.game.board[2][2], Some(PlayerSymbol::X));
+    assert_eq!(invited.game.player_round, Some(PlayerSymbol::O));
+    assert!(!invited.game.opponent_first);
+}
+
+#[test]
+fn double_or_nothing_holds_back_the_winnings() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), Some(PayoutMode::PULL));
+    play_opponent_wins(deps.as_mut());
+    let coord = Some(Coord { x: 2, y: 2 });
+
+    // WHEN
+    rematch(deps.as_mut(), "opponent", &[], coord, true).unwrap();
+    let invited = query_game(deps.as_ref(), "opponent", "host");
+    let held_back = claimable(deps.as_ref(), "opponent");
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Reject {
+            as_host: false,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+    let rejected = claimable(deps.as_ref(), "opponent");
+
+    // THEN
+    assert_eq!(invited.game.host_stake, coins(20, "token"));
+    assert_eq!(invited.game.opponent_stake, coins(20, "token"));
+    assert_eq!(invited.game.prize, coins(20, "token"));
+    assert_eq!(held_back, vec![]);
+    assert_eq!(rejected, coins(20, "token"));
+}
+
+#[test]
+fn double_or_nothing_requires_claimed_winnings_to_be_sent() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), Some(PayoutMode::PULL));
+    play_opponent_wins(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    let coord = Some(Coord { x: 2, y: 2 });
+
+    // WHEN
+    let unfunded_res = rematch(deps.as_mut(), "opponent", &coins(10, "token"), coord, true);
+    let res = rematch(deps.as_mut(), "opponent", &coins(20, "token"), coord, true);
+
+    // THEN
+    assert_eq!(
+        unfunded_res.unwrap_err(),
+        ContractError::InvalidReceivedFunds {}
+    );
+    assert!(res.is_ok());
+}
+
+#[test]
+fn double_or_nothing_requires_the_pull_payout_mode() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), Some(PayoutMode::PUSH));
+    play_opponent_wins(deps.as_mut());
+    let coord = Some(Coord { x: 2, y: 2 });
+
+    // WHEN
+    let res = rematch(deps.as_mut(), "opponent", &coins(20, "token"), coord, true);
+    let stakes_res = rematch(deps.as_mut(), "opponent", &coins(10, "token"), coord, false);
+
+    // THEN
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::InvalidRematch {
+            reason: String::from("winnings are only reinvested in the PULL payout mode"),
+        }
+    );
+    assert!(stakes_res.is_ok());
+}
+
+#[test]
+fn invalid_rematches() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut(), None);
+    play_opponent_wins(deps.as_mut());
+    let stake = coins(10, "token");
+
+    // WHEN
+    let stranger_res = rematch(deps.as_mut(), "stranger", &stake, None, false);
+    let loser_res = rematch(deps.as_mut(), "host", &coins(20, "token"), None, true);
+    let coord_res = rematch(
+        deps.as_mut(),
+        "host",
+        &stake,
+        Some(Coord { x: 0, y: 0 }),
+        false,
+    );
+    let funds_res = rematch(deps.as_mut(), "host", &coins(5, "token"), None, false);
+    let coord = Some(Coord { x: 2, y: 2 });
+    rematch(deps.as_mut(), "opponent", &stake, coord, false).unwrap();
+    let in_progress_res = rematch(deps.as_mut(), "host", &stake, None, false);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Reject {
+            as_host: false,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+    let stale_res = rematch(deps.as_mut(), "host", &stake, None, false);
+
+    // THEN
+    assert_eq!(
+        stranger_res.unwrap_err(),
+        ContractError::InvalidRematch {
+            reason: String::from("only the players of the game can request a rematch"),
+        }
+    );
+    assert_eq!(
+        loser_res.unwrap_err(),
+        ContractError::InvalidRematch {
+            reason: String::from("only the winner can reinvest its winnings"),
+        }
+    );
+    assert_eq!(
+        coord_res.unwrap_err(),
+        ContractError::InvalidRematch {
+            reason: String::from("the opponent plays the first move of the rematch"),
+        }
+    );
+    assert_eq!(
+        funds_res.unwrap_err(),
+        ContractError::InvalidReceivedFunds {}
+    );
+    assert_eq!(
+        in_progress_res.unwrap_err(),
+        ContractError::GameAlreadyInProgress {
+            host: Addr::unchecked("host"),
+            opponent: Addr::unchecked("opponent"),
+        }
+    );
+    assert_eq!(
+        stale_res.unwrap_err(),
+        ContractError::InvalidRematch {
+            reason: String::from("only the last game between the players can be rematched"),
+        }
+    );
+}
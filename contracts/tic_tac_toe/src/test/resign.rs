@@ -0,0 +1,270 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, Addr, BankMsg, Response};
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::GAMES;
+use crate::models::{events, state::Coord, state::PlayerSymbol, ExecuteMsg, InstantiateMsg};
+
+#[test]
+fn resign() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let host_info = mock_info("host", &coins(2, "token"));
+    let opponent_info = mock_info("opponent", &coins(2, "token"));
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 1, y: 1 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        opponent_info.clone(),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 0, y: 0 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let resign_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        opponent_info.clone(),
+        ExecuteMsg::Resign {
+            as_host: false,
+            opponent: String::from("host"),
+        },
+    )
+    .unwrap();
+
+    // THEN
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let game = GAMES.load(&deps.storage, (&host, &opponent)).unwrap();
+    let prize = vec![BankMsg::Send {
+        to_address: String::from("host"),
+        amount: coins(4, "token"),
+    }];
+    assert_eq!(
+        resign_res,
+        Response::new()
+            .add_attribute("method", "resign")
+            .add_attribute("status", "COMPLETED")
+            .add_attribute("opponent", "host")
+            .add_attribute("winner", "host")
+            .add_event(events::game_completed(&host, &opponent, &game))
+            .add_events(events::prizes_paid(&host, &opponent, &game, &prize))
+            .add_message(BankMsg::Send {
+                to_address: String::from("host"),
+                amount: coins(4, "token"),
+            })
+    );
+}
+
+#[test]
+fn draw_by_agreement() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let host_info = mock_info("host", &coins(2, "token"));
+    let opponent_info = mock_info("opponent", &coins(2, "token"));
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 1, y: 1 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        opponent_info.clone(),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 0, y: 0 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+    let offer_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::OfferDraw {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let draw_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        opponent_info.clone(),
+        ExecuteMsg::OfferDraw {
+            as_host: false,
+            opponent: String::from("host"),
+        },
+    )
+    .unwrap();
+
+    // THEN
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let game = GAMES.load(&deps.storage, (&host, &opponent)).unwrap();
+    let prize = vec![
+        BankMsg::Send {
+            to_address: String::from("host"),
+            amount: coins(2, "token"),
+        },
+        BankMsg::Send {
+            to_address: String::from("opponent"),
+            amount: coins(2, "token"),
+        },
+    ];
+    assert_eq!(
+        offer_res,
+        Response::new()
+            .add_attribute("method", "offer_draw")
+            .add_attribute("opponent", "opponent")
+            .add_attribute("status", "PLAYING")
+    );
+    assert_eq!(
+        draw_res,
+        Response::new()
+            .add_attribute("method", "offer_draw")
+            .add_attribute("opponent", "host")
+            .add_attribute("status", "COMPLETED")
+            .add_event(events::game_completed(&host, &opponent, &game))
+            .add_events(events::prizes_paid(&host, &opponent, &game, &prize))
+            .add_messages(vec![
+                BankMsg::Send {
+                    to_address: String::from("host"),
+                    amount: coins(2, "token"),
+                },
+                BankMsg::Send {
+                    to_address: String::from("opponent"),
+                    amount: coins(2, "token"),
+                },
+            ])
+    );
+}
+
+#[test]
+fn draw_offer_withdrawn_by_move() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let host_info = mock_info("host", &coins(2, "token"));
+    let opponent_info = mock_info("opponent", &coins(2, "token"));
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 1, y: 1 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        opponent_info.clone(),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 0, y: 0 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        opponent_info.clone(),
+        ExecuteMsg::OfferDraw {
+            as_host: false,
+            opponent: String::from("host"),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::Play {
+            as_host: true,
+            coord: Coord { x: 2, y: 2 },
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let offer_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        host_info.clone(),
+        ExecuteMsg::OfferDraw {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+
+    // THEN
+    assert_eq!(
+        offer_res,
+        Response::new()
+            .add_attribute("method", "offer_draw")
+            .add_attribute("opponent", "opponent")
+            .add_attribute("status", "PLAYING")
+    );
+}
@@ -0,0 +1,286 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, from_binary, Addr, BankMsg, DepsMut, Response};
+use cw_utils::Expiration;
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::GAMES;
+use crate::models::{
+    errors::ContractError,
+    events,
+    responses::SessionKeyResponse,
+    state::{Coord, PlayerSymbol, SessionScope},
+    DelegatedMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+
+fn start_game(mut deps: DepsMut) {
+    instantiate(
+        deps.branch(),
+        mock_env(),
+        mock_info("host", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.branch(),
+        mock_env(),
+        mock_info("host", &coins(2, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 1, y: 1 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.branch(),
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 0, y: 0 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+}
+
+fn authorize(deps: DepsMut, expires: Expiration, scope: SessionScope) {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::AuthorizeSessionKey {
+            session_key: String::from("session"),
+            expires,
+            scope,
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn play_with_session_key() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    start_game(deps.as_mut());
+    authorize(deps.as_mut(), Expiration::Never {}, SessionScope::Moves {});
+
+    // WHEN
+    let play_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("session", &[]),
+        ExecuteMsg::Delegate {
+            player: String::from("host"),
+            msg: DelegatedMsg::Play {
+                as_host: true,
+                coord: Coord { x: 2, y: 2 },
+                opponent: String::from("opponent"),
+            },
+        },
+    )
+    .unwrap();
+
+    // THEN
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let game = GAMES.load(&deps.storage, (&host, &opponent)).unwrap();
+    assert_eq!(
+        play_res,
+        Response::new()
+            .add_attribute("method", "play")
+            .add_attribute("x", "2")
+            .add_attribute("y", "2")
+            .add_attribute("status", "PLAYING")
+            .add_attribute("opponent", "opponent")
+            .add_event(events::move_played(&host, &opponent, &game, &host, Coord { x: 2, y: 2 }))
+    );
+}
+
+#[test]
+fn resign_with_moves_only_session_key() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    start_game(deps.as_mut());
+    authorize(deps.as_mut(), Expiration::Never {}, SessionScope::Moves {});
+
+    // WHEN
+    let resign_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("session", &[]),
+        ExecuteMsg::Delegate {
+            player: String::from("host"),
+            msg: DelegatedMsg::Resign {
+                as_host: true,
+                opponent: String::from("opponent"),
+            },
+        },
+    )
+    .unwrap_err();
+
+    // THEN
+    assert_eq!(
+        resign_res,
+        ContractError::SessionKeyUnauthorized {
+            session_key: Addr::unchecked("session"),
+            player: Addr::unchecked("host"),
+        }
+    );
+}
+
+#[test]
+fn resign_with_game_scoped_session_key() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    start_game(deps.as_mut());
+    authorize(
+        deps.as_mut(),
+        Expiration::Never {},
+        SessionScope::Games { ids: vec![1] },
+    );
+
+    // WHEN
+    let resign_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("session", &[]),
+        ExecuteMsg::Delegate {
+            player: String::from("host"),
+            msg: DelegatedMsg::Resign {
+                as_host: true,
+                opponent: String::from("opponent"),
+            },
+        },
+    )
+    .unwrap();
+
+    // THEN
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let game = GAMES.load(&deps.storage, (&host, &opponent)).unwrap();
+    let prize = vec![BankMsg::Send {
+        to_address: String::from("opponent"),
+        amount: coins(4, "token"),
+    }];
+    assert_eq!(
+        resign_res,
+        Response::new()
+            .add_attribute("method", "resign")
+            .add_attribute("status", "COMPLETED")
+            .add_attribute("opponent", "opponent")
+            .add_attribute("winner", "opponent")
+            .add_event(events::game_completed(&host, &opponent, &game))
+            .add_events(events::prizes_paid(&host, &opponent, &game, &prize))
+            .add_message(BankMsg::Send {
+                to_address: String::from("opponent"),
+                amount: coins(4, "token"),
+            })
+    );
+}
+
+#[test]
+fn play_with_expired_session_key() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    start_game(deps.as_mut());
+    authorize(
+        deps.as_mut(),
+        Expiration::AtHeight(mock_env().block.height),
+        SessionScope::Moves {},
+    );
+
+    // WHEN
+    let play_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("session", &[]),
+        ExecuteMsg::Delegate {
+            player: String::from("host"),
+            msg: DelegatedMsg::Play {
+                as_host: true,
+                coord: Coord { x: 2, y: 2 },
+                opponent: String::from("opponent"),
+            },
+        },
+    )
+    .unwrap_err();
+
+    // THEN
+    assert_eq!(
+        play_res,
+        ContractError::SessionKeyUnauthorized {
+            session_key: Addr::unchecked("session"),
+            player: Addr::unchecked("host"),
+        }
+    );
+}
+
+#[test]
+fn query_active_session_keys() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    start_game(deps.as_mut());
+    authorize(
+        deps.as_mut(),
+        Expiration::AtHeight(mock_env().block.height),
+        SessionScope::Moves {},
+    );
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::AuthorizeSessionKey {
+            session_key: String::from("laptop"),
+            expires: Expiration::Never {},
+            scope: SessionScope::Games { ids: vec![1] },
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::AuthorizeSessionKey {
+            session_key: String::from("phone"),
+            expires: Expiration::Never {},
+            scope: SessionScope::Moves {},
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::RevokeSessionKey {
+            session_key: String::from("phone"),
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SessionKeys {
+            player: String::from("host"),
+        },
+    );
+
+    // THEN
+    let value: Vec<SessionKeyResponse> = from_binary(&res.unwrap()).unwrap();
+    assert_eq!(
+        value,
+        vec![SessionKeyResponse {
+            session_key: Addr::unchecked("laptop"),
+            expires: Expiration::Never {},
+            scope: SessionScope::Games { ids: vec![1] },
+        }]
+    );
+}
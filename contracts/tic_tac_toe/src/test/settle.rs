@@ -0,0 +1,568 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, from_binary, Addr, BankMsg, Binary, DepsMut, Response};
+use k256::ecdsa::{signature::Signer, Signature, SigningKey, VerifyingKey};
+
+use crate::channel::board_hash;
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::models::{
+    errors::ContractError,
+    events,
+    responses::{ChannelResponse, ClockResponse, GameResponse},
+    state::{Coord, Game, PlayerSymbol, Status, TimeControl, Variant},
+    ExecuteMsg, InstantiateMsg, QueryKey, QueryMsg, SignedMove,
+};
+use crate::DRAW_OFFERS;
+
+use super::helpers::query_game;
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32]).unwrap()
+}
+
+fn register_players(mut deps: DepsMut) {
+    for (sender, seed) in [("host", 1), ("opponent", 2)] {
+        execute(
+            deps.branch(),
+            mock_env(),
+            mock_info(sender, &[]),
+            ExecuteMsg::RegisterPubkey {
+                pubkey: Binary::from(VerifyingKey::from(&signing_key(seed)).to_bytes().to_vec()),
+            },
+        )
+        .unwrap();
+    }
+}
+
+/**
+    Plays the move on the local copy of the game and signs
+    sha256(game_id || move_index || board_hash) as a client would.
+*/
+fn sign_move(game: &mut Game, key: &SigningKey, coord: Coord) -> SignedMove {
+    game.play(coord).unwrap();
+    let mut preimage = game.id.to_be_bytes().to_vec();
+    preimage.extend_from_slice(&game.moves_count().to_be_bytes());
+    preimage.extend_from_slice(&board_hash(game));
+    game.complete_round().unwrap();
+
+    let signature: Signature = key.sign(&preimage);
+    SignedMove {
+        from: None,
+        coord,
+        signature: Binary::from(signature.as_ref().to_vec()),
+    }
+}
+
+/**
+    Moves the piece of a MORRIS game on the local copy and signs
+    sha256(game_id || move_index || [from.x, from.y] || board_hash).
+*/
+fn sign_piece_move(game: &mut Game, key: &SigningKey, from: Coord, coord: Coord) -> SignedMove {
+    game.move_piece(from, coord).unwrap();
+    let mut preimage = game.id.to_be_bytes().to_vec();
+    preimage.extend_from_slice(&game.moves_count().to_be_bytes());
+    preimage.extend_from_slice(&[from.x, from.y]);
+    preimage.extend_from_slice(&board_hash(game));
+    game.complete_round().unwrap();
+
+    let signature: Signature = key.sign(&preimage);
+    SignedMove {
+        from: Some(from),
+        coord,
+        signature: Binary::from(signature.as_ref().to_vec()),
+    }
+}
+
+fn start_game(
+    mut deps: DepsMut,
+    time_control: Option<TimeControl>,
+    variant: Option<Variant>,
+) -> Game {
+    instantiate(
+        deps.branch(),
+        mock_env(),
+        mock_info("host", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.branch(),
+        mock_env(),
+        mock_info("host", &coins(2, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 1, y: 1 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control,
+            variant,
+            practice: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.branch(),
+        mock_env(),
+        mock_info("opponent", &coins(2, "token")),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 0, y: 0 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Games {
+            key: Some(QueryKey {
+                host: String::from("host"),
+                opponent: String::from("opponent"),
+            }),
+            status: None,
+        },
+    );
+    let value: Vec<GameResponse> = from_binary(&res.unwrap()).unwrap();
+    value[0].game.clone()
+}
+
+#[test]
+fn settle_completed_game() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let mut game = start_game(deps.as_mut(), None, None);
+    register_players(deps.as_mut());
+    let host = Addr::unchecked("host");
+    let opponent = Addr::unchecked("opponent");
+    let mut moves = vec![];
+    let mut move_events = vec![];
+    for (player, seed, coord) in [
+        (&host, 1, Coord { x: 1, y: 0 }),
+        (&opponent, 2, Coord { x: 2, y: 2 }),
+        (&host, 1, Coord { x: 1, y: 2 }),
+    ] {
+        moves.push(sign_move(&mut game, &signing_key(seed), coord));
+        move_events.push(events::move_played(&host, &opponent, &game, player, coord));
+    }
+
+    // WHEN
+    let settle_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &[]),
+        ExecuteMsg::SettleSignedMoves {
+            host: String::from("host"),
+            opponent: String::from("opponent"),
+            moves,
+        },
+    )
+    .unwrap();
+
+    // THEN
+    let prize = vec![BankMsg::Send {
+        to_address: String::from("host"),
+        amount: coins(4, "token"),
+    }];
+    assert_eq!(
+        settle_res,
+        Response::new()
+            .add_attribute("method", "settle_signed_moves")
+            .add_attribute("move_index", "5")
+            .add_attribute("status", "COMPLETED")
+            .add_events(move_events)
+            .add_attribute("winner", "host")
+            .add_event(events::game_completed(&host, &opponent, &game))
+            .add_events(events::prizes_paid(&host, &opponent, &game, &prize))
+            .add_messages(prize)
+    );
+}
+
+#[test]
+fn settle_move_signed_by_wrong_player() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let mut game = start_game(deps.as_mut(), None, None);
+    register_players(deps.as_mut());
+    let moves = vec![sign_move(&mut game, &signing_key(2), Coord { x: 1, y: 0 })];
+
+    // WHEN
+    let settle_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &[]),
+        ExecuteMsg::SettleSignedMoves {
+            host: String::from("host"),
+            opponent: String::from("opponent"),
+            moves,
+        },
+    )
+    .unwrap_err();
+
+    // THEN
+    assert_eq!(
+        settle_res,
+        ContractError::InvalidSignature { move_index: 3 }
+    );
+}
+
+#[test]
+fn settle_without_registered_pubkey() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let mut game = start_game(deps.as_mut(), None, None);
+    let moves = vec![sign_move(&mut game, &signing_key(1), Coord { x: 1, y: 0 })];
+
+    // WHEN
+    let settle_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &[]),
+        ExecuteMsg::SettleSignedMoves {
+            host: String::from("host"),
+            opponent: String::from("opponent"),
+            moves,
+        },
+    )
+    .unwrap_err();
+
+    // THEN
+    assert_eq!(
+        settle_res,
+        ContractError::PubkeyNotRegistered {
+            address: Addr::unchecked("host")
+        }
+    );
+}
+
+#[test]
+fn challenge_a_settled_state() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let mut game = start_game(deps.as_mut(), None, None);
+    register_players(deps.as_mut());
+    let first_move = sign_move(&mut game, &signing_key(1), Coord { x: 1, y: 0 });
+    let second_move = sign_move(&mut game, &signing_key(2), Coord { x: 1, y: 2 });
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::SettleSignedMoves {
+            host: String::from("host"),
+            opponent: String::from("opponent"),
+            moves: vec![first_move],
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let deadline = mock_env().block.time.plus_seconds(24 * 60 * 60);
+    let play_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &[]),
+        ExecuteMsg::Play {
+            as_host: false,
+            coord: Coord { x: 2, y: 2 },
+            opponent: String::from("host"),
+        },
+    )
+    .unwrap_err();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &[]),
+        ExecuteMsg::SettleSignedMoves {
+            host: String::from("host"),
+            opponent: String::from("opponent"),
+            moves: vec![second_move],
+        },
+    )
+    .unwrap();
+    let mut env = mock_env();
+    env.block.time = deadline.plus_seconds(24 * 60 * 60);
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("host", &[]),
+        ExecuteMsg::Play {
+            as_host: true,
+            coord: Coord { x: 2, y: 2 },
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+
+    // THEN
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Channel {
+            game: QueryKey {
+                host: String::from("host"),
+                opponent: String::from("opponent"),
+            },
+        },
+    );
+    let value: ChannelResponse = from_binary(&res.unwrap()).unwrap();
+    assert_eq!(play_res, ContractError::ChallengeWindowOpen { deadline });
+    assert_eq!(value.game_id, 1);
+    assert_eq!(value.move_index, 5);
+    assert_eq!(value.challenge_deadline, Some(deadline));
+}
+
+#[test]
+fn settled_moves_withdraw_the_draw_offer() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let mut game = start_game(deps.as_mut(), None, None);
+    register_players(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::OfferDraw {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+    let first_move = sign_move(&mut game, &signing_key(1), Coord { x: 1, y: 0 });
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::SettleSignedMoves {
+            host: String::from("host"),
+            opponent: String::from("opponent"),
+            moves: vec![first_move],
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &[]),
+        ExecuteMsg::OfferDraw {
+            as_host: false,
+            opponent: String::from("host"),
+        },
+    )
+    .unwrap();
+
+    // THEN
+    assert_eq!(query_game(deps.as_ref()).game.status, Status::PLAYING);
+    assert_eq!(
+        DRAW_OFFERS.load(deps.as_ref().storage, game.id).unwrap(),
+        PlayerSymbol::O
+    );
+}
+
+#[test]
+fn clocks_stop_during_the_challenge_window() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let mut game = start_game(
+        deps.as_mut(),
+        Some(TimeControl {
+            base: 300,
+            increment: 0,
+        }),
+        None,
+    );
+    register_players(deps.as_mut());
+    let first_move = sign_move(&mut game, &signing_key(1), Coord { x: 1, y: 0 });
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("host", &[]),
+        ExecuteMsg::SettleSignedMoves {
+            host: String::from("host"),
+            opponent: String::from("opponent"),
+            moves: vec![first_move],
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    env.block.time = env.block.time.plus_seconds(24 * 60 * 60 - 1);
+    let flag_res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("host", &[]),
+        ExecuteMsg::CallFlag {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    );
+    env.block.time = env.block.time.plus_seconds(101);
+    let clock: ClockResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Clock {
+                game: QueryKey {
+                    host: String::from("host"),
+                    opponent: String::from("opponent"),
+                },
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    // THEN
+    assert_eq!(
+        flag_res.unwrap_err(),
+        ContractError::FlagNotFallen { remaining: 300 }
+    );
+    assert_eq!(clock.host_remaining, 200);
+    assert_eq!(clock.opponent_remaining, 200);
+    assert_eq!(clock.running, Some(PlayerSymbol::O));
+}
+
+#[test]
+fn settling_does_not_escape_a_fallen_flag() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let mut game = start_game(
+        deps.as_mut(),
+        Some(TimeControl {
+            base: 300,
+            increment: 0,
+        }),
+        None,
+    );
+    register_players(deps.as_mut());
+    let first_move = sign_move(&mut game, &signing_key(1), Coord { x: 1, y: 0 });
+    let second_move = sign_move(&mut game, &signing_key(2), Coord { x: 2, y: 2 });
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(1000);
+
+    // WHEN
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("host", &[]),
+        ExecuteMsg::SettleSignedMoves {
+            host: String::from("host"),
+            opponent: String::from("opponent"),
+            moves: vec![first_move, second_move],
+        },
+    )
+    .unwrap();
+    let clock: ClockResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Clock {
+                game: QueryKey {
+                    host: String::from("host"),
+                    opponent: String::from("opponent"),
+                },
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let settled = query_game(deps.as_ref()).game;
+
+    // THEN
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == events::FLAG_FALLEN));
+    assert_eq!(settled.status, Status::COMPLETED);
+    assert_eq!(settled.winner, Some(PlayerSymbol::O));
+    assert_eq!(settled.board[0][1], None);
+    assert_eq!(clock.host_remaining, 0);
+    assert_eq!(clock.opponent_remaining, 300);
+}
+
+#[test]
+fn settle_moved_pieces_of_a_morris_game() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    let mut game = start_game(deps.as_mut(), None, Some(Variant::Morris {}));
+    register_players(deps.as_mut());
+    let mut moves = vec![];
+    for (seed, coord) in [
+        (1, Coord { x: 2, y: 0 }),
+        (2, Coord { x: 0, y: 2 }),
+        (1, Coord { x: 0, y: 1 }),
+        (2, Coord { x: 2, y: 1 }),
+    ] {
+        moves.push(sign_move(&mut game, &signing_key(seed), coord));
+    }
+    // O X . / X X O / O . . once the host moves 2,0 to 1,0
+    let mut unbound_game = game.clone();
+    moves.push(sign_piece_move(
+        &mut game,
+        &signing_key(1),
+        Coord { x: 2, y: 0 },
+        Coord { x: 1, y: 0 },
+    ));
+    // same move signed without the from bytes
+    unbound_game
+        .move_piece(Coord { x: 2, y: 0 }, Coord { x: 1, y: 0 })
+        .unwrap();
+    let mut preimage = unbound_game.id.to_be_bytes().to_vec();
+    preimage.extend_from_slice(&unbound_game.moves_count().to_be_bytes());
+    preimage.extend_from_slice(&board_hash(&unbound_game));
+    let signature: Signature = signing_key(1).sign(&preimage);
+    let unbound_move = SignedMove {
+        signature: Binary::from(signature.as_ref().to_vec()),
+        ..moves[4].clone()
+    };
+    let settle = |deps: DepsMut, moves: Vec<SignedMove>| {
+        execute(
+            deps,
+            mock_env(),
+            mock_info("opponent", &[]),
+            ExecuteMsg::SettleSignedMoves {
+                host: String::from("host"),
+                opponent: String::from("opponent"),
+                moves,
+            },
+        )
+    };
+
+    // WHEN
+    let mut unbound_moves = moves[..4].to_vec();
+    unbound_moves.push(unbound_move);
+    let unbound_res = settle(deps.as_mut(), unbound_moves);
+    let res = settle(deps.as_mut(), moves).unwrap();
+
+    // THEN
+    assert_eq!(
+        unbound_res.unwrap_err(),
+        ContractError::InvalidSignature { move_index: 7 }
+    );
+    assert_eq!(res.attributes[1].value, "7");
+    assert!(res
+        .events
+        .last()
+        .unwrap()
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "from_x" && attr.value == "2"));
+    let stored = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Games {
+            key: Some(QueryKey {
+                host: String::from("host"),
+                opponent: String::from("opponent"),
+            }),
+            status: None,
+        },
+    );
+    let stored: Vec<GameResponse> = from_binary(&stored.unwrap()).unwrap();
+    assert_eq!(stored[0].game, game);
+    assert_eq!(stored[0].game.status, Status::PLAYING);
+    assert_eq!(stored[0].game.positions.len(), 1);
+}
@@ -0,0 +1,58 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coins, from_binary};
+
+use crate::contract::execute::execute;
+use crate::contract::instantiate::instantiate;
+use crate::contract::query::query;
+use crate::models::{
+    errors::ContractError,
+    responses::SolvencyResponse,
+    state::{Coord, PlayerSymbol},
+    ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+
+#[test]
+fn missing_funds_are_a_deficit() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &coins(2, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+
+    // WHEN
+    let solvency: SolvencyResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Solvency {}).unwrap()).unwrap();
+    let sweep_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::SweepSurplus { recipient: None },
+    );
+
+    // THEN
+    assert_eq!(solvency.balance, vec![]);
+    assert_eq!(solvency.escrow, coins(2, "token"));
+    assert_eq!(solvency.deficit, coins(2, "token"));
+    assert!(!solvency.solvent);
+    assert_eq!(sweep_res.unwrap_err(), ContractError::NoSurplus {});
+}
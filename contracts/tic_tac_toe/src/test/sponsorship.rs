@@ -0,0 +1,427 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coins, from_binary, Addr, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Env, Response,
+};
+use cw_utils::Expiration;
+
+use crate::contract::execute::execute;
+use crate::contract::query::query;
+use crate::models::{
+    errors::ContractError,
+    events::{GAME_CREATED, PRIZE_PAID, SPONSORSHIP_RETURNED},
+    responses::SponsorshipResponse,
+    state::{Coord, Pause, PlayerSymbol},
+    ExecuteMsg, QueryKey, QueryMsg,
+};
+
+use super::helpers::{query_game, setup};
+
+fn key() -> QueryKey {
+    QueryKey {
+        host: String::from("host"),
+        opponent: String::from("opponent"),
+    }
+}
+
+/**
+    The sponsor puts 10 in the prize of the next game
+    between host and opponent, until block height + 10.
+*/
+fn sponsor(deps: DepsMut, opponent: &str) -> Result<Response, ContractError> {
+    let env = mock_env();
+    execute(
+        deps,
+        env.clone(),
+        mock_info("sponsor", &coins(10, "token")),
+        ExecuteMsg::Sponsor {
+            host: String::from("host"),
+            opponent: String::from(opponent),
+            expires: Expiration::AtHeight(env.block.height + 10),
+        },
+    )
+}
+
+fn reclaim(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        env,
+        mock_info("anyone", &[]),
+        ExecuteMsg::ReclaimSponsorship {
+            host: String::from("host"),
+            opponent: String::from("opponent"),
+        },
+    )
+}
+
+fn invite(deps: DepsMut) -> Response {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: None,
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: Some(true),
+        },
+    )
+    .unwrap()
+}
+
+fn accept(deps: DepsMut, env: Env) -> Response {
+    execute(
+        deps,
+        env,
+        mock_info("opponent", &[]),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    )
+    .unwrap()
+}
+
+fn offer_draw(deps: DepsMut, as_host: bool) -> Response {
+    let (sender, opponent) = if as_host {
+        ("host", "opponent")
+    } else {
+        ("opponent", "host")
+    };
+    execute(
+        deps,
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::OfferDraw {
+            as_host,
+            opponent: String::from(opponent),
+        },
+    )
+    .unwrap()
+}
+
+fn resign(deps: DepsMut) -> Response {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Resign {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap()
+}
+
+fn query_sponsorship(deps: Deps) -> SponsorshipResponse {
+    from_binary(&query(deps, mock_env(), QueryMsg::Sponsorship { game: key() }).unwrap()).unwrap()
+}
+
+fn bank_messages(res: &Response) -> Vec<CosmosMsg> {
+    res.messages
+        .iter()
+        .map(|sub_msg| sub_msg.msg.clone())
+        .collect()
+}
+
+fn send(to_address: &str, amount: u128) -> CosmosMsg {
+    BankMsg::Send {
+        to_address: String::from(to_address),
+        amount: coins(amount, "token"),
+    }
+    .into()
+}
+
+#[test]
+fn practice_game_sends_no_funds() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    let invite_res = invite(deps.as_mut());
+    let accept_res = accept(deps.as_mut(), mock_env());
+
+    // WHEN
+    let res = resign(deps.as_mut());
+
+    // THEN
+    assert!(res.messages.is_empty());
+    assert!(!res.events.iter().any(|event| event.ty == PRIZE_PAID));
+    assert!(query_game(deps.as_ref()).game.practice);
+    assert_eq!(query_game(deps.as_ref()).game.prize, vec![]);
+    for event in [invite_res, accept_res, res]
+        .iter()
+        .flat_map(|res| res.events.iter())
+    {
+        assert!(event
+            .attributes
+            .iter()
+            .all(|attribute| !attribute.value.is_empty()));
+    }
+}
+
+#[test]
+fn rematch_of_a_practice_game_is_a_practice_game() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    sponsor(deps.as_mut(), "opponent").unwrap();
+    invite(deps.as_mut());
+    accept(deps.as_mut(), mock_env());
+    resign(deps.as_mut());
+
+    // WHEN
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Rematch {
+            game: key(),
+            coord: None,
+            double_or_nothing: false,
+        },
+    )
+    .unwrap();
+
+    // THEN
+    let created = res
+        .events
+        .iter()
+        .find(|event| event.ty == GAME_CREATED)
+        .unwrap();
+    assert!(created
+        .attributes
+        .iter()
+        .any(|attribute| attribute.key == "practice" && attribute.value == "true"));
+    let rematch = query_game(deps.as_ref()).game;
+    assert!(rematch.practice);
+    assert_eq!(rematch.refunds(), (vec![], vec![]));
+}
+
+#[test]
+fn practice_game_requires_no_stakes() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    let invite = |deps: DepsMut, funds: &[Coin], opponent_stake: Option<Vec<Coin>>| {
+        execute(
+            deps,
+            mock_env(),
+            mock_info("host", funds),
+            ExecuteMsg::Invite {
+                coord: Coord { x: 0, y: 0 },
+                host_symbol: PlayerSymbol::X,
+                opponent: String::from("opponent"),
+                opponent_stake,
+                opening: None,
+                time_control: None,
+                variant: None,
+                practice: Some(true),
+            },
+        )
+    };
+
+    // WHEN
+    let funds_res = invite(deps.as_mut(), &coins(2, "token"), None);
+    let stake_res = invite(deps.as_mut(), &[], Some(coins(2, "token")));
+    let res = invite(deps.as_mut(), &[], None);
+
+    // THEN
+    assert_eq!(funds_res.unwrap_err(), ContractError::PracticeGameStake {});
+    assert_eq!(stake_res.unwrap_err(), ContractError::PracticeGameStake {});
+    assert!(res.is_ok());
+    assert_eq!(query_game(deps.as_ref()).game.opponent_stake, vec![]);
+}
+
+#[test]
+fn winner_takes_the_sponsorship() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    sponsor(deps.as_mut(), "opponent").unwrap();
+    invite(deps.as_mut());
+    accept(deps.as_mut(), mock_env());
+    let accepted = query_game(deps.as_ref()).game;
+
+    // WHEN
+    let res = resign(deps.as_mut());
+
+    // THEN
+    assert_eq!(accepted.sponsor, Some(Addr::unchecked("sponsor")));
+    assert_eq!(accepted.sponsorship, coins(10, "token"));
+    assert_eq!(accepted.prize, coins(10, "token"));
+    assert_eq!(query_sponsorship(deps.as_ref()).sponsorship, None);
+    assert_eq!(bank_messages(&res), vec![send("opponent", 10)]);
+}
+
+#[test]
+fn tie_returns_the_sponsorship() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    sponsor(deps.as_mut(), "opponent").unwrap();
+    invite(deps.as_mut());
+    accept(deps.as_mut(), mock_env());
+    offer_draw(deps.as_mut(), true);
+
+    // WHEN
+    let res = offer_draw(deps.as_mut(), false);
+
+    // THEN
+    assert_eq!(bank_messages(&res), vec![send("sponsor", 10)]);
+}
+
+#[test]
+fn reject_returns_the_sponsorship() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    sponsor(deps.as_mut(), "opponent").unwrap();
+    invite(deps.as_mut());
+
+    // WHEN
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("opponent", &[]),
+        ExecuteMsg::Reject {
+            as_host: false,
+            opponent: String::from("host"),
+        },
+    )
+    .unwrap();
+
+    // THEN
+    assert_eq!(bank_messages(&res), vec![send("sponsor", 10)]);
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == SPONSORSHIP_RETURNED));
+    assert_eq!(query_sponsorship(deps.as_ref()).sponsorship, None);
+}
+
+#[test]
+fn emergency_withdraw_returns_the_sponsorship() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    sponsor(deps.as_mut(), "opponent").unwrap();
+    invite(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdatePause { pause: Pause::ALL },
+    )
+    .unwrap();
+
+    // WHEN
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::EmergencyWithdraw {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+
+    // THEN
+    assert_eq!(bank_messages(&res), vec![send("sponsor", 10)]);
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == SPONSORSHIP_RETURNED));
+    assert_eq!(query_sponsorship(deps.as_ref()).sponsorship, None);
+}
+
+#[test]
+fn expired_sponsorship_is_reclaimed() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    sponsor(deps.as_mut(), "opponent").unwrap();
+    let mut expired_env = mock_env();
+    expired_env.block.height += 10;
+
+    // WHEN
+    let early_res = reclaim(deps.as_mut(), mock_env());
+    let res = reclaim(deps.as_mut(), expired_env.clone()).unwrap();
+    let again_res = reclaim(deps.as_mut(), expired_env);
+
+    // THEN
+    assert_eq!(
+        early_res.unwrap_err(),
+        ContractError::SponsorshipNotExpired {
+            expires: Expiration::AtHeight(mock_env().block.height + 10)
+        }
+    );
+    assert_eq!(bank_messages(&res), vec![send("sponsor", 10)]);
+    assert_eq!(
+        again_res.unwrap_err(),
+        ContractError::SponsorshipNotFound {
+            host: Addr::unchecked("host"),
+            opponent: Addr::unchecked("opponent"),
+        }
+    );
+}
+
+#[test]
+fn accept_returns_an_expired_sponsorship() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    sponsor(deps.as_mut(), "opponent").unwrap();
+    invite(deps.as_mut());
+    let mut expired_env = mock_env();
+    expired_env.block.height += 10;
+
+    // WHEN
+    let res = accept(deps.as_mut(), expired_env);
+
+    // THEN
+    assert_eq!(bank_messages(&res), vec![send("sponsor", 10)]);
+    assert_eq!(query_game(deps.as_ref()).game.sponsor, None);
+}
+
+#[test]
+fn invalid_sponsorships() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    sponsor(deps.as_mut(), "opponent").unwrap();
+
+    // WHEN
+    let exists_res = sponsor(deps.as_mut(), "opponent");
+    let house_res = sponsor(deps.as_mut(), MOCK_CONTRACT_ADDR);
+    let no_funds_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("sponsor", &[]),
+        ExecuteMsg::Sponsor {
+            host: String::from("host"),
+            opponent: String::from("other"),
+            expires: Expiration::Never {},
+        },
+    );
+
+    // THEN
+    assert_eq!(
+        exists_res.unwrap_err(),
+        ContractError::SponsorshipExists {
+            host: Addr::unchecked("host"),
+            opponent: Addr::unchecked("opponent"),
+        }
+    );
+    assert!(matches!(
+        house_res,
+        Err(ContractError::InvalidSponsorship { .. })
+    ));
+    assert!(matches!(
+        no_funds_res,
+        Err(ContractError::InvalidSponsorship { .. })
+    ));
+}
@@ -0,0 +1,186 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{coins, BankMsg, Coin, CosmosMsg, DepsMut, Response};
+
+use crate::contract::execute::execute;
+use crate::models::{
+    errors::ContractError,
+    state::{Coord, PlayerSymbol},
+    ExecuteMsg,
+};
+
+use super::helpers::{query_game, setup};
+
+/**
+    The host stakes 30 and offers odds to the
+    opponent, who only has to stake 10.
+*/
+fn invite_with_odds(deps: DepsMut) {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("host", &coins(30, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from("opponent"),
+            opponent_stake: Some(coins(10, "token")),
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    )
+    .unwrap();
+}
+
+fn accept(deps: DepsMut, funds: &[Coin]) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("opponent", funds),
+        ExecuteMsg::Accept {
+            coord: Coord { x: 1, y: 1 },
+            host: String::from("host"),
+        },
+    )
+}
+
+fn offer_draw(deps: DepsMut, as_host: bool) -> Response {
+    let (sender, opponent) = if as_host {
+        ("host", "opponent")
+    } else {
+        ("opponent", "host")
+    };
+    execute(
+        deps,
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::OfferDraw {
+            as_host,
+            opponent: String::from(opponent),
+        },
+    )
+    .unwrap()
+}
+
+fn bank_messages(res: &Response) -> Vec<CosmosMsg> {
+    res.messages
+        .iter()
+        .map(|sub_msg| sub_msg.msg.clone())
+        .collect()
+}
+
+#[test]
+fn accept_requires_the_opponent_stake() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    invite_with_odds(deps.as_mut());
+
+    // WHEN
+    let matching_res = accept(deps.as_mut(), &coins(30, "token"));
+    let invited = query_game(deps.as_ref());
+    let res = accept(deps.as_mut(), &coins(10, "token"));
+    let accepted = query_game(deps.as_ref());
+
+    // THEN
+    assert_eq!(
+        matching_res.unwrap_err(),
+        ContractError::InvalidReceivedFunds {}
+    );
+    assert!(res.is_ok());
+    assert_eq!(invited.game.host_stake, coins(30, "token"));
+    assert_eq!(invited.game.opponent_stake, coins(10, "token"));
+    assert_eq!(invited.game.prize, coins(30, "token"));
+    assert_eq!(accepted.game.host_stake, coins(30, "token"));
+    assert_eq!(accepted.game.opponent_stake, coins(10, "token"));
+    assert_eq!(accepted.game.prize, coins(40, "token"));
+}
+
+#[test]
+fn winner_takes_the_whole_pot() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    invite_with_odds(deps.as_mut());
+    accept(deps.as_mut(), &coins(10, "token")).unwrap();
+
+    // WHEN
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &[]),
+        ExecuteMsg::Resign {
+            as_host: true,
+            opponent: String::from("opponent"),
+        },
+    )
+    .unwrap();
+
+    // THEN
+    assert_eq!(
+        bank_messages(&res),
+        vec![BankMsg::Send {
+            to_address: String::from("opponent"),
+            amount: coins(40, "token"),
+        }
+        .into()]
+    );
+}
+
+#[test]
+fn draw_refunds_each_stake() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+    invite_with_odds(deps.as_mut());
+    accept(deps.as_mut(), &coins(10, "token")).unwrap();
+    offer_draw(deps.as_mut(), true);
+
+    // WHEN
+    let res = offer_draw(deps.as_mut(), false);
+
+    // THEN
+    assert_eq!(
+        bank_messages(&res),
+        vec![
+            BankMsg::Send {
+                to_address: String::from("host"),
+                amount: coins(30, "token"),
+            }
+            .into(),
+            BankMsg::Send {
+                to_address: String::from("opponent"),
+                amount: coins(10, "token"),
+            }
+            .into(),
+        ]
+    );
+}
+
+#[test]
+fn house_only_plays_equal_stakes() {
+    // GIVEN
+    let mut deps = mock_dependencies();
+    setup(deps.as_mut());
+
+    // WHEN
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("host", &coins(30, "token")),
+        ExecuteMsg::Invite {
+            coord: Coord { x: 0, y: 0 },
+            host_symbol: PlayerSymbol::X,
+            opponent: String::from(MOCK_CONTRACT_ADDR),
+            opponent_stake: Some(coins(10, "token")),
+            opening: None,
+            time_control: None,
+            variant: None,
+            practice: None,
+        },
+    );
+
+    // THEN
+    assert_eq!(res.unwrap_err(), ContractError::HouseUnevenStakes {});
+}
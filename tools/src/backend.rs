@@ -0,0 +1,125 @@
+use std::fs::{File, OpenOptions};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use cosmwasm_std::{Coin, Event};
+use cw_multi_test::{BankSudo, SudoMsg};
+use tic_tac_toe::models::{
+    responses::GameResponse, state::Status, ExecuteMsg, InstantiateMsg, QueryKey, QueryMsg,
+};
+
+use crate::local::LocalChain;
+use crate::log::{read_log, write_log};
+
+/**
+    Chain where the contract runs, the clients only depend
+    on this trait so they can be tested against a local app.
+*/
+pub trait Backend {
+    /**
+        Executes the message signed by the sender and
+        returns the events emitted by the transaction.
+    */
+    fn execute(
+        &mut self,
+        sender: &str,
+        msg: ExecuteMsg,
+        funds: &[Coin],
+    ) -> anyhow::Result<Vec<Event>>;
+
+    fn games(
+        &self,
+        key: Option<QueryKey>,
+        status: Option<Status>,
+    ) -> anyhow::Result<Vec<GameResponse>>;
+}
+
+impl Backend for LocalChain {
+    fn execute(
+        &mut self,
+        sender: &str,
+        msg: ExecuteMsg,
+        funds: &[Coin],
+    ) -> anyhow::Result<Vec<Event>> {
+        Ok(LocalChain::execute(self, sender, msg, funds)?.events)
+    }
+
+    fn games(
+        &self,
+        key: Option<QueryKey>,
+        status: Option<Status>,
+    ) -> anyhow::Result<Vec<GameResponse>> {
+        self.query(&QueryMsg::Games { key, status })
+    }
+}
+
+/**
+    Local chain persisted as a JSONL log (the same format
+    read by the indexer) that is replayed when opened.
+    Players do not need balances: the funds of every
+    message are minted to the sender before executing it.
+*/
+pub struct Playground {
+    chain: LocalChain,
+    path: PathBuf,
+}
+
+impl Playground {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Playground> {
+        let mut playground = Playground {
+            chain: LocalChain::new(&[], InstantiateMsg::default())?,
+            path: path.as_ref().to_path_buf(),
+        };
+
+        if playground.path.exists() {
+            let entries = read_log(BufReader::new(File::open(&playground.path)?))?;
+            for entry in entries {
+                playground.mint(&entry.sender, &entry.funds)?;
+                playground
+                    .chain
+                    .execute(&entry.sender, entry.msg, &entry.funds)?;
+            }
+        }
+
+        Ok(playground)
+    }
+
+    fn mint(&mut self, address: &str, amount: &[Coin]) -> anyhow::Result<()> {
+        if !amount.is_empty() {
+            self.chain.app.sudo(SudoMsg::Bank(BankSudo::Mint {
+                to_address: address.to_string(),
+                amount: amount.to_vec(),
+            }))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Backend for Playground {
+    fn execute(
+        &mut self,
+        sender: &str,
+        msg: ExecuteMsg,
+        funds: &[Coin],
+    ) -> anyhow::Result<Vec<Event>> {
+        self.mint(sender, funds)?;
+        let events = Backend::execute(&mut self.chain, sender, msg, funds)?;
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        write_log(file, &self.chain.log[self.chain.log.len() - 1..])?;
+
+        Ok(events)
+    }
+
+    fn games(
+        &self,
+        key: Option<QueryKey>,
+        status: Option<Status>,
+    ) -> anyhow::Result<Vec<GameResponse>> {
+        self.chain.games(key, status)
+    }
+}
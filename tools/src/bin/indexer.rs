@@ -0,0 +1,65 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use tic_tac_toe_tools::indexer::Indexer;
+use tic_tac_toe_tools::log::read_log;
+
+/**
+    Rebuilds the games of the contract from a JSONL
+    log of transactions into a SQLite database.
+*/
+#[derive(Parser)]
+#[command(name = "indexer")]
+struct Args {
+    /// SQLite database, created when it does not exist
+    #[arg(long, default_value = "tic_tac_toe.sqlite")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index every entry of a JSONL log
+    Ingest { log: PathBuf },
+    /// Print the indexed game with its moves
+    Game { id: u64 },
+    /// Print the number of games per status, moves, payouts and winners
+    Stats,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let mut indexer = Indexer::open(&args.db)?;
+
+    match args.command {
+        Command::Ingest { log } => {
+            let entries = read_log(BufReader::new(File::open(log)?))?;
+            for entry in &entries {
+                indexer.ingest(entry)?;
+            }
+            println!("indexed {} entries", entries.len());
+        }
+        Command::Game { id } => {
+            let game = indexer
+                .game(id)?
+                .ok_or_else(|| anyhow::anyhow!("game {} not found", id))?;
+            let moves = indexer.moves(id)?;
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "game": game,
+                    "moves": moves,
+                }))?
+            );
+        }
+        Command::Stats => {
+            println!("{}", serde_json::to_string_pretty(&indexer.stats()?)?);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::Parser;
+use tic_tac_toe_tools::scenario::Scenario;
+
+/**
+    Runs YAML scenarios against a local chain
+    and reports the ones that do not pass.
+*/
+#[derive(Parser)]
+#[command(name = "scenario")]
+struct Args {
+    /// Scenario files
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+}
+
+fn run_file(file: &Path) -> anyhow::Result<String> {
+    let scenario = Scenario::load(file)?;
+    scenario.run()?;
+
+    Ok(scenario.name)
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let mut failed = 0;
+
+    for file in &args.files {
+        match run_file(file) {
+            Ok(name) => println!("ok\t{}\t{}", file.display(), name),
+            Err(err) => {
+                failed += 1;
+                println!("FAILED\t{}\t{}", file.display(), err);
+            }
+        }
+    }
+
+    println!("{} passed, {} failed", args.files.len() - failed, failed);
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,12 @@
+use clap::Parser;
+use tic_tac_toe_tools::backend::Playground;
+use tic_tac_toe_tools::cli::{run, Cli};
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let mut backend = Playground::open(&cli.local)?;
+
+    print!("{}", run(&mut backend, cli.sender.as_deref(), cli.command)?);
+
+    Ok(())
+}
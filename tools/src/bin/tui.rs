@@ -0,0 +1,270 @@
+use std::fs::File;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::Parser;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Alignment, Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use tic_tac_toe::models::state::{Coord, Difficulty, PlayerSymbol, Variant};
+use tic_tac_toe_tools::cli::parse_variant;
+use tic_tac_toe_tools::hotseat::HotSeat;
+
+/**
+    Play on one machine against another person
+    or against the house bot of the contract.
+*/
+#[derive(Parser)]
+#[command(name = "tui")]
+struct Args {
+    /// Play against the bot with the given difficulty (random, medium or perfect)
+    #[arg(long, value_parser = parse_difficulty)]
+    bot: Option<Difficulty>,
+
+    /// Let the bot play X and move first
+    #[arg(long, requires = "bot")]
+    bot_first: bool,
+
+    /// CLASSIC, MORRIS, GRAVITY for Connect Four on 7x6 or GRAVITY=columns,rows,
+    /// the bot only plays CLASSIC
+    #[arg(long, value_parser = parse_variant, conflicts_with = "bot")]
+    variant: Option<Variant>,
+}
+
+fn parse_difficulty(value: &str) -> Result<Difficulty, String> {
+    match value.to_uppercase().as_str() {
+        "RANDOM" => Ok(Difficulty::RANDOM),
+        "MEDIUM" => Ok(Difficulty::MEDIUM),
+        "PERFECT" => Ok(Difficulty::PERFECT),
+        _ => Err(String::from("expected random, medium or perfect")),
+    }
+}
+
+struct App {
+    args: Args,
+    hot_seat: HotSeat,
+    cursor: Coord,
+    /// Piece picked up to be moved in a MORRIS game
+    selected: Option<Coord>,
+    message: String,
+}
+
+impl App {
+    fn new_game(&mut self) {
+        self.hot_seat = match self.args.bot {
+            Some(difficulty) if self.args.bot_first => {
+                HotSeat::against_bot(difficulty, PlayerSymbol::X)
+            }
+            Some(difficulty) => HotSeat::against_bot(difficulty, PlayerSymbol::O),
+            None => match self.args.variant.map(HotSeat::with_variant) {
+                Some(Ok(hot_seat)) => hot_seat,
+                Some(Err(err)) => {
+                    self.message = err.to_string();
+                    return;
+                }
+                None => HotSeat::new(),
+            },
+        };
+        self.selected = None;
+        self.message = String::new();
+    }
+
+    fn export(&mut self) -> anyhow::Result<String> {
+        let export = self
+            .hot_seat
+            .export()
+            .ok_or_else(|| anyhow::anyhow!("Only finished games can be exported"))?;
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let path = format!("tic_tac_toe-{}.json", secs);
+        serde_json::to_writer_pretty(File::create(&path)?, &export)?;
+
+        Ok(format!("Exported to {}", path))
+    }
+
+    /**
+        Returns false when the user quits.
+    */
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        let board = &self.hot_seat.game().board;
+        let (last_x, last_y) = (board[0].len() as u8 - 1, board.len() as u8 - 1);
+        let cursor = &mut self.cursor;
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Left | KeyCode::Char('h') => cursor.x = cursor.x.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => cursor.x = (cursor.x + 1).min(last_x),
+            KeyCode::Up | KeyCode::Char('k') => cursor.y = cursor.y.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => cursor.y = (cursor.y + 1).min(last_y),
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let game = self.hot_seat.game();
+                let own_piece = game.player_round.is_some()
+                    && game.board[cursor.y as usize][cursor.x as usize] == game.player_round;
+                let res = match (self.selected.take(), game.variant) {
+                    (Some(from), _) if from == *cursor => Ok(()),
+                    (Some(from), _) => self.hot_seat.move_piece(from, *cursor),
+                    (None, Variant::Morris {}) if own_piece => {
+                        self.selected = Some(*cursor);
+                        Ok(())
+                    }
+                    (None, Variant::Gravity { .. }) => self.hot_seat.drop_piece(cursor.x),
+                    (None, _) => self.hot_seat.play(*cursor),
+                };
+                self.message = match res {
+                    Ok(()) => String::new(),
+                    Err(err) => err.to_string(),
+                }
+            }
+            KeyCode::Char('u') => {
+                self.selected = None;
+                self.message = match self.hot_seat.undo() {
+                    Ok(()) => String::new(),
+                    Err(err) => err.to_string(),
+                }
+            }
+            KeyCode::Char('e') => {
+                self.message = self.export().unwrap_or_else(|err| err.to_string());
+            }
+            KeyCode::Char('n') => self.new_game(),
+            _ => {}
+        }
+
+        true
+    }
+
+    fn status(&self) -> String {
+        let game = self.hot_seat.game();
+        match (self.hot_seat.is_finished(), game.winner, game.player_round) {
+            (true, Some(winner), _) => format!("{} won", winner),
+            (true, None, _) => String::from("Tie"),
+            (false, _, Some(symbol)) => format!("{} to play", symbol),
+            (false, _, None) => String::new(),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let game = self.hot_seat.game();
+        let columns = game.board[0].len();
+        let [board_area, side] = Layout::horizontal([
+            Constraint::Length(columns.max(4) as u16 * 6 + 1),
+            Constraint::Min(0),
+        ])
+        .areas(main);
+        let [status_area, history_area] =
+            Layout::vertical([Constraint::Length(4), Constraint::Min(0)]).areas(side);
+
+        let mut lines = vec![];
+        for (y, row) in game.board.iter().enumerate() {
+            let mut spans = vec![];
+            for (x, cell) in row.iter().enumerate() {
+                let text = match cell {
+                    Some(symbol) => format!("  {}  ", symbol),
+                    None => String::from("     "),
+                };
+                let selected = match game.variant {
+                    Variant::Gravity { .. } => self.cursor.x as usize == x,
+                    _ => self.cursor.x as usize == x && self.cursor.y as usize == y,
+                };
+                let mut style = if selected {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                };
+                if self.selected
+                    == Some(Coord {
+                        x: x as u8,
+                        y: y as u8,
+                    })
+                {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                spans.push(Span::styled(text, style));
+                if x < row.len() - 1 {
+                    spans.push(Span::raw("|"));
+                }
+            }
+            lines.push(Line::from(spans));
+            if y < game.board.len() - 1 {
+                lines.push(Line::from(vec!["-----"; columns].join("+")));
+            }
+        }
+        frame.render_widget(
+            Paragraph::new(lines)
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).title("Tic Tac Toe")),
+            board_area,
+        );
+
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(self.status()),
+                Line::from(self.message.as_str()),
+            ])
+            .block(Block::default().borders(Borders::ALL).title("Status")),
+            status_area,
+        );
+
+        let history: Vec<ListItem> = self
+            .hot_seat
+            .history()
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let from = entry
+                    .from
+                    .map(|from| format!("x: {}, y: {} -> ", from.x, from.y))
+                    .unwrap_or_default();
+                ListItem::new(format!(
+                    "{}. {} {}x: {}, y: {}",
+                    index + 1,
+                    entry.symbol,
+                    from,
+                    entry.coord.x,
+                    entry.coord.y
+                ))
+            })
+            .collect();
+        frame.render_widget(
+            List::new(history).block(Block::default().borders(Borders::ALL).title("History")),
+            history_area,
+        );
+
+        frame.render_widget(
+            Paragraph::new("arrows/hjkl move  enter play or pick up a piece  u undo  e export  n new game  q quit"),
+            help,
+        );
+    }
+}
+
+fn run(terminal: &mut DefaultTerminal, mut app: App) -> anyhow::Result<()> {
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !app.handle_key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut app = App {
+        args: Args::parse(),
+        hot_seat: HotSeat::new(),
+        cursor: Coord { x: 1, y: 1 },
+        selected: None,
+        message: String::new(),
+    };
+    app.new_game();
+
+    let mut terminal = ratatui::init();
+    let res = run(&mut terminal, app);
+    ratatui::restore();
+
+    res
+}
@@ -0,0 +1,74 @@
+use tic_tac_toe::models::{
+    events::format_coins,
+    responses::GameResponse,
+    state::{Game, Status},
+};
+
+/**
+    ASCII board where the empty cells show their
+    coordinates (x,y), so the players know what to type:
+
+      X  | 1,0 |  O
+    -----+-----+-----
+     0,1 |  X  | 2,1
+    ...
+*/
+pub fn render_board(game: &Game) -> String {
+    let width = game.board.first().map(|row| row.len()).unwrap_or_default();
+    let separator = vec!["-----"; width].join("+");
+
+    game.board
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, cell)| match cell {
+                    Some(symbol) => format!("  {}  ", symbol),
+                    None => format!(" {},{} ", x, y),
+                })
+                .collect::<Vec<String>>()
+                .join("|")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<String>>()
+        .join(&format!("\n{}\n", separator))
+}
+
+/**
+    Players, status, prize, stakes and board of the game.
+*/
+pub fn render_game(res: &GameResponse) -> String {
+    let game = &res.game;
+    let status = match game.status {
+        Status::COMPLETED => match game.winner_address(&res.host, &res.opponent) {
+            Some(winner) => format!("COMPLETED, {} won", winner),
+            None => String::from("COMPLETED, tie"),
+        },
+        Status::PLAYING | Status::INVITED => match game.player_round {
+            Some(symbol) if symbol == game.host_symbol => {
+                format!("{}, {} ({}) to play", game.status, res.host, symbol)
+            }
+            Some(symbol) => format!("{}, {} ({}) to play", game.status, res.opponent, symbol),
+            None => game.status.to_string(),
+        },
+        Status::REJECTED | Status::VOIDED => game.status.to_string(),
+    };
+
+    format!(
+        "Game {}: {} ({}) vs {} ({})\nStatus: {}\nPrize: {}\nStakes: {} ({}), {} ({})\n\n{}\n",
+        game.id,
+        res.host,
+        game.host_symbol,
+        res.opponent,
+        game.host_symbol.other(),
+        status,
+        format_coins(&game.prize),
+        format_coins(&game.host_stake),
+        res.host,
+        format_coins(&game.opponent_stake),
+        res.opponent,
+        render_board(game),
+    )
+}
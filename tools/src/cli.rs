@@ -0,0 +1,465 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use cosmwasm_std::{Coin, Event};
+use tic_tac_toe::models::{
+    state::{
+        Coord, Opening, PlayerSymbol, Status, TimeControl, Variant, GRAVITY_COLUMNS, GRAVITY_ROWS,
+    },
+    ExecuteMsg, QueryKey,
+};
+
+use crate::backend::Backend;
+use crate::board::render_game;
+use crate::log::parse_coins;
+
+/**
+    Invite, play and query games of the contract.
+*/
+#[derive(Parser, Debug)]
+#[command(name = "ttt")]
+pub struct Cli {
+    /// Address signing the executed messages
+    #[arg(long, global = true)]
+    pub sender: Option<String>,
+
+    /// Log of the local chain, created when it does not exist
+    #[arg(long, global = true, default_value = "tic_tac_toe.jsonl")]
+    pub local: PathBuf,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Create a game playing the first move
+    Invite {
+        #[arg(long)]
+        opponent: String,
+        #[arg(long)]
+        x: u8,
+        #[arg(long)]
+        y: u8,
+        #[arg(long, default_value = "X", value_parser = parse_symbol)]
+        symbol: PlayerSymbol,
+        #[arg(long, value_parser = parse_amount)]
+        amount: Vec<Coin>,
+        /// Stake required from the opponent, defaults to --amount
+        #[arg(long, value_parser = parse_amount)]
+        opponent_amount: Vec<Coin>,
+        /// STANDARD, PIE, NO_CENTER or CELL=x,y to force the first move
+        #[arg(long, value_parser = parse_opening)]
+        opening: Option<Opening>,
+        /// Chess clock as base+increment in seconds, e.g. 300+5
+        #[arg(long, value_parser = parse_time_control)]
+        time_control: Option<TimeControl>,
+        /// CLASSIC, MORRIS, GRAVITY for Connect Four on 7x6 or GRAVITY=columns,rows,
+        /// on a gravity board y is the row where the piece falls
+        #[arg(long, value_parser = parse_variant)]
+        variant: Option<Variant>,
+        /// Play for nothing, fails with --amount or --opponent-amount
+        #[arg(long)]
+        practice: bool,
+    },
+    /// Accept an invite playing the second move
+    Accept {
+        #[arg(long)]
+        host: String,
+        #[arg(long)]
+        x: u8,
+        #[arg(long)]
+        y: u8,
+        #[arg(long, value_parser = parse_amount)]
+        amount: Vec<Coin>,
+    },
+    /// Accept a PIE invite taking the first move as your own
+    Swap {
+        #[arg(long)]
+        host: String,
+        #[arg(long, value_parser = parse_amount)]
+        amount: Vec<Coin>,
+    },
+    /// Reject an invite, refunding the host
+    Reject {
+        #[command(flatten)]
+        other: OtherPlayer,
+    },
+    /// Invite the other player of a finished game to a rematch
+    Rematch {
+        #[command(flatten)]
+        other: OtherPlayer,
+        /// First move, only when the sender moved second
+        #[arg(long, requires = "y")]
+        x: Option<u8>,
+        #[arg(long, requires = "x")]
+        y: Option<u8>,
+        /// Reinvest the prize won, both players stake it (PULL payout mode only)
+        #[arg(long)]
+        double_or_nothing: bool,
+        #[arg(long, value_parser = parse_amount)]
+        amount: Vec<Coin>,
+    },
+    /// Play a move in a game in status PLAYING
+    Play {
+        #[command(flatten)]
+        other: OtherPlayer,
+        #[arg(long)]
+        x: u8,
+        #[arg(long)]
+        y: u8,
+    },
+    /// Move a piece to an adjacent empty cell once all the pieces of a MORRIS game are placed
+    Move {
+        #[command(flatten)]
+        other: OtherPlayer,
+        #[arg(long)]
+        from_x: u8,
+        #[arg(long)]
+        from_y: u8,
+        #[arg(long)]
+        x: u8,
+        #[arg(long)]
+        y: u8,
+    },
+    /// Drop a piece in a column of a gravity game
+    Drop {
+        #[command(flatten)]
+        other: OtherPlayer,
+        #[arg(long)]
+        column: u8,
+    },
+    /// Win on time when the clock of the other player ran out
+    CallFlag {
+        #[command(flatten)]
+        other: OtherPlayer,
+    },
+    /// List the games, optionally filtered
+    Games {
+        #[arg(long, requires = "opponent")]
+        host: Option<String>,
+        #[arg(long, requires = "host")]
+        opponent: Option<String>,
+        #[arg(long, value_parser = parse_status)]
+        status: Option<Status>,
+    },
+    /// Print the board of the game between the host and the opponent
+    Show {
+        #[arg(long)]
+        host: String,
+        #[arg(long)]
+        opponent: String,
+    },
+}
+
+/**
+    The other player of the game: `--opponent` when the
+    sender is the host and `--host` when it is the opponent.
+*/
+#[derive(Args, Debug)]
+#[group(required = true, multiple = false)]
+pub struct OtherPlayer {
+    #[arg(long)]
+    host: Option<String>,
+    #[arg(long)]
+    opponent: Option<String>,
+}
+
+impl OtherPlayer {
+    fn as_host(&self) -> bool {
+        self.opponent.is_some()
+    }
+
+    fn address(&self) -> String {
+        self.opponent
+            .clone()
+            .or_else(|| self.host.clone())
+            .unwrap_or_default()
+    }
+
+    fn key(&self, sender: &str) -> QueryKey {
+        match &self.opponent {
+            Some(opponent) => QueryKey {
+                host: sender.to_string(),
+                opponent: opponent.clone(),
+            },
+            None => QueryKey {
+                host: self.address(),
+                opponent: sender.to_string(),
+            },
+        }
+    }
+}
+
+fn parse_symbol(value: &str) -> Result<PlayerSymbol, String> {
+    match value {
+        "X" | "x" => Ok(PlayerSymbol::X),
+        "O" | "o" => Ok(PlayerSymbol::O),
+        _ => Err(String::from("expected X or O")),
+    }
+}
+
+fn parse_status(value: &str) -> Result<Status, String> {
+    match value.to_uppercase().as_str() {
+        "INVITED" => Ok(Status::INVITED),
+        "PLAYING" => Ok(Status::PLAYING),
+        "COMPLETED" => Ok(Status::COMPLETED),
+        "REJECTED" => Ok(Status::REJECTED),
+        "VOIDED" => Ok(Status::VOIDED),
+        _ => Err(String::from(
+            "expected INVITED, PLAYING, COMPLETED, REJECTED or VOIDED",
+        )),
+    }
+}
+
+fn parse_opening(value: &str) -> Result<Opening, String> {
+    let value = value.to_uppercase();
+    match value.as_str() {
+        "STANDARD" => Ok(Opening::Standard {}),
+        "PIE" => Ok(Opening::Pie {}),
+        "NO_CENTER" => Ok(Opening::NoCenter {}),
+        _ => value
+            .strip_prefix("CELL=")
+            .and_then(|coord| coord.split_once(','))
+            .and_then(|(x, y)| {
+                Some(Coord {
+                    x: x.parse().ok()?,
+                    y: y.parse().ok()?,
+                })
+            })
+            .map(|coord| Opening::Cell { coord })
+            .ok_or_else(|| String::from("expected STANDARD, PIE, NO_CENTER or CELL=x,y")),
+    }
+}
+
+fn parse_time_control(value: &str) -> Result<TimeControl, String> {
+    value
+        .split_once('+')
+        .and_then(|(base, increment)| {
+            Some(TimeControl {
+                base: base.parse().ok()?,
+                increment: increment.parse().ok()?,
+            })
+        })
+        .ok_or_else(|| String::from("expected base+increment in seconds like 300+5"))
+}
+
+/**
+    Shared with the TUI, which plays the same variants locally.
+*/
+pub fn parse_variant(value: &str) -> Result<Variant, String> {
+    let value = value.to_uppercase();
+    match value.as_str() {
+        "CLASSIC" => Ok(Variant::Classic {}),
+        "MORRIS" => Ok(Variant::Morris {}),
+        "GRAVITY" => Ok(Variant::Gravity {
+            columns: GRAVITY_COLUMNS,
+            rows: GRAVITY_ROWS,
+        }),
+        _ => value
+            .strip_prefix("GRAVITY=")
+            .and_then(|size| size.split_once(','))
+            .and_then(|(columns, rows)| {
+                Some(Variant::Gravity {
+                    columns: columns.parse().ok()?,
+                    rows: rows.parse().ok()?,
+                })
+            })
+            .ok_or_else(|| {
+                String::from("expected CLASSIC, MORRIS, GRAVITY or GRAVITY=columns,rows")
+            }),
+    }
+}
+
+/**
+    Amounts are written as in the contract events, e.g. "2token",
+    the flag can be repeated to send more than one denom.
+*/
+fn parse_amount(value: &str) -> Result<Coin, String> {
+    match parse_coins(value).as_deref() {
+        Some([coin]) => Ok(coin.clone()),
+        _ => Err(String::from("expected an amount like 2token")),
+    }
+}
+
+/**
+    Runs the command against the backend and returns the
+    text to print, executed commands print the attributes
+    of the transaction followed by the resulting game.
+*/
+pub fn run(
+    backend: &mut dyn Backend,
+    sender: Option<&str>,
+    command: Command,
+) -> anyhow::Result<String> {
+    let sender =
+        || sender.ok_or_else(|| anyhow::anyhow!("--sender is required to execute messages"));
+
+    let (msg, funds, key) = match command {
+        Command::Games {
+            host,
+            opponent,
+            status,
+        } => {
+            let key = host
+                .zip(opponent)
+                .map(|(host, opponent)| QueryKey { host, opponent });
+            let mut games = backend.games(key, status)?;
+            games.sort_by_key(|res| res.game.id);
+            return Ok(games
+                .iter()
+                .map(|res| {
+                    format!(
+                        "{}\t{}\t{}\t{}\n",
+                        res.game.id, res.host, res.opponent, res.game.status
+                    )
+                })
+                .collect());
+        }
+        Command::Show { host, opponent } => return show(backend, QueryKey { host, opponent }),
+        Command::Invite {
+            opponent,
+            x,
+            y,
+            symbol,
+            amount,
+            opponent_amount,
+            opening,
+            time_control,
+            variant,
+            practice,
+        } => (
+            ExecuteMsg::Invite {
+                coord: Coord { x, y },
+                host_symbol: symbol,
+                opponent: opponent.clone(),
+                opponent_stake: Some(opponent_amount).filter(|amount| !amount.is_empty()),
+                opening,
+                time_control,
+                variant,
+                practice: Some(practice).filter(|practice| *practice),
+            },
+            amount,
+            QueryKey {
+                host: sender()?.to_string(),
+                opponent,
+            },
+        ),
+        Command::Accept { host, x, y, amount } => (
+            ExecuteMsg::Accept {
+                coord: Coord { x, y },
+                host: host.clone(),
+            },
+            amount,
+            QueryKey {
+                host,
+                opponent: sender()?.to_string(),
+            },
+        ),
+        Command::Swap { host, amount } => (
+            ExecuteMsg::Swap { host: host.clone() },
+            amount,
+            QueryKey {
+                host,
+                opponent: sender()?.to_string(),
+            },
+        ),
+        Command::Reject { other } => (
+            ExecuteMsg::Reject {
+                as_host: other.as_host(),
+                opponent: other.address(),
+            },
+            vec![],
+            other.key(sender()?),
+        ),
+        Command::Rematch {
+            other,
+            x,
+            y,
+            double_or_nothing,
+            amount,
+        } => (
+            ExecuteMsg::Rematch {
+                game: other.key(sender()?),
+                coord: x.zip(y).map(|(x, y)| Coord { x, y }),
+                double_or_nothing,
+            },
+            amount,
+            QueryKey {
+                host: sender()?.to_string(),
+                opponent: other.address(),
+            },
+        ),
+        Command::Play { other, x, y } => (
+            ExecuteMsg::Play {
+                as_host: other.as_host(),
+                coord: Coord { x, y },
+                opponent: other.address(),
+            },
+            vec![],
+            other.key(sender()?),
+        ),
+        Command::Move {
+            other,
+            from_x,
+            from_y,
+            x,
+            y,
+        } => (
+            ExecuteMsg::MovePiece {
+                as_host: other.as_host(),
+                from: Coord {
+                    x: from_x,
+                    y: from_y,
+                },
+                to: Coord { x, y },
+                opponent: other.address(),
+            },
+            vec![],
+            other.key(sender()?),
+        ),
+        Command::Drop { other, column } => (
+            ExecuteMsg::DropPiece {
+                as_host: other.as_host(),
+                column,
+                opponent: other.address(),
+            },
+            vec![],
+            other.key(sender()?),
+        ),
+        Command::CallFlag { other } => (
+            ExecuteMsg::CallFlag {
+                as_host: other.as_host(),
+                opponent: other.address(),
+            },
+            vec![],
+            other.key(sender()?),
+        ),
+    };
+
+    let events = backend.execute(sender()?, msg, &funds)?;
+    Ok(format!("{}\n\n{}", summary(&events), show(backend, key)?))
+}
+
+fn show(backend: &dyn Backend, key: QueryKey) -> anyhow::Result<String> {
+    let games = backend.games(Some(key.clone()), None)?;
+    let res = games
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("no game between {} and {}", key.host, key.opponent))?;
+
+    Ok(render_game(res))
+}
+
+/**
+    The `wasm` attributes of the transaction as key=value pairs.
+*/
+fn summary(events: &[Event]) -> String {
+    events
+        .iter()
+        .filter(|event| event.ty == "wasm")
+        .flat_map(|event| event.attributes.iter())
+        .filter(|attr| !attr.key.starts_with('_'))
+        .map(|attr| format!("{}={}", attr.key, attr.value))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
@@ -0,0 +1,243 @@
+use serde::Serialize;
+use thiserror::Error;
+use tic_tac_toe::bot::choose_move;
+use tic_tac_toe::models::errors::ContractError;
+use tic_tac_toe::models::state::{Coord, Difficulty, Game, Opening, PlayerSymbol, Status, Variant};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum HotSeatError {
+    #[error("The game is finished")]
+    Finished {},
+
+    #[error("Invalid coordinate x: {}, y: {}", coord.x, coord.y)]
+    InvalidCoord { coord: Coord },
+
+    #[error("Coordinate x: {}, y: {} already played", coord.x, coord.y)]
+    CoordinateAlreadyPlayed { coord: Coord },
+
+    #[error("There are no moves to undo")]
+    NothingToUndo {},
+
+    #[error("{0}")]
+    Game(#[from] ContractError),
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct HistoryMove {
+    pub symbol: PlayerSymbol,
+    /// Cell the piece was moved from in a MORRIS game
+    pub from: Option<Coord>,
+    pub coord: Coord,
+}
+
+/**
+    Finished game as exported by the TUI.
+*/
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Export {
+    pub moves: Vec<HistoryMove>,
+    pub bot: Option<Difficulty>,
+    pub game: Game,
+}
+
+/**
+    Local game between two people sharing the keyboard or between
+    a person and the house bot. Every move goes through the same
+    `Game` methods as the contract, so the session can be used to
+    try the rules by hand. The game is replayed from the history
+    on undo, which keeps the board and the history in sync.
+    The bot, like the house, only plays classic games.
+*/
+pub struct HotSeat {
+    game: Game,
+    history: Vec<HistoryMove>,
+    bot: Option<(Difficulty, PlayerSymbol)>,
+}
+
+impl HotSeat {
+    /**
+        The host plays X and moves first.
+    */
+    pub fn new() -> HotSeat {
+        HotSeat {
+            game: HotSeat::empty_game(Variant::Classic {}),
+            history: vec![],
+            bot: None,
+        }
+    }
+
+    /**
+        Game of the given variant between two people,
+        the host plays X and moves first.
+    */
+    pub fn with_variant(variant: Variant) -> Result<HotSeat, HotSeatError> {
+        Ok(HotSeat {
+            game: HotSeat::empty_game(variant.check()?),
+            ..HotSeat::new()
+        })
+    }
+
+    /**
+        The bot plays with the given symbol and
+        moves straight away when it plays X.
+    */
+    pub fn against_bot(difficulty: Difficulty, symbol: PlayerSymbol) -> HotSeat {
+        let mut hot_seat = HotSeat {
+            bot: Some((difficulty, symbol)),
+            ..HotSeat::new()
+        };
+        hot_seat.bot_move();
+        hot_seat
+    }
+
+    fn empty_game(variant: Variant) -> Game {
+        Game {
+            id: 0,
+            board: variant.board(),
+            host_symbol: PlayerSymbol::X,
+            player_round: Some(PlayerSymbol::X),
+            prize: vec![],
+            host_stake: vec![],
+            opponent_stake: vec![],
+            sponsor: None,
+            sponsorship: vec![],
+            practice: true,
+            opponent_first: false,
+            opening: Opening::Standard {},
+            time_control: None,
+            variant,
+            positions: vec![],
+            status: Status::PLAYING,
+            winner: None,
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn history(&self) -> &[HistoryMove] {
+        &self.history
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.game.status == Status::COMPLETED
+    }
+
+    /**
+        Plays the move of the player in round,
+        followed by the bot move when there is a bot.
+    */
+    pub fn play(&mut self, coord: Coord) -> Result<(), HotSeatError> {
+        self.apply(None, coord)?;
+        self.bot_move();
+
+        Ok(())
+    }
+
+    /**
+        Drops the piece of the player in round in the column
+        of a gravity board, where it falls to the lowest empty cell.
+    */
+    pub fn drop_piece(&mut self, column: u8) -> Result<(), HotSeatError> {
+        let coord = self.game.drop_coord(column)?;
+        self.play(coord)
+    }
+
+    /**
+        Moves the piece of the player in round on `from` to the
+        adjacent empty cell `to`, once all the pieces of a MORRIS
+        game are placed.
+    */
+    pub fn move_piece(&mut self, from: Coord, to: Coord) -> Result<(), HotSeatError> {
+        self.apply(Some(from), to)
+    }
+
+    fn apply(&mut self, from: Option<Coord>, coord: Coord) -> Result<(), HotSeatError> {
+        let symbol = match self.game.player_round {
+            Some(symbol) if self.game.status == Status::PLAYING => symbol,
+            _ => return Err(HotSeatError::Finished {}),
+        };
+        match self.game.variant.check_coord(coord) {
+            Err(ContractError::InvalidCoord { coord }) => {
+                return Err(HotSeatError::InvalidCoord { coord })
+            }
+            res => res?,
+        }
+        if self.game.already_played_on(coord)? {
+            return Err(HotSeatError::CoordinateAlreadyPlayed { coord });
+        }
+
+        match from {
+            Some(from) => self.game.move_piece(from, coord)?,
+            None => self.game.play(coord)?,
+        }
+        .complete_round()?;
+        self.history.push(HistoryMove {
+            symbol,
+            from,
+            coord,
+        });
+
+        Ok(())
+    }
+
+    fn bot_move(&mut self) {
+        if let Some((difficulty, symbol)) = self.bot {
+            if self.game.player_round == Some(symbol) {
+                let seed = self.history.len() as u64;
+                if let Some(coord) = choose_move(&self.game, difficulty, seed) {
+                    let _ = self.apply(None, coord);
+                }
+            }
+        }
+    }
+
+    /**
+        Takes back the last move, or the last move of
+        the person and the answer of the bot, and replays
+        the remaining history on an empty board.
+    */
+    pub fn undo(&mut self) -> Result<(), HotSeatError> {
+        let mut history = self.history.clone();
+        let human_moves = history
+            .iter()
+            .filter(|entry| Some(entry.symbol) != self.bot.map(|(_, symbol)| symbol))
+            .count();
+        if human_moves == 0 {
+            return Err(HotSeatError::NothingToUndo {});
+        }
+
+        while let Some(last) = history.pop() {
+            if Some(last.symbol) != self.bot.map(|(_, symbol)| symbol) {
+                break;
+            }
+        }
+
+        self.game = HotSeat::empty_game(self.game.variant);
+        self.history = vec![];
+        for entry in history {
+            self.apply(entry.from, entry.coord)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn export(&self) -> Option<Export> {
+        if !self.is_finished() {
+            return None;
+        }
+
+        Some(Export {
+            moves: self.history.clone(),
+            bot: self.bot.map(|(difficulty, _)| difficulty),
+            game: self.game.clone(),
+        })
+    }
+}
+
+impl Default for HotSeat {
+    fn default() -> HotSeat {
+        HotSeat::new()
+    }
+}
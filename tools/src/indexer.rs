@@ -0,0 +1,715 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use cosmwasm_std::{Addr, Coin, Event, Uint128};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::Serialize;
+use thiserror::Error;
+use tic_tac_toe::channel::board_hash;
+use tic_tac_toe::models::{
+    errors::ContractError,
+    events,
+    responses::GameResponse,
+    state::{Coord, Game, Opening, PlayerSymbol, Status, TimeControl, Variant},
+};
+
+use crate::log::{parse_coins, LogEntry};
+
+#[derive(Error, Debug)]
+pub enum IndexerError {
+    #[error("{0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    Contract(#[from] ContractError),
+
+    #[error("Event {event} at height {height} is missing the attribute {key}")]
+    MissingAttribute {
+        event: String,
+        height: u64,
+        key: String,
+    },
+
+    #[error("Event {event} at height {height} has an invalid {key}: {value}")]
+    InvalidAttribute {
+        event: String,
+        height: u64,
+        key: String,
+        value: String,
+    },
+
+    #[error("Game {game_id} at height {height} has not been indexed")]
+    UnknownGame { game_id: u64, height: u64 },
+
+    #[error("Game {game_id} at height {height} does not match the contract: {reason}")]
+    Drift {
+        game_id: u64,
+        height: u64,
+        reason: String,
+    },
+}
+
+/**
+    A move as indexed from a move_played event.
+*/
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct IndexedMove {
+    pub game_id: u64,
+    pub move_index: u32,
+    pub player: Addr,
+    pub symbol: PlayerSymbol,
+    pub coord: Coord,
+    pub height: u64,
+}
+
+/**
+    A payment as indexed from a prize_paid event,
+    either a prize or the refund of a rejected game.
+*/
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Payout {
+    pub game_id: u64,
+    pub recipient: Addr,
+    pub amount: Vec<Coin>,
+    pub height: u64,
+}
+
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub games: u64,
+    pub by_status: BTreeMap<String, u64>,
+    pub moves: u64,
+    pub paid: Vec<Coin>,
+    /**
+        Addresses sorted by number of games won.
+    */
+    pub wins: Vec<(Addr, u64)>,
+}
+
+/**
+    Rebuilds every game from the contract events into a SQLite
+    database. Games are replayed with the contract `Game` methods
+    and the board hash and status of every event are checked
+    against the replayed game, so a log that does not match the
+    rules of the contract is reported instead of indexed.
+*/
+pub struct Indexer {
+    conn: Connection,
+}
+
+impl Indexer {
+    pub fn open(path: impl AsRef<Path>) -> Result<Indexer, IndexerError> {
+        Indexer::migrate(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> Result<Indexer, IndexerError> {
+        Indexer::migrate(Connection::open_in_memory()?)
+    }
+
+    fn migrate(conn: Connection) -> Result<Indexer, IndexerError> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS games (
+                id INTEGER PRIMARY KEY,
+                host TEXT NOT NULL,
+                opponent TEXT NOT NULL,
+                status TEXT NOT NULL,
+                winner TEXT,
+                game TEXT NOT NULL,
+                created_height INTEGER NOT NULL,
+                updated_height INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS moves (
+                game_id INTEGER NOT NULL,
+                move_index INTEGER NOT NULL,
+                player TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                x INTEGER NOT NULL,
+                y INTEGER NOT NULL,
+                height INTEGER NOT NULL,
+                PRIMARY KEY (game_id, move_index)
+            );
+            CREATE TABLE IF NOT EXISTS payouts (
+                game_id INTEGER NOT NULL,
+                recipient TEXT NOT NULL,
+                amount TEXT NOT NULL,
+                height INTEGER NOT NULL
+            );",
+        )?;
+
+        Ok(Indexer { conn })
+    }
+
+    /**
+        Indexes every contract event of the entry in a single
+        database transaction, so a failing entry leaves the
+        database as it was before the entry.
+    */
+    pub fn ingest(&mut self, entry: &LogEntry) -> Result<(), IndexerError> {
+        let tx = self.conn.transaction()?;
+        for (ty, event) in entry.contract_events() {
+            let mut ctx = EventContext {
+                tx: &tx,
+                entry,
+                ty,
+                event,
+            };
+            match ty {
+                events::GAME_CREATED => ctx.game_created()?,
+                events::GAME_ACCEPTED => ctx.game_accepted()?,
+                events::GAME_REJECTED => ctx.game_rejected()?,
+                events::SIDES_SWAPPED => ctx.sides_swapped()?,
+                events::MOVE_PLAYED => ctx.move_played()?,
+                events::GAME_COMPLETED => ctx.game_completed()?,
+                events::FLAG_FALLEN => ctx.flag_fallen()?,
+                events::GAME_VOIDED => ctx.game_voided()?,
+                events::PRIZE_PAID => ctx.prize_paid()?,
+                _ => {}
+            }
+            ctx.check_game()?;
+        }
+
+        Ok(tx.commit()?)
+    }
+
+    pub fn game(&self, id: u64) -> Result<Option<GameResponse>, IndexerError> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT host, opponent, game FROM games WHERE id = ?1",
+                params![id],
+                game_row,
+            )
+            .optional()?;
+
+        row.map(to_game_response).transpose()
+    }
+
+    pub fn games(&self) -> Result<Vec<GameResponse>, IndexerError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT host, opponent, game FROM games ORDER BY id")?;
+        let rows = stmt.query_map([], game_row)?;
+
+        rows.map(|row| to_game_response(row?)).collect()
+    }
+
+    pub fn moves(&self, game_id: u64) -> Result<Vec<IndexedMove>, IndexerError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT move_index, player, symbol, x, y, height
+            FROM moves WHERE game_id = ?1 ORDER BY move_index",
+        )?;
+        let rows = stmt.query_map(params![game_id], |row| {
+            let symbol: String = row.get(2)?;
+            Ok(IndexedMove {
+                game_id,
+                move_index: row.get(0)?,
+                player: Addr::unchecked(row.get::<_, String>(1)?),
+                symbol: if symbol == "X" {
+                    PlayerSymbol::X
+                } else {
+                    PlayerSymbol::O
+                },
+                coord: Coord {
+                    x: row.get(3)?,
+                    y: row.get(4)?,
+                },
+                height: row.get(5)?,
+            })
+        })?;
+
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    pub fn payouts(&self) -> Result<Vec<Payout>, IndexerError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT game_id, recipient, amount, height FROM payouts ORDER BY rowid")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(Payout {
+                game_id: row.get(0)?,
+                recipient: Addr::unchecked(row.get::<_, String>(1)?),
+                amount: parse_coins(&row.get::<_, String>(2)?).unwrap_or_default(),
+                height: row.get(3)?,
+            })
+        })?;
+
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    pub fn stats(&self) -> Result<Stats, IndexerError> {
+        let mut stats = Stats::default();
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT status, COUNT(*) FROM games GROUP BY status")?;
+        for row in stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))? {
+            let (status, count): (String, u64) = row?;
+            stats.games += count;
+            stats.by_status.insert(status, count);
+        }
+
+        stats.moves = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM moves", [], |row| row.get(0))?;
+
+        let mut paid: BTreeMap<String, u128> = BTreeMap::new();
+        for payout in self.payouts()? {
+            for coin in payout.amount {
+                *paid.entry(coin.denom).or_default() += coin.amount.u128();
+            }
+        }
+        stats.paid = paid
+            .into_iter()
+            .map(|(denom, amount)| Coin {
+                denom,
+                amount: Uint128::new(amount),
+            })
+            .collect();
+
+        let mut stmt = self.conn.prepare(
+            "SELECT winner, COUNT(*) FROM games WHERE winner IS NOT NULL
+            GROUP BY winner ORDER BY COUNT(*) DESC, winner",
+        )?;
+        for row in stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))? {
+            let (winner, count): (String, u64) = row?;
+            stats.wins.push((Addr::unchecked(winner), count));
+        }
+
+        Ok(stats)
+    }
+}
+
+fn game_row(row: &rusqlite::Row) -> rusqlite::Result<(String, String, String)> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+}
+
+fn to_game_response(
+    (host, opponent, game): (String, String, String),
+) -> Result<GameResponse, IndexerError> {
+    Ok(GameResponse {
+        host: Addr::unchecked(host),
+        opponent: Addr::unchecked(opponent),
+        game: serde_json::from_str(&game)?,
+    })
+}
+
+struct EventContext<'a> {
+    tx: &'a Transaction<'a>,
+    entry: &'a LogEntry,
+    ty: &'a str,
+    event: &'a Event,
+}
+
+impl<'a> EventContext<'a> {
+    fn attr(&self, key: &str) -> Result<&'a str, IndexerError> {
+        self.event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .map(|attr| attr.value.as_str())
+            .ok_or_else(|| IndexerError::MissingAttribute {
+                event: self.ty.to_string(),
+                height: self.entry.height,
+                key: key.to_string(),
+            })
+    }
+
+    fn has_attr(&self, key: &str) -> bool {
+        self.event.attributes.iter().any(|attr| attr.key == key)
+    }
+
+    fn parsed<T: std::str::FromStr>(&self, key: &str) -> Result<T, IndexerError> {
+        let value = self.attr(key)?;
+        value.parse().map_err(|_| self.invalid(key, value))
+    }
+
+    fn coins(&self, key: &str) -> Result<Vec<Coin>, IndexerError> {
+        let value = self.attr(key)?;
+        parse_coins(value).ok_or_else(|| self.invalid(key, value))
+    }
+
+    fn coord(&self) -> Result<Coord, IndexerError> {
+        Ok(Coord {
+            x: self.parsed("x")?,
+            y: self.parsed("y")?,
+        })
+    }
+
+    fn symbol(&self, key: &str) -> Result<PlayerSymbol, IndexerError> {
+        match self.attr(key)? {
+            "X" => Ok(PlayerSymbol::X),
+            "O" => Ok(PlayerSymbol::O),
+            value => Err(self.invalid(key, value)),
+        }
+    }
+
+    /**
+        Games created before openings were added have no
+        opening attribute and use the standard opening.
+    */
+    fn opening(&self) -> Result<Opening, IndexerError> {
+        if !self.has_attr("opening") {
+            return Ok(Opening::default());
+        }
+        match self.attr("opening")? {
+            "STANDARD" => Ok(Opening::Standard {}),
+            "PIE" => Ok(Opening::Pie {}),
+            "NO_CENTER" => Ok(Opening::NoCenter {}),
+            value => value
+                .strip_prefix("CELL x=")
+                .and_then(|coord| coord.split_once(" y="))
+                .and_then(|(x, y)| {
+                    Some(Coord {
+                        x: x.parse().ok()?,
+                        y: y.parse().ok()?,
+                    })
+                })
+                .map(|coord| Opening::Cell { coord })
+                .ok_or_else(|| self.invalid("opening", value)),
+        }
+    }
+
+    /**
+        Games created before variants were added have no
+        variant attribute and are played on the classic board.
+    */
+    fn variant(&self) -> Result<Variant, IndexerError> {
+        if !self.has_attr("variant") {
+            return Ok(Variant::default());
+        }
+        match self.attr("variant")? {
+            "CLASSIC" => Ok(Variant::Classic {}),
+            "MORRIS" => Ok(Variant::Morris {}),
+            value => value
+                .strip_prefix("GRAVITY columns=")
+                .and_then(|size| size.split_once(" rows="))
+                .and_then(|(columns, rows)| {
+                    Some(Variant::Gravity {
+                        columns: columns.parse().ok()?,
+                        rows: rows.parse().ok()?,
+                    })
+                })
+                .ok_or_else(|| self.invalid("variant", value)),
+        }
+    }
+
+    fn time_control(&self) -> Result<Option<TimeControl>, IndexerError> {
+        if !self.has_attr("time_control") {
+            return Ok(None);
+        }
+        let value = self.attr("time_control")?;
+        value
+            .split_once('+')
+            .and_then(|(base, increment)| {
+                Some(TimeControl {
+                    base: base.parse().ok()?,
+                    increment: increment.parse().ok()?,
+                })
+            })
+            .map(Some)
+            .ok_or_else(|| self.invalid("time_control", value))
+    }
+
+    fn invalid(&self, key: &str, value: &str) -> IndexerError {
+        IndexerError::InvalidAttribute {
+            event: self.ty.to_string(),
+            height: self.entry.height,
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn drift(&self, game: &Game, reason: String) -> IndexerError {
+        IndexerError::Drift {
+            game_id: game.id,
+            height: self.entry.height,
+            reason,
+        }
+    }
+
+    fn load(&self) -> Result<(Addr, Addr, Game), IndexerError> {
+        self.load_id(self.parsed("game_id")?)
+    }
+
+    fn load_id(&self, game_id: u64) -> Result<(Addr, Addr, Game), IndexerError> {
+        let row = self
+            .tx
+            .query_row(
+                "SELECT host, opponent, game FROM games WHERE id = ?1",
+                params![game_id],
+                game_row,
+            )
+            .optional()?
+            .ok_or(IndexerError::UnknownGame {
+                game_id,
+                height: self.entry.height,
+            })?;
+        let res = to_game_response(row)?;
+
+        Ok((res.host, res.opponent, res.game))
+    }
+
+    fn save(&self, host: &Addr, opponent: &Addr, game: &Game) -> Result<(), IndexerError> {
+        self.tx.execute(
+            "INSERT INTO games (id, host, opponent, status, winner, game, created_height, updated_height)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+            ON CONFLICT (id) DO UPDATE SET
+                status = excluded.status,
+                winner = excluded.winner,
+                game = excluded.game,
+                updated_height = excluded.updated_height",
+            params![
+                game.id,
+                host.as_str(),
+                opponent.as_str(),
+                game.status.to_string(),
+                game.winner_address(host, opponent).map(Addr::as_str),
+                serde_json::to_string(game)?,
+                self.entry.height,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn game_created(&mut self) -> Result<(), IndexerError> {
+        if self.has_attr("rematch_of") {
+            return self.rematch_created();
+        }
+        let mut game = Game::new(
+            self.parsed("game_id")?,
+            self.coord()?,
+            self.coins("amount")?,
+            self.coins("opponent_amount")?,
+            self.symbol("host_symbol")?,
+            self.opening()?,
+            self.time_control()?,
+            self.variant()?,
+        )?;
+        game.practice = self.has_attr("practice");
+        let host = Addr::unchecked(self.attr("host")?);
+        let opponent = Addr::unchecked(self.attr("opponent")?);
+
+        self.save(&host, &opponent, &game)?;
+        self.save_move(&game, &host, game.host_symbol, self.coord()?)
+    }
+
+    /**
+        The rematch is replayed from the game it rematches, its
+        first move is only played when the host moves first.
+    */
+    fn rematch_created(&mut self) -> Result<(), IndexerError> {
+        let (previous_host, _, previous) = self.load_id(self.parsed("rematch_of")?)?;
+        let host = Addr::unchecked(self.attr("host")?);
+        let opponent = Addr::unchecked(self.attr("opponent")?);
+        let coord = if self.has_attr("x") {
+            Some(self.coord()?)
+        } else {
+            None
+        };
+        let game = previous.rematch(
+            self.parsed("game_id")?,
+            host == previous_host,
+            coord,
+            self.coins("amount")?,
+            self.coins("opponent_amount")?,
+        )?;
+
+        self.save(&host, &opponent, &game)?;
+        match coord {
+            Some(coord) => self.save_move(&game, &host, game.host_symbol, coord),
+            None => Ok(()),
+        }
+    }
+
+    /**
+        The accept move is indexed by the
+        move_played event that follows.
+    */
+    fn game_accepted(&mut self) -> Result<(), IndexerError> {
+        let (host, opponent, mut game) = self.load()?;
+        game.add_opponent_stake()?;
+        if self.has_attr("sponsor") {
+            let sponsor = Addr::unchecked(self.attr("sponsor")?);
+            game.add_sponsorship(sponsor, self.coins("sponsorship")?)?;
+        }
+        game.status = Status::PLAYING;
+
+        self.save(&host, &opponent, &game)
+    }
+
+    fn game_rejected(&mut self) -> Result<(), IndexerError> {
+        let (host, opponent, mut game) = self.load()?;
+        game.status = Status::REJECTED;
+
+        self.save(&host, &opponent, &game)
+    }
+
+    fn sides_swapped(&mut self) -> Result<(), IndexerError> {
+        let (host, opponent, mut game) = self.load()?;
+        game.swap_sides()?;
+
+        self.save(&host, &opponent, &game)
+    }
+
+    fn move_played(&mut self) -> Result<(), IndexerError> {
+        let (host, opponent, mut game) = self.load()?;
+        let coord = self.coord()?;
+        let symbol = self.symbol("symbol")?;
+        if game.player_round != Some(symbol) {
+            return Err(self.drift(&game, format!("{} played out of turn", symbol)));
+        }
+        if self.has_attr("from_x") {
+            let from = Coord {
+                x: self.parsed("from_x")?,
+                y: self.parsed("from_y")?,
+            };
+            if game.check_move_piece(from, coord).is_err() {
+                return Err(self.drift(
+                    &game,
+                    format!(
+                        "invalid piece move from x: {}, y: {} to x: {}, y: {}",
+                        from.x, from.y, coord.x, coord.y
+                    ),
+                ));
+            }
+            game.move_piece(from, coord)?;
+        } else if game.check_move(coord).is_err() {
+            return Err(self.drift(
+                &game,
+                format!("invalid move x: {}, y: {}", coord.x, coord.y),
+            ));
+        } else {
+            game.play(coord)?;
+        }
+        game.complete_round()?;
+
+        self.save(&host, &opponent, &game)?;
+        self.save_move(&game, &Addr::unchecked(self.attr("player")?), symbol, coord)
+    }
+
+    fn save_move(
+        &self,
+        game: &Game,
+        player: &Addr,
+        symbol: PlayerSymbol,
+        coord: Coord,
+    ) -> Result<(), IndexerError> {
+        self.tx.execute(
+            "INSERT INTO moves (game_id, move_index, player, symbol, x, y, height)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                game.id,
+                game.moves_count(),
+                player.as_str(),
+                symbol.to_string(),
+                coord.x,
+                coord.y,
+                self.entry.height,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /**
+        Games finished by a move are already COMPLETED once the
+        move is replayed, otherwise the winner attribute tells
+        which player resigned, without a winner both agreed a draw.
+    */
+    fn game_completed(&mut self) -> Result<(), IndexerError> {
+        let (host, opponent, mut game) = self.load()?;
+        let expected = self.attr("winner").ok();
+        if game.status != Status::COMPLETED {
+            match expected {
+                Some(winner) if winner == host.as_str() => game.resign(game.host_symbol.other()),
+                Some(winner) if winner == opponent.as_str() => game.resign(game.host_symbol),
+                Some(winner) => return Err(self.invalid("winner", winner)),
+                None => game.draw(),
+            };
+        }
+
+        let winner = game.winner_address(&host, &opponent).map(Addr::as_str);
+        if winner != expected {
+            return Err(self.drift(
+                &game,
+                format!("winner {:?} expected {:?}", winner, expected),
+            ));
+        }
+
+        self.save(&host, &opponent, &game)
+    }
+
+    fn flag_fallen(&mut self) -> Result<(), IndexerError> {
+        let (host, opponent, mut game) = self.load()?;
+        if self.attr("player")? == host.as_str() {
+            game.resign(game.host_symbol);
+        } else {
+            game.resign(game.host_symbol.other());
+        }
+
+        self.save(&host, &opponent, &game)
+    }
+
+    fn game_voided(&mut self) -> Result<(), IndexerError> {
+        let (host, opponent, mut game) = self.load()?;
+        game.status = Status::VOIDED;
+        game.player_round = None;
+
+        self.save(&host, &opponent, &game)
+    }
+
+    fn prize_paid(&mut self) -> Result<(), IndexerError> {
+        let amount = self.coins("amount")?;
+        self.tx.execute(
+            "INSERT INTO payouts (game_id, recipient, amount, height)
+            VALUES (?1, ?2, ?3, ?4)",
+            params![
+                self.parsed::<u64>("game_id")?,
+                self.attr("recipient")?,
+                events::format_coins(&amount),
+                self.entry.height,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /**
+        Every game event carries the status and the board hash
+        after the action, which must match the replayed game.
+        The board of game_accepted already contains the accept
+        move, so it is checked by the move_played that follows.
+    */
+    fn check_game(&self) -> Result<(), IndexerError> {
+        if matches!(
+            self.ty,
+            events::PRIZE_PAID | events::SPONSORSHIP_ADDED | events::SPONSORSHIP_RETURNED
+        ) {
+            return Ok(());
+        }
+
+        let (_, _, game) = self.load()?;
+        let status = self.attr("status")?;
+        if status != game.status.to_string() {
+            return Err(self.drift(&game, format!("status {} expected {}", game.status, status)));
+        }
+
+        if self.ty == events::GAME_ACCEPTED {
+            return Ok(());
+        }
+
+        let hash: String = board_hash(&game)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        if hash != self.attr("board_hash")? {
+            return Err(self.drift(&game, String::from("board hash mismatch")));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,8 @@
+pub mod backend;
+pub mod board;
+pub mod cli;
+pub mod hotseat;
+pub mod indexer;
+pub mod local;
+pub mod log;
+pub mod scenario;
@@ -0,0 +1,89 @@
+use cosmwasm_std::{Addr, Coin, Empty};
+use cw_multi_test::{next_block, App, AppResponse, Contract, ContractWrapper, Executor};
+use serde::de::DeserializeOwned;
+use tic_tac_toe::contract::{execute::execute, instantiate::instantiate, query::query};
+use tic_tac_toe::models::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+use crate::log::LogEntry;
+
+fn tic_tac_toe_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+/**
+    The contract running on a local cw-multi-test app, every
+    successful execution is recorded as a LogEntry so the
+    log can be replayed by the indexer.
+*/
+pub struct LocalChain {
+    pub app: App,
+    pub contract: Addr,
+    pub log: Vec<LogEntry>,
+}
+
+impl LocalChain {
+    pub fn new(balances: &[(&str, Vec<Coin>)], msg: InstantiateMsg) -> anyhow::Result<LocalChain> {
+        let mut app = App::new(|router, _, storage| {
+            for (address, amount) in balances {
+                router
+                    .bank
+                    .init_balance(storage, &Addr::unchecked(*address), amount.clone())
+                    .unwrap();
+            }
+        });
+        let code_id = app.store_code(tic_tac_toe_contract());
+        let contract = app.instantiate_contract(
+            code_id,
+            Addr::unchecked("admin"),
+            &msg,
+            &[],
+            "tic_tac_toe",
+            None,
+        )?;
+
+        Ok(LocalChain {
+            app,
+            contract,
+            log: vec![],
+        })
+    }
+
+    /**
+        Executes the message in a new block.
+    */
+    pub fn execute(
+        &mut self,
+        sender: &str,
+        msg: ExecuteMsg,
+        funds: &[Coin],
+    ) -> anyhow::Result<AppResponse> {
+        self.app.update_block(next_block);
+        let res = self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.contract.clone(),
+            &msg,
+            funds,
+        )?;
+
+        self.log.push(LogEntry {
+            height: self.app.block_info().height,
+            sender: sender.to_string(),
+            msg,
+            funds: funds.to_vec(),
+            events: res.events.clone(),
+        });
+
+        Ok(res)
+    }
+
+    pub fn query<T: DeserializeOwned>(&self, msg: &QueryMsg) -> anyhow::Result<T> {
+        Ok(self
+            .app
+            .wrap()
+            .query_wasm_smart(self.contract.clone(), msg)?)
+    }
+
+    pub fn balance(&self, address: &str, denom: &str) -> anyhow::Result<Coin> {
+        Ok(self.app.wrap().query_balance(address, denom)?)
+    }
+}
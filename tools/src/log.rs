@@ -0,0 +1,81 @@
+use std::io::{BufRead, Write};
+
+use cosmwasm_std::{Coin, Event, Uint128};
+use serde::{Deserialize, Serialize};
+use tic_tac_toe::models::ExecuteMsg;
+
+/**
+    One line of the JSONL transaction log: the message
+    executed on the contract and the events it emitted,
+    as returned by the node or by cw-multi-test.
+*/
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LogEntry {
+    pub height: u64,
+    pub sender: String,
+    pub msg: ExecuteMsg,
+    #[serde(default)]
+    pub funds: Vec<Coin>,
+    pub events: Vec<Event>,
+}
+
+impl LogEntry {
+    /**
+        Events emitted by the contract with the `wasm-` prefix
+        added by wasmd removed, so their type matches the
+        constants of `tic_tac_toe::models::events`.
+    */
+    pub fn contract_events(&self) -> impl Iterator<Item = (&str, &Event)> {
+        self.events.iter().filter_map(|event| {
+            let ty = event.ty.strip_prefix("wasm-").unwrap_or(&event.ty);
+            ty.starts_with("tic_tac_toe.").then_some((ty, event))
+        })
+    }
+}
+
+pub fn read_log(reader: impl BufRead) -> anyhow::Result<Vec<LogEntry>> {
+    let mut entries = vec![];
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line)
+            .map_err(|err| anyhow::anyhow!("invalid log entry on line {}: {}", number + 1, err))?;
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+pub fn write_log(mut writer: impl Write, entries: &[LogEntry]) -> anyhow::Result<()> {
+    for entry in entries {
+        serde_json::to_writer(&mut writer, entry)?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+/**
+    Parses a comma separated list of coins as emitted
+    by the contract events, e.g. "2token,5uluna", or "0".
+*/
+pub fn parse_coins(value: &str) -> Option<Vec<Coin>> {
+    if value == "0" {
+        return Some(vec![]);
+    }
+
+    value
+        .split(',')
+        .filter(|coin| !coin.is_empty())
+        .map(|coin| {
+            let split = coin.find(|c: char| !c.is_ascii_digit())?;
+            let (amount, denom) = coin.split_at(split);
+            Some(Coin {
+                denom: denom.to_string(),
+                amount: Uint128::new(amount.parse().ok()?),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,300 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::Path;
+
+use cosmwasm_std::Coin;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+use tic_tac_toe::models::{
+    responses::GameResponse,
+    state::{PlayerSymbol, Status},
+    ExecuteMsg, InstantiateMsg, QueryKey, QueryMsg,
+};
+
+use crate::local::LocalChain;
+use crate::log::parse_coins;
+
+/**
+    Name used in the expectations to refer to the contract address.
+*/
+pub const CONTRACT: &str = "contract";
+
+#[derive(Error, Debug)]
+pub enum ScenarioError {
+    #[error("{0}")]
+    Setup(#[from] anyhow::Error),
+
+    #[error("Step {step} failed: {error}")]
+    UnexpectedError { step: usize, error: String },
+
+    #[error("Step {step} succeeded but was expected to fail with '{expected}'")]
+    MissingError { step: usize, expected: String },
+
+    #[error("Step {step} failed with '{error}' instead of '{expected}'")]
+    WrongError {
+        step: usize,
+        expected: String,
+        error: String,
+    },
+
+    #[error("Balance of {address} is {actual}{denom} instead of {expected}{denom}")]
+    Balance {
+        address: String,
+        denom: String,
+        expected: u128,
+        actual: u128,
+    },
+
+    #[error("No game between {host} and {opponent}")]
+    MissingGame { host: String, opponent: String },
+
+    #[error("Game between {host} and {opponent} has {field} {actual} instead of {expected}")]
+    Game {
+        host: String,
+        opponent: String,
+        field: &'static str,
+        expected: String,
+        actual: String,
+    },
+}
+
+/**
+    Amount written as in the contract events, e.g. "2token"
+    or "2token,1other", an empty string means no funds.
+*/
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(try_from = "String")]
+pub struct Coins(pub Vec<Coin>);
+
+impl TryFrom<String> for Coins {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Coins, String> {
+        parse_coins(&value)
+            .map(Coins)
+            .ok_or_else(|| format!("expected an amount like 2token, got '{}'", value))
+    }
+}
+
+/**
+    Declarative description of a game session: the actors with
+    their initial balances, the messages they execute in order
+    and the expected state of the chain once all of them ran.
+*/
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub instantiate: InstantiateMsg,
+    #[serde(default)]
+    pub actors: BTreeMap<String, Coins>,
+    pub steps: Vec<Step>,
+    #[serde(default)]
+    pub expect: Expectations,
+}
+
+/**
+    Message executed by the sender. When `error` is set the step
+    must fail with an error that contains the given text.
+*/
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    pub sender: String,
+    #[serde(deserialize_with = "deserialize_msg")]
+    pub msg: ExecuteMsg,
+    #[serde(default)]
+    pub funds: Coins,
+    pub error: Option<String>,
+}
+
+/**
+    Messages are written as their JSON form, e.g. `invite: {...}`,
+    instead of the YAML tags that serde_yaml expects for enums.
+*/
+fn deserialize_msg<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ExecuteMsg, D::Error> {
+    let value = serde_json::Value::deserialize(deserializer)?;
+    serde_json::from_value(value).map_err(D::Error::custom)
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Expectations {
+    #[serde(default)]
+    pub balances: BTreeMap<String, Coins>,
+    #[serde(default)]
+    pub games: Vec<ExpectedGame>,
+}
+
+/**
+    The board is written as one string per row where
+    each cell is X, O or `.` when it is empty, e.g.:
+
+    ```yaml
+    board: ["X..", ".X.", "OOX"]
+    ```
+
+    The winner is the address of the winning player.
+*/
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedGame {
+    pub host: String,
+    pub opponent: String,
+    pub status: Option<Status>,
+    pub winner: Option<String>,
+    pub prize: Option<Coins>,
+    pub board: Option<Vec<String>>,
+}
+
+impl Scenario {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Scenario> {
+        Ok(serde_yaml::from_reader(File::open(path)?)?)
+    }
+
+    /**
+        Runs the scenario on a new local chain and
+        returns the first unmet expectation.
+    */
+    pub fn run(&self) -> Result<LocalChain, ScenarioError> {
+        let balances: Vec<(&str, Vec<Coin>)> = self
+            .actors
+            .iter()
+            .map(|(address, coins)| (address.as_str(), coins.0.clone()))
+            .collect();
+        let mut chain = LocalChain::new(&balances, self.instantiate.clone())?;
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let step_number = index + 1;
+            let res = chain.execute(&step.sender, step.msg.clone(), &step.funds.0);
+            match (res, &step.error) {
+                (Ok(_), None) => {}
+                (Ok(_), Some(expected)) => {
+                    return Err(ScenarioError::MissingError {
+                        step: step_number,
+                        expected: expected.clone(),
+                    })
+                }
+                (Err(err), None) => {
+                    return Err(ScenarioError::UnexpectedError {
+                        step: step_number,
+                        error: root_cause(&err),
+                    })
+                }
+                (Err(err), Some(expected)) => {
+                    if !root_cause(&err).contains(expected.as_str()) {
+                        return Err(ScenarioError::WrongError {
+                            step: step_number,
+                            expected: expected.clone(),
+                            error: root_cause(&err),
+                        });
+                    }
+                }
+            }
+        }
+
+        for (address, coins) in &self.expect.balances {
+            check_balance(&chain, address, &coins.0)?;
+        }
+        for game in &self.expect.games {
+            check_game(&chain, game)?;
+        }
+
+        Ok(chain)
+    }
+}
+
+fn root_cause(err: &anyhow::Error) -> String {
+    err.root_cause().to_string()
+}
+
+fn check_balance(
+    chain: &LocalChain,
+    address: &str,
+    expected: &[Coin],
+) -> Result<(), ScenarioError> {
+    let address = match address {
+        CONTRACT => chain.contract.to_string(),
+        _ => address.to_string(),
+    };
+
+    for coin in expected {
+        let actual = chain.balance(&address, &coin.denom)?;
+        if actual.amount != coin.amount {
+            return Err(ScenarioError::Balance {
+                address,
+                denom: coin.denom.clone(),
+                expected: coin.amount.u128(),
+                actual: actual.amount.u128(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn check_game(chain: &LocalChain, expected: &ExpectedGame) -> Result<(), ScenarioError> {
+    let games: Vec<GameResponse> = chain.query(&QueryMsg::Games {
+        key: Some(QueryKey {
+            host: expected.host.clone(),
+            opponent: expected.opponent.clone(),
+        }),
+        status: None,
+    })?;
+    let res = games.first().ok_or_else(|| ScenarioError::MissingGame {
+        host: expected.host.clone(),
+        opponent: expected.opponent.clone(),
+    })?;
+    let game = &res.game;
+
+    let mismatch = |field, expected_value: String, actual: String| {
+        if expected_value == actual {
+            return Ok(());
+        }
+        Err(ScenarioError::Game {
+            host: expected.host.clone(),
+            opponent: expected.opponent.clone(),
+            field,
+            expected: expected_value,
+            actual,
+        })
+    };
+
+    if let Some(status) = expected.status {
+        mismatch("status", status.to_string(), game.status.to_string())?;
+    }
+    if let Some(winner) = &expected.winner {
+        let actual = game
+            .winner_address(&res.host, &res.opponent)
+            .map(|address| address.to_string())
+            .unwrap_or_default();
+        mismatch("winner", winner.clone(), actual)?;
+    }
+    if let Some(prize) = &expected.prize {
+        mismatch(
+            "prize",
+            format!("{:?}", prize.0),
+            format!("{:?}", game.prize),
+        )?;
+    }
+    if let Some(board) = &expected.board {
+        let actual = game
+            .board
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        Some(PlayerSymbol::X) => 'X',
+                        Some(PlayerSymbol::O) => 'O',
+                        None => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+        mismatch("board", board.join("/"), actual.join("/"))?;
+    }
+
+    Ok(())
+}
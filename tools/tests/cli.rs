@@ -0,0 +1,330 @@
+use clap::Parser;
+use cosmwasm_std::coins;
+use tic_tac_toe::models::InstantiateMsg;
+use tic_tac_toe_tools::backend::{Backend, Playground};
+use tic_tac_toe_tools::cli::{run, Cli};
+use tic_tac_toe_tools::local::LocalChain;
+
+fn local_chain() -> LocalChain {
+    LocalChain::new(
+        &[("alice", coins(10, "token")), ("bob", coins(10, "token"))],
+        InstantiateMsg::default(),
+    )
+    .unwrap()
+}
+
+fn ttt(backend: &mut dyn Backend, args: &str) -> anyhow::Result<String> {
+    let cli = Cli::try_parse_from(std::iter::once("ttt").chain(args.split_whitespace()))?;
+    run(backend, cli.sender.as_deref(), cli.command)
+}
+
+#[test]
+fn play_a_game() {
+    // GIVEN
+    let mut chain = local_chain();
+    ttt(
+        &mut chain,
+        "--sender alice invite --opponent bob --x 1 --y 1 --amount 2token",
+    )
+    .unwrap();
+    ttt(
+        &mut chain,
+        "--sender bob accept --host alice --x 0 --y 0 --amount 2token",
+    )
+    .unwrap();
+    ttt(&mut chain, "--sender alice play --opponent bob --x 1 --y 0").unwrap();
+    ttt(&mut chain, "--sender bob play --host alice --x 0 --y 2").unwrap();
+
+    // WHEN
+    let output = ttt(&mut chain, "--sender alice play --opponent bob --x 1 --y 2").unwrap();
+
+    // THEN
+    assert_eq!(
+        output,
+        "method=play x=1 y=2 status=COMPLETED opponent=bob winner=alice
+
+Game 1: alice (X) vs bob (O)
+Status: COMPLETED, alice won
+Prize: 4token
+Stakes: 2token (alice), 2token (bob)
+
+  O  |  X  | 2,0
+-----+-----+-----
+ 0,1 |  X  | 2,1
+-----+-----+-----
+  O  |  X  | 2,2
+"
+    );
+    assert_eq!(chain.balance("alice", "token").unwrap().amount.u128(), 12);
+}
+
+#[test]
+fn invite_with_odds() {
+    // GIVEN
+    let mut chain = local_chain();
+
+    // WHEN
+    ttt(
+        &mut chain,
+        "--sender alice invite --opponent bob --x 1 --y 1 --amount 6token --opponent-amount 2token",
+    )
+    .unwrap();
+    let err = ttt(
+        &mut chain,
+        "--sender bob accept --host alice --x 0 --y 0 --amount 6token",
+    )
+    .unwrap_err();
+    let output = ttt(
+        &mut chain,
+        "--sender bob accept --host alice --x 0 --y 0 --amount 2token",
+    )
+    .unwrap();
+
+    // THEN
+    assert!(err
+        .root_cause()
+        .to_string()
+        .starts_with("[invalid_received_funds]"));
+    assert!(output.contains("Prize: 8token\nStakes: 6token (alice), 2token (bob)\n"));
+    assert_eq!(chain.balance("bob", "token").unwrap().amount.u128(), 8);
+}
+
+#[test]
+fn rematch_a_game() {
+    // GIVEN
+    let mut chain = local_chain();
+    ttt(
+        &mut chain,
+        "--sender alice invite --opponent bob --x 1 --y 1 --amount 2token",
+    )
+    .unwrap();
+    ttt(
+        &mut chain,
+        "--sender bob accept --host alice --x 0 --y 0 --amount 2token",
+    )
+    .unwrap();
+    ttt(&mut chain, "--sender alice play --opponent bob --x 1 --y 0").unwrap();
+    ttt(&mut chain, "--sender bob play --host alice --x 0 --y 2").unwrap();
+    ttt(&mut chain, "--sender alice play --opponent bob --x 1 --y 2").unwrap();
+
+    // WHEN
+    let output = ttt(
+        &mut chain,
+        "--sender bob rematch --host alice --x 1 --y 1 --amount 2token",
+    )
+    .unwrap();
+
+    // THEN
+    assert!(output.starts_with(
+        "method=rematch opponent=alice rematch_of=1 x=1 y=1
+
+Game 2: bob (X) vs alice (O)
+Status: INVITED, alice (O) to play
+Prize: 2token
+Stakes: 2token (bob), 2token (alice)
+"
+    ));
+    assert_eq!(chain.balance("bob", "token").unwrap().amount.u128(), 6);
+}
+
+#[test]
+fn swap_sides_of_a_pie_invite() {
+    // GIVEN
+    let mut chain = local_chain();
+    ttt(
+        &mut chain,
+        "--sender alice invite --opponent bob --x 1 --y 1 --amount 2token --opening pie",
+    )
+    .unwrap();
+
+    // WHEN
+    let output = ttt(&mut chain, "--sender bob swap --host alice --amount 2token").unwrap();
+
+    // THEN
+    assert!(output.starts_with(
+        "method=swap host=alice
+
+Game 1: alice (O) vs bob (X)
+Status: PLAYING, alice (O) to play
+"
+    ));
+}
+
+#[test]
+fn call_the_flag_of_a_timed_game() {
+    // GIVEN
+    let mut chain = local_chain();
+    ttt(
+        &mut chain,
+        "--sender alice invite --opponent bob --x 1 --y 1 --amount 2token --time-control 10+0",
+    )
+    .unwrap();
+    ttt(
+        &mut chain,
+        "--sender bob accept --host alice --x 0 --y 0 --amount 2token",
+    )
+    .unwrap();
+
+    // WHEN
+    // every block is 5 seconds later
+    let early = ttt(&mut chain, "--sender bob call-flag --host alice");
+    let output = ttt(&mut chain, "--sender bob call-flag --host alice").unwrap();
+
+    // THEN
+    assert!(early.is_err());
+    assert!(output.starts_with("method=flag_fallen player=alice status=COMPLETED winner=bob"));
+    assert_eq!(chain.balance("bob", "token").unwrap().amount.u128(), 12);
+}
+
+#[test]
+fn drop_pieces_in_a_gravity_game() {
+    // GIVEN
+    let mut chain = local_chain();
+    ttt(
+        &mut chain,
+        "--sender alice invite --opponent bob --x 3 --y 5 --amount 2token --variant gravity",
+    )
+    .unwrap();
+    ttt(
+        &mut chain,
+        "--sender bob accept --host alice --x 3 --y 4 --amount 2token",
+    )
+    .unwrap();
+
+    // WHEN
+    let output = ttt(&mut chain, "--sender alice drop --opponent bob --column 3").unwrap();
+    let outside = ttt(&mut chain, "--sender bob drop --host alice --column 7");
+
+    // THEN
+    assert!(output.starts_with("method=play x=3 y=3 status=PLAYING"));
+    assert!(outside.is_err());
+}
+
+#[test]
+fn move_a_piece_of_a_morris_game() {
+    // GIVEN
+    let mut chain = local_chain();
+    ttt(
+        &mut chain,
+        "--sender alice invite --opponent bob --x 0 --y 0 --amount 2token --variant morris",
+    )
+    .unwrap();
+    ttt(
+        &mut chain,
+        "--sender bob accept --host alice --x 0 --y 1 --amount 2token",
+    )
+    .unwrap();
+    for (sender, other, x, y) in [
+        ("alice", "--opponent bob", 0, 2),
+        ("bob", "--host alice", 1, 1),
+        ("alice", "--opponent bob", 2, 2),
+        ("bob", "--host alice", 1, 2),
+    ] {
+        let command = format!("--sender {} play {} --x {} --y {}", sender, other, x, y);
+        ttt(&mut chain, &command).unwrap();
+    }
+
+    // WHEN
+    let placed = ttt(&mut chain, "--sender alice play --opponent bob --x 1 --y 0");
+    let output = ttt(
+        &mut chain,
+        "--sender alice move --opponent bob --from-x 0 --from-y 0 --x 1 --y 0",
+    )
+    .unwrap();
+
+    // THEN
+    assert!(placed.is_err());
+    assert!(output.starts_with("method=play x=1 y=0 status=PLAYING opponent=bob from_x=0 from_y=0"));
+}
+
+#[test]
+fn reject_and_list_games() {
+    // GIVEN
+    let mut chain = local_chain();
+    ttt(
+        &mut chain,
+        "--sender alice invite --opponent bob --x 1 --y 1 --amount 2token",
+    )
+    .unwrap();
+
+    // WHEN
+    let output = ttt(&mut chain, "--sender bob reject --host alice").unwrap();
+    ttt(
+        &mut chain,
+        "--sender bob invite --opponent carol --x 0 --y 0 --amount 2token",
+    )
+    .unwrap();
+
+    // THEN
+    assert!(output.starts_with(
+        "method=reject opponent=alice\n\nGame 1: alice (X) vs bob (O)\nStatus: REJECTED\n"
+    ));
+    assert_eq!(
+        ttt(&mut chain, "games").unwrap(),
+        "1\talice\tbob\tREJECTED\n2\tbob\tcarol\tINVITED\n"
+    );
+    assert_eq!(
+        ttt(&mut chain, "games --status invited").unwrap(),
+        "2\tbob\tcarol\tINVITED\n"
+    );
+    assert_eq!(chain.balance("alice", "token").unwrap().amount.u128(), 10);
+}
+
+#[test]
+fn execute_without_sender() {
+    // GIVEN
+    let mut chain = local_chain();
+
+    // WHEN
+    let err = ttt(&mut chain, "invite --opponent bob --x 1 --y 1").unwrap_err();
+
+    // THEN
+    assert_eq!(err.to_string(), "--sender is required to execute messages");
+}
+
+#[test]
+fn play_with_both_players() {
+    // GIVEN
+    let mut chain = local_chain();
+
+    // WHEN
+    let err = ttt(
+        &mut chain,
+        "--sender alice play --host bob --opponent bob --x 1 --y 1",
+    )
+    .unwrap_err();
+
+    // THEN
+    assert!(err.to_string().contains("cannot be used with"));
+}
+
+#[test]
+fn playground_replays_the_log() {
+    // GIVEN
+    let path = std::env::temp_dir().join(format!("ttt-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut playground = Playground::open(&path).unwrap();
+    ttt(
+        &mut playground,
+        "--sender alice invite --opponent bob --x 1 --y 1 --amount 2token",
+    )
+    .unwrap();
+    ttt(
+        &mut playground,
+        "--sender bob accept --host alice --x 0 --y 0 --amount 2token",
+    )
+    .unwrap();
+
+    // WHEN
+    let mut playground = Playground::open(&path).unwrap();
+    let output = ttt(
+        &mut playground,
+        "--sender alice play --opponent bob --x 2 --y 2",
+    )
+    .unwrap();
+
+    // THEN
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.contains("Status: PLAYING, bob (O) to play"));
+    assert!(output.contains("  O  | 1,0 | 2,0\n"));
+}
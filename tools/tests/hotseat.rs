@@ -0,0 +1,293 @@
+use tic_tac_toe::models::errors::ContractError;
+use tic_tac_toe::models::state::{Coord, Difficulty, PlayerSymbol, Status, Variant};
+use tic_tac_toe_tools::hotseat::{HotSeat, HotSeatError};
+
+fn play_all(hot_seat: &mut HotSeat, moves: &[(u8, u8)]) {
+    for (x, y) in moves {
+        hot_seat.play(Coord { x: *x, y: *y }).unwrap();
+    }
+}
+
+#[test]
+fn play_until_a_win() {
+    // GIVEN
+    let mut hot_seat = HotSeat::new();
+    play_all(&mut hot_seat, &[(0, 0), (0, 1), (1, 0), (1, 1)]);
+
+    // WHEN
+    hot_seat.play(Coord { x: 2, y: 0 }).unwrap();
+
+    // THEN
+    let game = hot_seat.game();
+    assert_eq!(game.status, Status::COMPLETED);
+    assert_eq!(game.winner, Some(PlayerSymbol::X));
+    assert_eq!(hot_seat.history().len(), 5);
+    assert_eq!(
+        hot_seat.play(Coord { x: 2, y: 2 }),
+        Err(HotSeatError::Finished {})
+    );
+}
+
+#[test]
+fn reject_invalid_moves() {
+    // GIVEN
+    let mut hot_seat = HotSeat::new();
+    play_all(&mut hot_seat, &[(1, 1)]);
+
+    // WHEN
+    let occupied = hot_seat.play(Coord { x: 1, y: 1 });
+    let outside = hot_seat.play(Coord { x: 3, y: 0 });
+
+    // THEN
+    assert_eq!(
+        occupied,
+        Err(HotSeatError::CoordinateAlreadyPlayed {
+            coord: Coord { x: 1, y: 1 }
+        })
+    );
+    assert_eq!(
+        outside,
+        Err(HotSeatError::InvalidCoord {
+            coord: Coord { x: 3, y: 0 }
+        })
+    );
+    assert_eq!(hot_seat.history().len(), 1);
+    assert_eq!(hot_seat.game().player_round, Some(PlayerSymbol::O));
+}
+
+#[test]
+fn drop_pieces_on_a_gravity_board() {
+    // GIVEN
+    let mut hot_seat = HotSeat::with_variant(Variant::Gravity {
+        columns: 4,
+        rows: 4,
+    })
+    .unwrap();
+    for column in [0, 0, 1, 1, 2, 2] {
+        hot_seat.drop_piece(column).unwrap();
+    }
+
+    // WHEN
+    let outside = hot_seat.drop_piece(4);
+    hot_seat.drop_piece(3).unwrap();
+
+    // THEN
+    let game = hot_seat.game();
+    assert_eq!(
+        outside,
+        Err(HotSeatError::Game(ContractError::InvalidColumn {
+            column: 4,
+            columns: 4
+        }))
+    );
+    assert_eq!(game.status, Status::COMPLETED);
+    assert_eq!(game.winner, Some(PlayerSymbol::X));
+    assert_eq!(game.board[3], vec![Some(PlayerSymbol::X); 4]);
+    assert_eq!(hot_seat.history()[1].coord, Coord { x: 0, y: 2 });
+}
+
+#[test]
+fn undo_on_a_gravity_board() {
+    // GIVEN
+    let mut hot_seat = HotSeat::with_variant(Variant::Gravity {
+        columns: 5,
+        rows: 4,
+    })
+    .unwrap();
+    hot_seat.drop_piece(2).unwrap();
+    hot_seat.drop_piece(2).unwrap();
+
+    // WHEN
+    hot_seat.undo().unwrap();
+
+    // THEN
+    let game = hot_seat.game();
+    assert_eq!(game.board.len(), 4);
+    assert_eq!(game.board[0].len(), 5);
+    assert_eq!(game.board[2][2], None);
+    assert_eq!(game.board[3][2], Some(PlayerSymbol::X));
+    assert_eq!(
+        hot_seat.play(Coord { x: 2, y: 0 }),
+        Err(HotSeatError::Game(ContractError::PieceMustFall {
+            coord: Coord { x: 2, y: 0 },
+            landing: Coord { x: 2, y: 2 }
+        }))
+    );
+}
+
+#[test]
+fn move_pieces_of_a_morris_game() {
+    // GIVEN
+    let mut hot_seat = HotSeat::with_variant(Variant::Morris {}).unwrap();
+    // X O . / . X O / X O . after placing
+    play_all(
+        &mut hot_seat,
+        &[(0, 0), (1, 0), (0, 2), (1, 2), (1, 1), (2, 1)],
+    );
+
+    // WHEN
+    let placing = hot_seat.play(Coord { x: 2, y: 0 });
+    let far = hot_seat.move_piece(Coord { x: 0, y: 0 }, Coord { x: 2, y: 2 });
+    hot_seat
+        .move_piece(Coord { x: 1, y: 1 }, Coord { x: 0, y: 1 })
+        .unwrap();
+
+    // THEN
+    let game = hot_seat.game();
+    assert_eq!(
+        placing,
+        Err(HotSeatError::Game(ContractError::PiecesPlaced {}))
+    );
+    assert!(matches!(
+        far,
+        Err(HotSeatError::Game(ContractError::InvalidPieceMove { .. }))
+    ));
+    assert_eq!(game.status, Status::COMPLETED);
+    assert_eq!(game.winner, Some(PlayerSymbol::X));
+    assert_eq!(hot_seat.history()[6].from, Some(Coord { x: 1, y: 1 }));
+}
+
+#[test]
+fn undo_replays_moved_pieces() {
+    // GIVEN
+    let mut hot_seat = HotSeat::with_variant(Variant::Morris {}).unwrap();
+    play_all(
+        &mut hot_seat,
+        &[(0, 0), (1, 0), (0, 2), (1, 2), (1, 1), (2, 1)],
+    );
+    hot_seat
+        .move_piece(Coord { x: 1, y: 1 }, Coord { x: 2, y: 0 })
+        .unwrap();
+    hot_seat
+        .move_piece(Coord { x: 2, y: 1 }, Coord { x: 2, y: 2 })
+        .unwrap();
+
+    // WHEN
+    hot_seat.undo().unwrap();
+
+    // THEN
+    let game = hot_seat.game();
+    assert_eq!(game.board[0][2], Some(PlayerSymbol::X));
+    assert_eq!(game.board[1][1], None);
+    assert_eq!(game.board[1][2], Some(PlayerSymbol::O));
+    assert_eq!(game.positions.len(), 1);
+    assert_eq!(game.player_round, Some(PlayerSymbol::O));
+    assert_eq!(hot_seat.history().len(), 7);
+}
+
+#[test]
+fn invalid_variant() {
+    // WHEN
+    let res = HotSeat::with_variant(Variant::Gravity {
+        columns: 3,
+        rows: 6,
+    });
+
+    // THEN
+    assert!(matches!(
+        res,
+        Err(HotSeatError::Game(ContractError::InvalidVariant { .. }))
+    ));
+}
+
+#[test]
+fn undo_the_last_move() {
+    // GIVEN
+    let mut hot_seat = HotSeat::new();
+    play_all(&mut hot_seat, &[(0, 0), (0, 1), (1, 0), (1, 1), (2, 0)]);
+
+    // WHEN
+    hot_seat.undo().unwrap();
+
+    // THEN
+    let game = hot_seat.game();
+    assert_eq!(game.status, Status::PLAYING);
+    assert_eq!(game.winner, None);
+    assert_eq!(game.player_round, Some(PlayerSymbol::X));
+    assert_eq!(game.board[0][2], None);
+    assert_eq!(hot_seat.history().len(), 4);
+}
+
+#[test]
+fn undo_without_moves() {
+    // GIVEN
+    let mut hot_seat = HotSeat::new();
+
+    // WHEN
+    let res = hot_seat.undo();
+
+    // THEN
+    assert_eq!(res, Err(HotSeatError::NothingToUndo {}));
+}
+
+#[test]
+fn undo_takes_back_the_answer_of_the_bot() {
+    // GIVEN
+    let mut hot_seat = HotSeat::against_bot(Difficulty::PERFECT, PlayerSymbol::O);
+    play_all(&mut hot_seat, &[(1, 1)]);
+    assert_eq!(hot_seat.history().len(), 2);
+
+    // WHEN
+    hot_seat.undo().unwrap();
+
+    // THEN
+    assert!(hot_seat.history().is_empty());
+    assert_eq!(hot_seat.game().player_round, Some(PlayerSymbol::X));
+    assert_eq!(hot_seat.undo(), Err(HotSeatError::NothingToUndo {}));
+}
+
+#[test]
+fn bot_playing_x_moves_first() {
+    // GIVEN
+    let mut hot_seat = HotSeat::against_bot(Difficulty::MEDIUM, PlayerSymbol::X);
+    assert_eq!(hot_seat.history().len(), 1);
+    assert_eq!(hot_seat.history()[0].symbol, PlayerSymbol::X);
+
+    // WHEN
+    let res = hot_seat.undo();
+
+    // THEN
+    assert_eq!(res, Err(HotSeatError::NothingToUndo {}));
+    assert_eq!(hot_seat.history().len(), 1);
+}
+
+#[test]
+fn perfect_bot_never_loses() {
+    // GIVEN
+    let mut hot_seat = HotSeat::against_bot(Difficulty::PERFECT, PlayerSymbol::O);
+
+    // WHEN
+    while !hot_seat.is_finished() {
+        let coord = (0..9)
+            .map(|cell| Coord {
+                x: cell % 3,
+                y: cell / 3,
+            })
+            .find(|coord| !hot_seat.game().already_played_on(*coord).unwrap())
+            .unwrap();
+        hot_seat.play(coord).unwrap();
+    }
+
+    // THEN
+    assert_ne!(hot_seat.game().winner, Some(PlayerSymbol::X));
+}
+
+#[test]
+fn export_only_finished_games() {
+    // GIVEN
+    let mut hot_seat = HotSeat::new();
+    play_all(&mut hot_seat, &[(0, 0), (0, 1), (1, 0), (1, 1)]);
+    assert_eq!(hot_seat.export(), None);
+
+    // WHEN
+    hot_seat.play(Coord { x: 2, y: 0 }).unwrap();
+
+    // THEN
+    let export = hot_seat.export().unwrap();
+    assert_eq!(export.moves, hot_seat.history());
+    assert_eq!(export.bot, None);
+    assert_eq!(&export.game, hot_seat.game());
+    let json = serde_json::to_value(&export).unwrap();
+    assert_eq!(json["moves"][4]["coord"]["x"], 2);
+    assert_eq!(json["game"]["winner"], "X");
+}
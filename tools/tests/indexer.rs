@@ -0,0 +1,780 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use cosmwasm_std::{coins, Uint128};
+use cw_utils::Expiration;
+use tic_tac_toe::models::{
+    responses::GameResponse,
+    state::{
+        Coord, Difficulty, Opening, Pause, PayoutMode, PlayerSymbol, Status, TimeControl, Variant,
+    },
+    ExecuteMsg, InstantiateMsg, QueryKey, QueryMsg,
+};
+use tic_tac_toe_tools::indexer::{Indexer, IndexerError};
+use tic_tac_toe_tools::local::LocalChain;
+use tic_tac_toe_tools::log::{read_log, write_log, LogEntry};
+
+const PLAYERS: [&str; 11] = [
+    "alice", "bob", "carol", "dave", "erin", "frank", "grace", "heidi", "ivan", "judy", "mallory",
+];
+
+fn fixture_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/games.jsonl")
+}
+
+fn invite(chain: &mut LocalChain, host: &str, opponent: &str, x: u8, y: u8) {
+    chain
+        .execute(
+            host,
+            ExecuteMsg::Invite {
+                coord: Coord { x, y },
+                host_symbol: PlayerSymbol::X,
+                opponent: opponent.to_string(),
+                opponent_stake: None,
+                opening: None,
+                time_control: None,
+                variant: None,
+                practice: None,
+            },
+            &coins(2, "token"),
+        )
+        .unwrap();
+}
+
+fn accept(chain: &mut LocalChain, opponent: &str, host: &str, x: u8, y: u8) {
+    chain
+        .execute(
+            opponent,
+            ExecuteMsg::Accept {
+                coord: Coord { x, y },
+                host: host.to_string(),
+            },
+            &coins(2, "token"),
+        )
+        .unwrap();
+}
+
+fn play(chain: &mut LocalChain, player: &str, opponent: &str, as_host: bool, x: u8, y: u8) {
+    chain
+        .execute(
+            player,
+            ExecuteMsg::Play {
+                as_host,
+                coord: Coord { x, y },
+                opponent: opponent.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+fn query_game(chain: &LocalChain, host: &str, opponent: &str) -> GameResponse {
+    let games: Vec<GameResponse> = chain
+        .query(&QueryMsg::Games {
+            key: Some(QueryKey {
+                host: host.to_string(),
+                opponent: opponent.to_string(),
+            }),
+            status: None,
+        })
+        .unwrap();
+    games[0].clone()
+}
+
+/**
+    Plays one game of every kind, each one between
+    a different pair so all of them can be queried.
+*/
+fn record_games() -> LocalChain {
+    let mut balances: Vec<(&str, Vec<_>)> = PLAYERS
+        .iter()
+        .map(|player| (*player, coins(10, "token")))
+        .collect();
+    balances.push(("admin", coins(100, "token")));
+    let mut chain = LocalChain::new(
+        &balances,
+        InstantiateMsg {
+            house_difficulty: Some(Difficulty::RANDOM),
+            house_max_exposure: Some(coins(10, "token")),
+            ..InstantiateMsg::default()
+        },
+    )
+    .unwrap();
+
+    // alice wins against bob
+    invite(&mut chain, "alice", "bob", 0, 0);
+    accept(&mut chain, "bob", "alice", 1, 0);
+    play(&mut chain, "alice", "bob", true, 0, 1);
+    play(&mut chain, "bob", "alice", false, 1, 1);
+    play(&mut chain, "alice", "bob", true, 0, 2);
+
+    // dave rejects carol
+    invite(&mut chain, "carol", "dave", 1, 1);
+    chain
+        .execute(
+            "dave",
+            ExecuteMsg::Reject {
+                as_host: false,
+                opponent: String::from("carol"),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // erin and frank agree a draw
+    invite(&mut chain, "erin", "frank", 1, 1);
+    accept(&mut chain, "frank", "erin", 0, 0);
+    for (player, opponent, as_host) in [("erin", "frank", true), ("frank", "erin", false)] {
+        chain
+            .execute(
+                player,
+                ExecuteMsg::OfferDraw {
+                    as_host,
+                    opponent: opponent.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    // heidi resigns against grace
+    invite(&mut chain, "grace", "heidi", 2, 2);
+    accept(&mut chain, "heidi", "grace", 0, 0);
+    chain
+        .execute(
+            "heidi",
+            ExecuteMsg::Resign {
+                as_host: false,
+                opponent: String::from("grace"),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // ivan and judy are still playing
+    invite(&mut chain, "ivan", "judy", 0, 0);
+    accept(&mut chain, "judy", "ivan", 2, 2);
+    play(&mut chain, "ivan", "judy", true, 1, 1);
+
+    // mallory plays against the house until the game finishes
+    chain
+        .execute("admin", ExecuteMsg::FundHouse {}, &coins(100, "token"))
+        .unwrap();
+    let house = chain.contract.to_string();
+    invite(&mut chain, "mallory", &house, 1, 1);
+    loop {
+        let res = query_game(&chain, "mallory", &house);
+        if res.game.status != Status::PLAYING {
+            break;
+        }
+        let coord = (0..9)
+            .map(|cell| Coord {
+                x: cell % 3,
+                y: cell / 3,
+            })
+            .find(|coord| !res.game.already_played_on(*coord).unwrap())
+            .unwrap();
+        play(&mut chain, "mallory", &house, true, coord.x, coord.y);
+    }
+
+    chain
+}
+
+#[test]
+fn fixture_matches_recorded_games() {
+    let chain = record_games();
+    if std::env::var("UPDATE_FIXTURES").is_ok() {
+        write_log(File::create(fixture_path()).unwrap(), &chain.log).unwrap();
+    }
+
+    let fixture = read_log(BufReader::new(File::open(fixture_path()).unwrap())).unwrap();
+
+    assert_eq!(
+        fixture, chain.log,
+        "the contract events changed, run the tests with UPDATE_FIXTURES=1"
+    );
+}
+
+#[test]
+fn index_fixture_matches_contract_queries() {
+    // GIVEN
+    let chain = record_games();
+    let fixture = read_log(BufReader::new(File::open(fixture_path()).unwrap())).unwrap();
+    let mut indexer = Indexer::in_memory().unwrap();
+
+    // WHEN
+    for entry in &fixture {
+        indexer.ingest(entry).unwrap();
+    }
+
+    // THEN
+    let games = indexer.games().unwrap();
+    let queried: Vec<GameResponse> = chain
+        .query(&QueryMsg::Games {
+            key: None,
+            status: None,
+        })
+        .unwrap();
+    assert_eq!(games.len(), 6);
+    assert_eq!(games.len(), queried.len());
+    for game in &games {
+        assert_eq!(
+            *game,
+            query_game(&chain, game.host.as_str(), game.opponent.as_str())
+        );
+        assert_eq!(
+            indexer.moves(game.game.id).unwrap().len() as u32,
+            game.game.moves_count()
+        );
+    }
+
+    let mut paid: BTreeMap<String, Uint128> = BTreeMap::new();
+    for payout in indexer.payouts().unwrap() {
+        *paid.entry(payout.recipient.to_string()).or_default() += payout.amount[0].amount;
+    }
+    for player in PLAYERS {
+        let staked: Uint128 = fixture
+            .iter()
+            .filter(|entry| entry.sender == player)
+            .flat_map(|entry| entry.funds.iter().map(|coin| coin.amount))
+            .sum();
+        let balance = Uint128::new(10) - staked + paid.get(player).copied().unwrap_or_default();
+        assert_eq!(
+            chain.balance(player, "token").unwrap().amount,
+            balance,
+            "balance of {}",
+            player
+        );
+    }
+
+    let stats = indexer.stats().unwrap();
+    assert_eq!(stats.games, 6);
+    assert_eq!(stats.by_status["COMPLETED"], 4);
+    assert_eq!(stats.by_status["PLAYING"], 1);
+    assert_eq!(stats.by_status["REJECTED"], 1);
+    assert_eq!(stats.wins[0].1, 1);
+}
+
+#[test]
+fn index_voided_game() {
+    // GIVEN
+    let mut chain = LocalChain::new(
+        &[("alice", coins(10, "token")), ("bob", coins(10, "token"))],
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    invite(&mut chain, "alice", "bob", 0, 0);
+    accept(&mut chain, "bob", "alice", 1, 1);
+    chain
+        .execute("admin", ExecuteMsg::UpdatePause { pause: Pause::ALL }, &[])
+        .unwrap();
+    chain
+        .execute(
+            "bob",
+            ExecuteMsg::EmergencyWithdraw {
+                as_host: false,
+                opponent: String::from("alice"),
+            },
+            &[],
+        )
+        .unwrap();
+    let mut indexer = Indexer::in_memory().unwrap();
+
+    // WHEN
+    for entry in &chain.log {
+        indexer.ingest(entry).unwrap();
+    }
+
+    // THEN
+    let game = indexer.game(1).unwrap().unwrap();
+    assert_eq!(game.game.status, Status::VOIDED);
+    assert_eq!(game, query_game(&chain, "alice", "bob"));
+    assert_eq!(indexer.payouts().unwrap().len(), 2);
+}
+
+#[test]
+fn index_sponsored_game() {
+    // GIVEN
+    let mut chain = LocalChain::new(
+        &[
+            ("alice", coins(10, "token")),
+            ("bob", coins(10, "token")),
+            ("sponsor", coins(10, "token")),
+        ],
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    chain
+        .execute(
+            "sponsor",
+            ExecuteMsg::Sponsor {
+                host: String::from("alice"),
+                opponent: String::from("bob"),
+                expires: Expiration::Never {},
+            },
+            &coins(6, "token"),
+        )
+        .unwrap();
+    invite(&mut chain, "alice", "bob", 0, 0);
+    accept(&mut chain, "bob", "alice", 1, 1);
+    let mut indexer = Indexer::in_memory().unwrap();
+
+    // WHEN
+    for entry in &chain.log {
+        indexer.ingest(entry).unwrap();
+    }
+
+    // THEN
+    let game = indexer.game(1).unwrap().unwrap();
+    assert_eq!(game.game.prize, coins(10, "token"));
+    assert_eq!(game, query_game(&chain, "alice", "bob"));
+}
+
+#[test]
+fn index_practice_game() {
+    // GIVEN
+    let mut chain = LocalChain::new(&[], InstantiateMsg::default()).unwrap();
+    chain
+        .execute(
+            "alice",
+            ExecuteMsg::Invite {
+                coord: Coord { x: 0, y: 0 },
+                host_symbol: PlayerSymbol::X,
+                opponent: String::from("bob"),
+                opponent_stake: None,
+                opening: None,
+                time_control: None,
+                variant: None,
+                practice: Some(true),
+            },
+            &[],
+        )
+        .unwrap();
+    chain
+        .execute(
+            "bob",
+            ExecuteMsg::Accept {
+                coord: Coord { x: 1, y: 1 },
+                host: String::from("alice"),
+            },
+            &[],
+        )
+        .unwrap();
+    let mut indexer = Indexer::in_memory().unwrap();
+
+    // WHEN
+    for entry in &chain.log {
+        indexer.ingest(entry).unwrap();
+    }
+
+    // THEN
+    let game = indexer.game(1).unwrap().unwrap();
+    assert!(game.game.practice);
+    assert_eq!(game, query_game(&chain, "alice", "bob"));
+}
+
+fn resign(chain: &mut LocalChain, player: &str, opponent: &str, as_host: bool) {
+    chain
+        .execute(
+            player,
+            ExecuteMsg::Resign {
+                as_host,
+                opponent: opponent.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+/**
+    Every rematch stakes 4, which is the prize of the first game
+    reinvested and then the stake of that rematch. The reinvested
+    prize is held back from the claims so nothing is sent.
+*/
+fn rematch(
+    chain: &mut LocalChain,
+    player: &str,
+    game: (&str, &str),
+    coord: Option<Coord>,
+    double_or_nothing: bool,
+) {
+    let funds = if double_or_nothing {
+        vec![]
+    } else {
+        coins(4, "token")
+    };
+    chain
+        .execute(
+            player,
+            ExecuteMsg::Rematch {
+                game: QueryKey {
+                    host: game.0.to_string(),
+                    opponent: game.1.to_string(),
+                },
+                coord,
+                double_or_nothing,
+            },
+            &funds,
+        )
+        .unwrap();
+}
+
+fn accept_rematch(chain: &mut LocalChain, x: u8, y: u8) {
+    chain
+        .execute(
+            "alice",
+            ExecuteMsg::Accept {
+                coord: Coord { x, y },
+                host: String::from("bob"),
+            },
+            &coins(4, "token"),
+        )
+        .unwrap();
+}
+
+#[test]
+fn index_rematches() {
+    // GIVEN
+    let mut chain = LocalChain::new(
+        &[("alice", coins(20, "token")), ("bob", coins(20, "token"))],
+        InstantiateMsg {
+            payout_mode: Some(PayoutMode::PULL),
+            ..InstantiateMsg::default()
+        },
+    )
+    .unwrap();
+    invite(&mut chain, "alice", "bob", 0, 0);
+    accept(&mut chain, "bob", "alice", 1, 1);
+    resign(&mut chain, "alice", "bob", true);
+    // bob reinvests the prize and plays first
+    let coord = Some(Coord { x: 0, y: 0 });
+    rematch(&mut chain, "bob", ("alice", "bob"), coord, true);
+    accept_rematch(&mut chain, 1, 1);
+    resign(&mut chain, "alice", "bob", false);
+    // bob hosts again but alice plays first
+    rematch(&mut chain, "bob", ("bob", "alice"), None, false);
+    accept_rematch(&mut chain, 2, 2);
+    let mut indexer = Indexer::in_memory().unwrap();
+
+    // WHEN
+    for entry in &chain.log {
+        indexer.ingest(entry).unwrap();
+    }
+
+    // THEN
+    let double_or_nothing = indexer.game(2).unwrap().unwrap();
+    let rematch = indexer.game(3).unwrap().unwrap();
+    assert_eq!(double_or_nothing.game.prize, coins(8, "token"));
+    assert_eq!(double_or_nothing.game.status, Status::COMPLETED);
+    assert!(rematch.game.opponent_first);
+    assert_eq!(rematch, query_game(&chain, "bob", "alice"));
+    assert_eq!(indexer.moves(3).unwrap().len(), 1);
+}
+
+#[test]
+fn index_swapped_sides() {
+    // GIVEN
+    let mut chain = LocalChain::new(
+        &[
+            ("alice", coins(20, "token")),
+            ("bob", coins(20, "token")),
+            ("carol", coins(20, "token")),
+        ],
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    chain
+        .execute(
+            "alice",
+            ExecuteMsg::Invite {
+                coord: Coord { x: 2, y: 2 },
+                host_symbol: PlayerSymbol::X,
+                opponent: String::from("bob"),
+                opponent_stake: None,
+                opening: Some(Opening::Cell {
+                    coord: Coord { x: 2, y: 2 },
+                }),
+                time_control: None,
+                variant: None,
+                practice: None,
+            },
+            &coins(2, "token"),
+        )
+        .unwrap();
+    accept(&mut chain, "bob", "alice", 1, 1);
+    chain
+        .execute(
+            "alice",
+            ExecuteMsg::Invite {
+                coord: Coord { x: 0, y: 0 },
+                host_symbol: PlayerSymbol::X,
+                opponent: String::from("carol"),
+                opponent_stake: None,
+                opening: Some(Opening::Pie {}),
+                time_control: None,
+                variant: None,
+                practice: None,
+            },
+            &coins(2, "token"),
+        )
+        .unwrap();
+    chain
+        .execute(
+            "carol",
+            ExecuteMsg::Swap {
+                host: String::from("alice"),
+            },
+            &coins(2, "token"),
+        )
+        .unwrap();
+    play(&mut chain, "alice", "carol", true, 1, 1);
+    let mut indexer = Indexer::in_memory().unwrap();
+
+    // WHEN
+    for entry in &chain.log {
+        indexer.ingest(entry).unwrap();
+    }
+
+    // THEN
+    let cell = indexer.game(1).unwrap().unwrap();
+    let swapped = indexer.game(2).unwrap().unwrap();
+    assert_eq!(cell, query_game(&chain, "alice", "bob"));
+    assert_eq!(swapped.game.host_symbol, PlayerSymbol::O);
+    assert_eq!(swapped, query_game(&chain, "alice", "carol"));
+    assert_eq!(indexer.moves(2).unwrap().len(), 2);
+}
+
+fn timed_invite(chain: &mut LocalChain, host: &str, opponent: &str, increment: u64) {
+    chain
+        .execute(
+            host,
+            ExecuteMsg::Invite {
+                coord: Coord { x: 0, y: 0 },
+                host_symbol: PlayerSymbol::X,
+                opponent: opponent.to_string(),
+                opponent_stake: None,
+                opening: None,
+                time_control: Some(TimeControl {
+                    base: 10,
+                    increment,
+                }),
+                variant: None,
+                practice: None,
+            },
+            &coins(2, "token"),
+        )
+        .unwrap();
+}
+
+#[test]
+fn index_flags_fallen() {
+    // GIVEN
+    let mut chain = LocalChain::new(
+        &[
+            ("alice", coins(20, "token")),
+            ("bob", coins(20, "token")),
+            ("carol", coins(20, "token")),
+        ],
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    // every block is 5 seconds later
+    timed_invite(&mut chain, "alice", "bob", 0);
+    accept(&mut chain, "bob", "alice", 1, 1);
+    timed_invite(&mut chain, "alice", "carol", 2);
+    accept(&mut chain, "carol", "alice", 1, 1);
+    chain
+        .execute(
+            "bob",
+            ExecuteMsg::CallFlag {
+                as_host: false,
+                opponent: String::from("alice"),
+            },
+            &[],
+        )
+        .unwrap();
+    play(&mut chain, "alice", "carol", true, 2, 2);
+    let mut indexer = Indexer::in_memory().unwrap();
+
+    // WHEN
+    for entry in &chain.log {
+        indexer.ingest(entry).unwrap();
+    }
+
+    // THEN
+    let called = indexer.game(1).unwrap().unwrap();
+    let played = indexer.game(2).unwrap().unwrap();
+    assert_eq!(called, query_game(&chain, "alice", "bob"));
+    assert_eq!(called.game.winner, Some(PlayerSymbol::O));
+    assert_eq!(played, query_game(&chain, "alice", "carol"));
+    assert_eq!(played.game.status, Status::COMPLETED);
+    assert_eq!(indexer.moves(2).unwrap().len(), 2);
+}
+
+#[test]
+fn index_gravity_games() {
+    // GIVEN
+    let mut chain = LocalChain::new(
+        &[("alice", coins(20, "token")), ("bob", coins(20, "token"))],
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    chain
+        .execute(
+            "alice",
+            ExecuteMsg::Invite {
+                coord: Coord { x: 0, y: 5 },
+                host_symbol: PlayerSymbol::X,
+                opponent: String::from("bob"),
+                opponent_stake: None,
+                opening: None,
+                time_control: None,
+                variant: Some(Variant::Gravity {
+                    columns: 7,
+                    rows: 6,
+                }),
+                practice: None,
+            },
+            &coins(2, "token"),
+        )
+        .unwrap();
+    accept(&mut chain, "bob", "alice", 0, 4);
+    for column in 1..4 {
+        chain
+            .execute(
+                "alice",
+                ExecuteMsg::DropPiece {
+                    as_host: true,
+                    column,
+                    opponent: String::from("bob"),
+                },
+                &[],
+            )
+            .unwrap();
+        if column < 3 {
+            play(&mut chain, "bob", "alice", false, 0, 4 - column);
+        }
+    }
+    let mut indexer = Indexer::in_memory().unwrap();
+
+    // WHEN
+    for entry in &chain.log {
+        indexer.ingest(entry).unwrap();
+    }
+
+    // THEN
+    let indexed = indexer.game(1).unwrap().unwrap();
+    assert_eq!(indexed, query_game(&chain, "alice", "bob"));
+    assert_eq!(indexed.game.board.len(), 6);
+    assert_eq!(indexed.game.winner, Some(PlayerSymbol::X));
+    assert_eq!(indexer.moves(1).unwrap().len(), 7);
+}
+
+#[test]
+fn index_morris_games() {
+    // GIVEN
+    let mut chain = LocalChain::new(
+        &[("alice", coins(20, "token")), ("bob", coins(20, "token"))],
+        InstantiateMsg::default(),
+    )
+    .unwrap();
+    chain
+        .execute(
+            "alice",
+            ExecuteMsg::Invite {
+                coord: Coord { x: 0, y: 0 },
+                host_symbol: PlayerSymbol::X,
+                opponent: String::from("bob"),
+                opponent_stake: None,
+                opening: None,
+                time_control: None,
+                variant: Some(Variant::Morris {}),
+                practice: None,
+            },
+            &coins(2, "token"),
+        )
+        .unwrap();
+    accept(&mut chain, "bob", "alice", 1, 0);
+    play(&mut chain, "alice", "bob", true, 0, 2);
+    play(&mut chain, "bob", "alice", false, 1, 2);
+    play(&mut chain, "alice", "bob", true, 1, 1);
+    play(&mut chain, "bob", "alice", false, 2, 1);
+    chain
+        .execute(
+            "alice",
+            ExecuteMsg::MovePiece {
+                as_host: true,
+                from: Coord { x: 1, y: 1 },
+                to: Coord { x: 0, y: 1 },
+                opponent: String::from("bob"),
+            },
+            &[],
+        )
+        .unwrap();
+    let mut indexer = Indexer::in_memory().unwrap();
+
+    // WHEN
+    for entry in &chain.log {
+        indexer.ingest(entry).unwrap();
+    }
+
+    // THEN
+    let indexed = indexer.game(1).unwrap().unwrap();
+    assert_eq!(indexed, query_game(&chain, "alice", "bob"));
+    assert_eq!(indexed.game.positions.len(), 1);
+    assert_eq!(indexed.game.winner, Some(PlayerSymbol::X));
+    assert_eq!(indexer.moves(1).unwrap().len(), 7);
+}
+
+#[test]
+fn ingest_tampered_move() {
+    // GIVEN
+    let fixture = read_log(BufReader::new(File::open(fixture_path()).unwrap())).unwrap();
+    let mut indexer = Indexer::in_memory().unwrap();
+    let tampered: Vec<LogEntry> = fixture
+        .into_iter()
+        .take(3)
+        .map(|mut entry| {
+            for event in &mut entry.events {
+                for attr in &mut event.attributes {
+                    if event.ty == "wasm-tic_tac_toe.move_played" && attr.key == "x" {
+                        attr.value = String::from("2");
+                    }
+                }
+            }
+            entry
+        })
+        .collect();
+    indexer.ingest(&tampered[0]).unwrap();
+
+    // WHEN
+    let err = indexer.ingest(&tampered[1]).unwrap_err();
+
+    // THEN
+    assert!(matches!(err, IndexerError::Drift { game_id: 1, .. }));
+    assert_eq!(indexer.moves(1).unwrap().len(), 1);
+}
+
+#[test]
+fn index_from_the_events_alone() {
+    // GIVEN
+    let chain = record_games();
+    let fixture = read_log(BufReader::new(File::open(fixture_path()).unwrap())).unwrap();
+    let mut indexer = Indexer::in_memory().unwrap();
+
+    // WHEN
+    for mut entry in fixture {
+        entry.msg = ExecuteMsg::Claim {};
+        indexer.ingest(&entry).unwrap();
+    }
+
+    // THEN
+    for game in indexer.games().unwrap() {
+        assert_eq!(
+            game,
+            query_game(&chain, game.host.as_str(), game.opponent.as_str())
+        );
+    }
+}
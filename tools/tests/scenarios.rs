@@ -0,0 +1,129 @@
+use std::fs;
+use std::path::PathBuf;
+
+use tic_tac_toe_tools::scenario::{Scenario, ScenarioError};
+
+fn scenarios_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios")
+}
+
+fn parse(yaml: &str) -> Scenario {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+#[test]
+fn run_every_scenario() {
+    // GIVEN
+    let mut files: Vec<PathBuf> = fs::read_dir(scenarios_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+        .collect();
+    files.sort();
+    assert!(!files.is_empty());
+
+    // WHEN
+    let failures: Vec<String> = files
+        .iter()
+        .filter_map(|file| {
+            let res = Scenario::load(file)
+                .map_err(|err| err.to_string())
+                .and_then(|scenario| scenario.run().map_err(|err| err.to_string()));
+            res.err().map(|err| format!("{}: {}", file.display(), err))
+        })
+        .collect();
+
+    // THEN
+    assert_eq!(failures, Vec::<String>::new());
+}
+
+#[test]
+fn report_unexpected_success() {
+    // GIVEN
+    let scenario = parse(
+        r#"
+name: invite
+actors:
+  host: 2token
+steps:
+  - sender: host
+    funds: 2token
+    msg:
+      invite: { coord: { x: 1, y: 1 }, host_symbol: X, opponent: opponent }
+    error: Unauthorized
+"#,
+    );
+
+    // WHEN
+    let res = scenario.run();
+
+    // THEN
+    match res {
+        Err(ScenarioError::MissingError { step, expected }) => {
+            assert_eq!(step, 1);
+            assert_eq!(expected, "Unauthorized");
+        }
+        _ => panic!("expected a missing error"),
+    }
+}
+
+#[test]
+fn report_wrong_balance() {
+    // GIVEN
+    let scenario = parse(
+        r#"
+name: invite
+actors:
+  host: 2token
+steps:
+  - sender: host
+    funds: 2token
+    msg:
+      invite: { coord: { x: 1, y: 1 }, host_symbol: X, opponent: opponent }
+expect:
+  balances:
+    host: 2token
+"#,
+    );
+
+    // WHEN
+    let res = scenario.run();
+
+    // THEN
+    assert_eq!(
+        res.err().unwrap().to_string(),
+        "Balance of host is 0token instead of 2token"
+    );
+}
+
+#[test]
+fn report_wrong_board() {
+    // GIVEN
+    let scenario = parse(
+        r#"
+name: invite
+actors:
+  host: 2token
+steps:
+  - sender: host
+    funds: 2token
+    msg:
+      invite: { coord: { x: 1, y: 1 }, host_symbol: X, opponent: opponent }
+expect:
+  games:
+    - host: host
+      opponent: opponent
+      status: INVITED
+      board: ["X..", "...", "..."]
+"#,
+    );
+
+    // WHEN
+    let res = scenario.run();
+
+    // THEN
+    assert_eq!(
+        res.err().unwrap().to_string(),
+        "Game between host and opponent has board .../.X./... instead of X../.../..."
+    );
+}